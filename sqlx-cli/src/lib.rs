@@ -21,8 +21,11 @@ pub async fn run(opt: Opt) -> anyhow::Result<()> {
     match opt.command {
         Command::Migrate(migrate) => match migrate.command {
             MigrateCommand::Add { description } => migrate::add(&description)?,
-            MigrateCommand::Run => migrate::run(&database_url).await?,
-            MigrateCommand::Info => migrate::info(&database_url).await?,
+            MigrateCommand::Run { ignore_checksums } => {
+                migrate::run(&database_url, &migrate.table_name, ignore_checksums).await?
+            }
+            MigrateCommand::Revert => migrate::revert(&database_url, &migrate.table_name).await?,
+            MigrateCommand::Info => migrate::info(&database_url, &migrate.table_name).await?,
         },
 
         Command::Database(database) => match database.command {
@@ -30,9 +33,21 @@ pub async fn run(opt: Opt) -> anyhow::Result<()> {
             DatabaseCommand::Drop { yes } => database::drop(&database_url, !yes).await?,
         },
 
-        Command::Prepare { check: false, args } => prepare::run(&database_url, args)?,
-
-        Command::Prepare { check: true, args } => prepare::check(&database_url, args)?,
+        Command::Prepare { split: true, .. } => prepare::split()?,
+
+        Command::Prepare {
+            check: false,
+            sharded,
+            args,
+            ..
+        } => prepare::run(&database_url, sharded, args)?,
+
+        Command::Prepare {
+            check: true,
+            sharded,
+            args,
+            ..
+        } => prepare::check(&database_url, sharded, args)?,
     };
 
     Ok(())
@@ -33,13 +33,13 @@ pub fn add(description: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub async fn info(uri: &str) -> anyhow::Result<()> {
+pub async fn info(uri: &str, table_name: &str) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(MIGRATION_FOLDER)).await?;
     let mut conn = AnyConnection::connect(uri).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table(table_name).await?;
 
-    let (version, _) = conn.version().await?.unwrap_or((0, false));
+    let (version, _) = conn.version(table_name).await?.unwrap_or((0, false));
 
     for migration in migrator.iter() {
         println!(
@@ -57,13 +57,16 @@ pub async fn info(uri: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub async fn run(uri: &str) -> anyhow::Result<()> {
+pub async fn run(uri: &str, table_name: &str, ignore_checksums: bool) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(MIGRATION_FOLDER)).await?;
     let mut conn = AnyConnection::connect(uri).await?;
 
-    conn.ensure_migrations_table().await?;
+    // should anything fail, the lock is released as the connection is closed on exit
+    conn.lock().await?;
 
-    let (version, dirty) = conn.version().await?.unwrap_or((0, false));
+    conn.ensure_migrations_table(table_name).await?;
+
+    let (version, dirty) = conn.version(table_name).await?.unwrap_or((0, false));
 
     if dirty {
         bail!(MigrateError::Dirty(version));
@@ -71,7 +74,7 @@ pub async fn run(uri: &str) -> anyhow::Result<()> {
 
     for migration in migrator.iter() {
         if migration.version() > version {
-            let elapsed = conn.apply(migration).await?;
+            let elapsed = conn.apply(table_name, migration).await?;
 
             println!(
                 "{}/{} {} {}",
@@ -81,9 +84,59 @@ pub async fn run(uri: &str) -> anyhow::Result<()> {
                 style(format!("({:?})", elapsed)).dim()
             );
         } else {
-            conn.validate(migration).await?;
+            match conn.validate(table_name, migration).await {
+                Err(MigrateError::VersionMismatch(_)) if ignore_checksums => {}
+                res => res?,
+            }
         }
     }
 
+    conn.unlock().await?;
+
+    Ok(())
+}
+
+pub async fn revert(uri: &str, table_name: &str) -> anyhow::Result<()> {
+    let migrator = Migrator::new(Path::new(MIGRATION_FOLDER)).await?;
+    let mut conn = AnyConnection::connect(uri).await?;
+
+    conn.lock().await?;
+
+    conn.ensure_migrations_table(table_name).await?;
+
+    let (version, dirty) = conn.version(table_name).await?.unwrap_or((0, false));
+
+    if dirty {
+        bail!(MigrateError::Dirty(version));
+    }
+
+    let version = match conn.list_applied_migrations(table_name).await?.last() {
+        Some(&version) => version,
+        None => {
+            println!("No migrations to revert");
+            conn.unlock().await?;
+            return Ok(());
+        }
+    };
+
+    let migration = migrator
+        .iter()
+        .find(|migration| migration.version() == version)
+        .ok_or(MigrateError::VersionMissing(version))?;
+
+    conn.validate(table_name, migration).await?;
+
+    let elapsed = conn.revert(table_name, migration).await?;
+
+    println!(
+        "{}/{} {} {}",
+        style(migration.version()).cyan(),
+        style("revert").green(),
+        migration.description(),
+        style(format!("({:?})", elapsed)).dim()
+    );
+
+    conn.unlock().await?;
+
     Ok(())
 }
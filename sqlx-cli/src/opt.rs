@@ -26,6 +26,17 @@ pub enum Command {
         #[clap(long)]
         check: bool,
 
+        /// Save the metadata of each query to its own file, `.sqlx/query-<hash>.json`, instead
+        /// of `sqlx-data.json`. As unrelated queries no longer share a file, this avoids most
+        /// merge conflicts.
+        #[clap(long)]
+        sharded: bool,
+
+        /// Split an existing `sqlx-data.json` into one file per query in `.sqlx/`, as saved
+        /// with `--sharded`, without recompiling the project.
+        #[clap(long, conflicts_with = "check")]
+        split: bool,
+
         /// Arguments to be passed to `cargo rustc ...`.
         #[clap(last = true)]
         args: Vec<String>,
@@ -59,6 +70,11 @@ pub enum DatabaseCommand {
 /// Group of commands for creating and running migrations.
 #[derive(Clap, Debug)]
 pub struct MigrateOpt {
+    /// Name of the table that tracks the applied migrations, optionally qualified with
+    /// a schema.
+    #[clap(long, default_value = "_sqlx_migrations")]
+    pub table_name: String,
+
     #[clap(subcommand)]
     pub command: MigrateCommand,
 }
@@ -70,7 +86,15 @@ pub enum MigrateCommand {
     Add { description: String },
 
     /// Run all pending migrations.
-    Run,
+    Run {
+        /// Accept previously applied migrations that have been modified since. Without this
+        /// option, a modified migration is an error.
+        #[clap(long)]
+        ignore_checksums: bool,
+    },
+
+    /// Revert the latest applied migration with its down script.
+    Revert,
 
     /// List all available migrations.
     Info,
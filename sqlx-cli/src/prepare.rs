@@ -4,6 +4,7 @@ use console::style;
 use sqlx::any::{AnyConnectOptions, AnyKind};
 use std::collections::BTreeMap;
 use std::fs::File;
+use std::path::Path;
 use std::process::Command;
 use std::str::FromStr;
 use std::time::SystemTime;
@@ -12,14 +13,10 @@ use std::{env, fs};
 type QueryData = BTreeMap<String, serde_json::Value>;
 type JsonObject = serde_json::Map<String, serde_json::Value>;
 
-pub fn run(url: &str, cargo_args: Vec<String>) -> anyhow::Result<()> {
-    #[derive(serde::Serialize)]
-    struct DataFile {
-        db: &'static str,
-        #[serde(flatten)]
-        data: QueryData,
-    }
+/// The directory that `prepare --sharded` saves query data to.
+const QUERY_DIR: &str = ".sqlx";
 
+pub fn run(url: &str, sharded: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
     let db_kind = get_db_kind(url)?;
     let data = run_prepare_step(cargo_args)?;
 
@@ -30,59 +27,194 @@ pub fn run(url: &str, cargo_args: Vec<String>) -> anyhow::Result<()> {
         );
     }
 
-    serde_json::to_writer_pretty(
-        File::create("sqlx-data.json").context("failed to create/open `sqlx-data.json`")?,
-        &DataFile { db: db_kind, data },
-    )
-    .context("failed to write to `sqlx-data.json`")?;
+    if sharded {
+        write_query_files(Path::new(QUERY_DIR), db_kind, &data)?;
 
-    println!(
-        "query data written to `sqlx-data.json` in the current directory; \
-         please check this into version control"
-    );
+        println!(
+            "query data written to `.sqlx/` in the current directory; \
+             please check this into version control"
+        );
+    } else {
+        write_data_file(db_kind, data)?;
+
+        println!(
+            "query data written to `sqlx-data.json` in the current directory; \
+             please check this into version control"
+        );
+    }
 
     Ok(())
 }
 
-pub fn check(url: &str, cargo_args: Vec<String>) -> anyhow::Result<()> {
+pub fn check(url: &str, sharded: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
     let db_kind = get_db_kind(url)?;
     let data = run_prepare_step(cargo_args)?;
 
+    let (expected_db, saved_data) = if sharded {
+        read_query_files(Path::new(QUERY_DIR))?
+    } else {
+        read_data_file()?
+    };
+
+    if db_kind != expected_db {
+        bail!(
+            "saved prepare data is for {}, not {} (inferred from `DATABASE_URL`)",
+            expected_db,
+            db_kind
+        )
+    }
+
+    if data != saved_data {
+        bail!("`cargo sqlx prepare` needs to be rerun")
+    }
+
+    Ok(())
+}
+
+/// Split the data in `sqlx-data.json` into one file per query in `.sqlx/`.
+pub fn split() -> anyhow::Result<()> {
+    let (db_kind, data) = read_data_file()?;
+
+    write_query_files(Path::new(QUERY_DIR), &db_kind, &data)?;
+
+    println!(
+        "query data in `sqlx-data.json` split into `.sqlx/` in the current directory; \
+         please check this into version control, delete `sqlx-data.json` and use \
+         `cargo sqlx prepare --sharded` from now on"
+    );
+
+    Ok(())
+}
+
+fn write_data_file(db_kind: &str, data: QueryData) -> anyhow::Result<()> {
+    #[derive(serde::Serialize)]
+    struct DataFile<'a> {
+        db: &'a str,
+        #[serde(flatten)]
+        data: QueryData,
+    }
+
+    serde_json::to_writer_pretty(
+        File::create("sqlx-data.json").context("failed to create/open `sqlx-data.json`")?,
+        &DataFile { db: db_kind, data },
+    )
+    .context("failed to write to `sqlx-data.json`")
+}
+
+fn read_data_file() -> anyhow::Result<(String, QueryData)> {
     let data_file = fs::read("sqlx-data.json").context(
         "failed to open `sqlx-data.json`; you may need to run `cargo sqlx prepare` first",
     )?;
 
     let mut saved_data: QueryData = serde_json::from_slice(&data_file)?;
 
-    let expected_db = saved_data
+    let db_kind = saved_data
         .remove("db")
         .context("expected key `db` in data file")?;
 
-    let expected_db = expected_db
+    let db_kind = db_kind
         .as_str()
-        .context("expected key `db` to be a string")?;
+        .context("expected key `db` to be a string")?
+        .to_owned();
 
-    if db_kind != expected_db {
-        bail!(
-            "saved prepare data is for {}, not {} (inferred from `DATABASE_URL`)",
-            expected_db,
-            db_kind
+    Ok((db_kind, saved_data))
+}
+
+// each file is named after the hash of its query and holds the query data next to a `db` key
+fn write_query_files(dir: &Path, db_kind: &str, data: &QueryData) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).context("failed to create `.sqlx/`")?;
+
+    for (hash, query_data) in data {
+        let mut object = JsonObject::new();
+        object.insert("db".into(), db_kind.into());
+
+        if let serde_json::Value::Object(query_data) = query_data {
+            object.extend(query_data.clone());
+        }
+
+        let path = dir.join(format!("query-{}.json", hash));
+
+        serde_json::to_writer_pretty(
+            File::create(&path)
+                .with_context(|| format!("failed to create/open `{}`", path.display()))?,
+            &object,
         )
+        .with_context(|| format!("failed to write to `{}`", path.display()))?;
     }
 
-    if data != saved_data {
-        bail!("`cargo sqlx prepare` needs to be rerun")
+    // only once every query is written, remove the data of queries that no longer exist;
+    // a failed write leaves the previous data in place
+    for path in glob::glob(&format!("{}/query-*.json", dir.display()))? {
+        let path = path?;
+
+        let hash = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("query-"))
+            .and_then(|name| name.strip_suffix(".json"));
+
+        if !hash.map_or(false, |hash| data.contains_key(hash)) {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove `{}`", path.display()))?;
+        }
     }
 
     Ok(())
 }
 
+fn read_query_files(dir: &Path) -> anyhow::Result<(String, QueryData)> {
+    if !dir.is_dir() {
+        bail!("failed to find `.sqlx/`; you may need to run `cargo sqlx prepare --sharded` first");
+    }
+
+    let mut db_kind = None;
+    let mut data = BTreeMap::new();
+
+    for path in glob::glob(&format!("{}/query-*.json", dir.display()))? {
+        let path = path?;
+        let mut query_data: JsonObject = serde_json::from_slice(&fs::read(&path)?)?;
+
+        let db = query_data
+            .remove("db")
+            .with_context(|| format!("expected key `db` in {}", path.display()))?;
+
+        let db = db
+            .as_str()
+            .with_context(|| format!("expected key `db` to be a string in {}", path.display()))?;
+
+        match &db_kind {
+            Some(db_kind) if db_kind != db => bail!(
+                "found saved prepare data for both {} and {} in `.sqlx/`",
+                db_kind,
+                db
+            ),
+            Some(_) => {}
+            None => db_kind = Some(db.to_owned()),
+        }
+
+        // the name of the file is `query-<hash>.json`
+        let hash = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix("query-"))
+            .with_context(|| format!("unexpected file name {}", path.display()))?;
+
+        data.insert(hash.to_owned(), serde_json::Value::Object(query_data));
+    }
+
+    let db_kind = db_kind.context("no saved prepare data found in `.sqlx/`")?;
+
+    Ok((db_kind, data))
+}
+
 fn run_prepare_step(cargo_args: Vec<String>) -> anyhow::Result<QueryData> {
     // path to the Cargo executable
     let cargo = env::var("CARGO")
         .context("`prepare` subcommand may only be invoked as `cargo sqlx prepare``")?;
 
     let check_status = Command::new(&cargo)
+        // the query data is only saved when the macros connect to the database
+        .env("SQLX_OFFLINE", "false")
         .arg("rustc")
         .args(cargo_args)
         .arg("--")
@@ -158,3 +290,42 @@ fn get_db_kind(url: &str) -> anyhow::Result<&'static str> {
         AnyKind::Mssql => Ok("MSSQL"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{read_query_files, write_query_files, QueryData};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn it_round_trips_query_files() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("sqlx-cli-query-files-{}", std::process::id()));
+
+        let mut data = QueryData::new();
+        data.insert(
+            "4a8a0a3ba34d3ee5a8f3ef50cd1ca59a".into(),
+            json!({ "query": "SELECT 1", "describe": { "columns": [] }, "hash": "4a8a0a3ba34d3ee5a8f3ef50cd1ca59a" }),
+        );
+        data.insert(
+            "8c0a5f9e4c2d3b1a6e7f8091a2b3c4d5".into(),
+            json!({ "query": "SELECT 2", "describe": { "columns": [] }, "hash": "8c0a5f9e4c2d3b1a6e7f8091a2b3c4d5" }),
+        );
+
+        write_query_files(&dir, "SQLite", &data)?;
+
+        // the data of a query that was removed is not kept
+        let mut remaining = data.clone();
+        remaining.remove("8c0a5f9e4c2d3b1a6e7f8091a2b3c4d5");
+
+        write_query_files(&dir, "SQLite", &remaining)?;
+
+        let (db_kind, saved_data) = read_query_files(&dir)?;
+
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(db_kind, "SQLite");
+        assert_eq!(saved_data, remaining);
+
+        Ok(())
+    }
+}
@@ -21,6 +21,10 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     {
         self.values.push(Box::new(value));
     }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
 }
 
 pub struct AnyArgumentBuffer<'q>(pub(crate) AnyArgumentBufferKind<'q>);
@@ -13,7 +13,7 @@ use crate::sqlite::SqliteColumn;
 #[cfg(feature = "mssql")]
 use crate::mssql::MssqlColumn;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AnyColumn {
     pub(crate) kind: AnyColumnKind,
     pub(crate) type_info: AnyTypeInfo,
@@ -21,7 +21,7 @@ pub struct AnyColumn {
 
 impl crate::column::private_column::Sealed for AnyColumn {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum AnyColumnKind {
     #[cfg(feature = "postgres")]
     Postgres(PgColumn),
@@ -1,3 +1,7 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -5,10 +9,11 @@ use futures_util::{StreamExt, TryStreamExt};
 
 use crate::any::connection::AnyConnectionKind;
 use crate::any::{Any, AnyColumn, AnyConnection, AnyDone, AnyRow, AnyTypeInfo};
-use crate::database::Database;
+use crate::database::{Database, HasArguments};
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
-use crate::statement::StatementInfo;
+use crate::query::Query;
+use crate::statement::{PreparedId, Statement, StatementInfo};
 
 impl<'c> Executor<'c> for &'c mut AnyConnection {
     type Database = Any;
@@ -22,30 +27,32 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         E: Execute<'q, Self::Database>,
     {
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+        let prepared = query.prepared().cloned();
         let query = query.query();
 
         match &mut self.0 {
             #[cfg(feature = "postgres")]
             AnyConnectionKind::Postgres(conn) => conn
-                .fetch_many((query, arguments.map(Into::into)))
+                .fetch_many(forward(query, arguments.map(Into::into), timeout, prepared))
                 .map_ok(|v| v.map_right(Into::into).map_left(Into::into))
                 .boxed(),
 
             #[cfg(feature = "mysql")]
             AnyConnectionKind::MySql(conn) => conn
-                .fetch_many((query, arguments.map(Into::into)))
+                .fetch_many(forward(query, arguments.map(Into::into), timeout, prepared))
                 .map_ok(|v| v.map_right(Into::into).map_left(Into::into))
                 .boxed(),
 
             #[cfg(feature = "sqlite")]
             AnyConnectionKind::Sqlite(conn) => conn
-                .fetch_many((query, arguments.map(Into::into)))
+                .fetch_many(forward(query, arguments.map(Into::into), timeout, prepared))
                 .map_ok(|v| v.map_right(Into::into).map_left(Into::into))
                 .boxed(),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => conn
-                .fetch_many((query, arguments.map(Into::into)))
+                .fetch_many(forward(query, arguments.map(Into::into), timeout, prepared))
                 .map_ok(|v| v.map_right(Into::into).map_left(Into::into))
                 .boxed(),
         }
@@ -60,31 +67,33 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         E: Execute<'q, Self::Database>,
     {
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+        let prepared = query.prepared().cloned();
         let query = query.query();
 
         Box::pin(async move {
             Ok(match &mut self.0 {
                 #[cfg(feature = "postgres")]
                 AnyConnectionKind::Postgres(conn) => conn
-                    .fetch_optional((query, arguments.map(Into::into)))
+                    .fetch_optional(forward(query, arguments.map(Into::into), timeout, prepared))
                     .await?
                     .map(Into::into),
 
                 #[cfg(feature = "mysql")]
                 AnyConnectionKind::MySql(conn) => conn
-                    .fetch_optional((query, arguments.map(Into::into)))
+                    .fetch_optional(forward(query, arguments.map(Into::into), timeout, prepared))
                     .await?
                     .map(Into::into),
 
                 #[cfg(feature = "sqlite")]
                 AnyConnectionKind::Sqlite(conn) => conn
-                    .fetch_optional((query, arguments.map(Into::into)))
+                    .fetch_optional(forward(query, arguments.map(Into::into), timeout, prepared))
                     .await?
                     .map(Into::into),
 
                 #[cfg(feature = "mssql")]
                 AnyConnectionKind::Mssql(conn) => conn
-                    .fetch_optional((query, arguments.map(Into::into)))
+                    .fetch_optional(forward(query, arguments.map(Into::into), timeout, prepared))
                     .await?
                     .map(Into::into),
             })
@@ -121,6 +130,35 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     }
 }
 
+// keeps the timeout and prepared statement of the query when handing it to the underlying
+// connection
+fn forward<'q, DB: Database>(
+    query: &'q str,
+    arguments: Option<<DB as HasArguments<'q>>::Arguments>,
+    timeout: Option<Duration>,
+    prepared: Option<Arc<PreparedId>>,
+) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
+    Query {
+        query,
+        arguments,
+        database: PhantomData,
+        timeout,
+        prepared,
+    }
+}
+
+pub(super) fn map_statement<DB: Database>(statement: Statement<'_, DB>) -> Statement<'_, Any>
+where
+    AnyTypeInfo: From<DB::TypeInfo>,
+    AnyColumn: From<DB::Column>,
+{
+    Statement {
+        sql: statement.sql,
+        info: map_describe(statement.info),
+        id: statement.id,
+    }
+}
+
 fn map_describe<DB: Database>(info: StatementInfo<DB>) -> StatementInfo<Any>
 where
     AnyTypeInfo: From<DB::TypeInfo>,
@@ -138,5 +176,6 @@ where
         parameters,
         nullable: info.nullable,
         columns: info.columns.into_iter().map(Into::into).collect(),
+        parameter_names: info.parameter_names,
     }
 }
@@ -1,8 +1,10 @@
 use futures_core::future::BoxFuture;
 
-use crate::any::{Any, AnyConnectOptions};
+use crate::any::{Any, AnyConnectOptions, AnyKind};
 use crate::connection::Connection;
 use crate::error::Error;
+use crate::executor::Executor;
+use crate::statement::Statement;
 
 #[cfg(feature = "postgres")]
 use crate::postgres;
@@ -15,11 +17,13 @@ use crate::mssql;
 
 #[cfg(feature = "mysql")]
 use crate::mysql;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 mod establish;
 mod executor;
 
+use executor::map_statement;
+
 /// A connection to _any_ SQLx database.
 ///
 /// The database driver used is determined by the scheme
@@ -83,6 +87,27 @@ macro_rules! delegate_to_mut {
     };
 }
 
+impl AnyConnection {
+    /// Returns the database driver used by this connection.
+    ///
+    /// This allows running database-specific SQL through a generic connection.
+    pub fn kind(&self) -> AnyKind {
+        match self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(_) => AnyKind::Postgres,
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(_) => AnyKind::MySql,
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(_) => AnyKind::Sqlite,
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_) => AnyKind::Mssql,
+        }
+    }
+}
+
 impl Connection for AnyConnection {
     type Database = Any;
 
@@ -108,6 +133,10 @@ impl Connection for AnyConnection {
         delegate_to_mut!(self.ping())
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        delegate_to_mut!(self.reset())
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -115,6 +144,16 @@ impl Connection for AnyConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         match &self.0 {
             #[cfg(feature = "postgres")]
@@ -149,6 +188,35 @@ impl Connection for AnyConnection {
         }
     }
 
+    fn prepare<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Statement<'q, Self::Database>, Error>>
+    where
+        Self: Sized,
+        for<'c> &'c mut Self: Executor<'c, Database = Self::Database>,
+    {
+        Box::pin(async move {
+            Ok(match &mut self.0 {
+                #[cfg(feature = "postgres")]
+                AnyConnectionKind::Postgres(conn) => map_statement(conn.prepare(sql).await?),
+
+                #[cfg(feature = "mysql")]
+                AnyConnectionKind::MySql(conn) => map_statement(conn.prepare(sql).await?),
+
+                #[cfg(feature = "sqlite")]
+                AnyConnectionKind::Sqlite(conn) => map_statement(conn.prepare(sql).await?),
+
+                #[cfg(feature = "mssql")]
+                AnyConnectionKind::Mssql(conn) => map_statement(conn.prepare(sql).await?),
+            })
+        })
+    }
+
+    fn shrink_buffers(&mut self) {
+        delegate_to_mut!(self.shrink_buffers())
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         delegate_to_mut!(self.flush())
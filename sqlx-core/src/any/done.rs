@@ -9,6 +9,11 @@ pub struct AnyDone {
 }
 
 impl AnyDone {
+    /// Returns the ID generated by the last `INSERT`, for databases that report one
+    /// (MySQL and SQLite).
+    ///
+    /// This is always `None` for PostgreSQL, where the ID should be fetched with a
+    /// `RETURNING` clause instead, and for MSSQL.
     pub fn last_insert_id(&self) -> Option<i64> {
         self.last_insert_id
     }
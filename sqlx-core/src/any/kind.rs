@@ -1,7 +1,9 @@
 use crate::error::Error;
 use std::str::FromStr;
 
-#[derive(Debug)]
+/// The database driver behind an [`AnyConnection`](crate::any::AnyConnection), as determined
+/// by the scheme of the connection URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnyKind {
     #[cfg(feature = "postgres")]
     Postgres,
@@ -64,32 +64,38 @@ impl MigrateDatabase for Any {
 }
 
 impl Migrate for AnyConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::Postgres(conn) => conn.ensure_migrations_table(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::Sqlite(conn) => conn.ensure_migrations_table(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::MySql(conn) => conn.ensure_migrations_table(table_name),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => unimplemented!(),
         }
     }
 
-    fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+    fn version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.version(),
+            AnyConnectionKind::Postgres(conn) => conn.version(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.version(),
+            AnyConnectionKind::Sqlite(conn) => conn.version(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.version(),
+            AnyConnectionKind::MySql(conn) => conn.version(table_name),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => unimplemented!(),
@@ -128,19 +134,39 @@ impl Migrate for AnyConnection {
         }
     }
 
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<i64>, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.list_applied_migrations(table_name),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.list_applied_migrations(table_name),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.list_applied_migrations(table_name),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(conn) => unimplemented!(),
+        }
+    }
+
     fn validate<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.validate(migration),
+            AnyConnectionKind::Postgres(conn) => conn.validate(table_name, migration),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.validate(migration),
+            AnyConnectionKind::Sqlite(conn) => conn.validate(table_name, migration),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.validate(migration),
+            AnyConnectionKind::MySql(conn) => conn.validate(table_name, migration),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => unimplemented!(),
@@ -149,17 +175,38 @@ impl Migrate for AnyConnection {
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.apply(table_name, migration),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.apply(table_name, migration),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.apply(table_name, migration),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(conn) => unimplemented!(),
+        }
+    }
+
+    fn revert<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.apply(migration),
+            AnyConnectionKind::Postgres(conn) => conn.revert(table_name, migration),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.apply(migration),
+            AnyConnectionKind::Sqlite(conn) => conn.revert(table_name, migration),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.apply(migration),
+            AnyConnectionKind::MySql(conn) => conn.revert(table_name, migration),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => unimplemented!(),
@@ -4,33 +4,38 @@ use crate::any::connection::AnyConnectionKind;
 use crate::any::{Any, AnyConnection};
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::TransactionManager;
+use crate::transaction::{TransactionManager, TransactionOptions};
 
 pub struct AnyTransactionManager;
 
 impl TransactionManager for AnyTransactionManager {
     type Database = Any;
 
-    fn begin(conn: &mut AnyConnection) -> BoxFuture<'_, Result<(), Error>> {
+    fn begin_with(
+        conn: &mut AnyConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         match &mut conn.0 {
             #[cfg(feature = "postgres")]
             AnyConnectionKind::Postgres(conn) => {
-                <crate::postgres::Postgres as Database>::TransactionManager::begin(conn)
+                <crate::postgres::Postgres as Database>::TransactionManager::begin_with(
+                    conn, options,
+                )
             }
 
             #[cfg(feature = "mysql")]
             AnyConnectionKind::MySql(conn) => {
-                <crate::mysql::MySql as Database>::TransactionManager::begin(conn)
+                <crate::mysql::MySql as Database>::TransactionManager::begin_with(conn, options)
             }
 
             #[cfg(feature = "sqlite")]
             AnyConnectionKind::Sqlite(conn) => {
-                <crate::sqlite::Sqlite as Database>::TransactionManager::begin(conn)
+                <crate::sqlite::Sqlite as Database>::TransactionManager::begin_with(conn, options)
             }
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => {
-                <crate::mssql::Mssql as Database>::TransactionManager::begin(conn)
+                <crate::mssql::Mssql as Database>::TransactionManager::begin_with(conn, options)
             }
         }
     }
@@ -3,6 +3,7 @@
 use crate::database::{Database, HasArguments};
 use crate::encode::Encode;
 use crate::types::Type;
+use std::fmt::{self, Write};
 
 /// A tuple of arguments to be sent to the database.
 pub trait Arguments<'q>: Send + Sized + Default {
@@ -16,6 +17,24 @@ pub trait Arguments<'q>: Send + Sized + Default {
     fn add<T>(&mut self, value: T)
     where
         T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>;
+
+    /// The number of values that have been added to the arguments.
+    ///
+    /// This can be compared against the number of parameters of a statement (see
+    /// [`StatementInfo::parameters`](crate::statement::StatementInfo::parameters)) to catch
+    /// a mismatch before the query is sent.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no values have been added to the arguments.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the placeholder that refers to the next value to be added
+    /// (e.g., `?` for most SQL flavors, `$N` for Postgres).
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("?")
+    }
 }
 
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
@@ -49,7 +49,7 @@ impl<T> StatementCache<T> {
     }
 
     /// Clear all cached statements from the cache.
-    #[cfg(feature = "sqlite")]
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     pub fn clear(&mut self) {
         self.inner.clear();
     }
@@ -63,4 +63,9 @@ impl<T> StatementCache<T> {
     pub fn capacity(&self) -> usize {
         self.inner.capacity()
     }
+
+    /// True if statements should be cached at all; a capacity of `0` disables caching.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity() > 0
+    }
 }
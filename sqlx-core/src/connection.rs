@@ -1,10 +1,16 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::executor::Executor;
+use crate::statement::Statement;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
-use futures_core::Future;
+use sqlx_rt::sleep;
 use std::fmt::Debug;
 use std::str::FromStr;
+use std::time::Duration;
+
+// the longest delay between attempts of `transaction_retrying`, unless the initial one is longer
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(1);
 
 /// Represents a single database connection.
 pub trait Connection: Send {
@@ -22,6 +28,21 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Resets the state of the session, as if the connection was newly opened.
+    ///
+    /// This discards session settings, temporary tables, and prepared statements (the
+    /// statement cache is cleared). For **PostgreSQL** this runs `DISCARD ALL`; for **MySQL** it
+    /// sends `COM_RESET_CONNECTION` and then applies the session parameters SQLx sets on connect
+    /// again (the `sql_mode`, the UTC `time_zone` and the `utf8mb4` character set). Other
+    /// databases do nothing.
+    ///
+    /// Settings applied when the connection was opened (e.g., by
+    /// [`PoolOptions::after_connect`](crate::pool::PoolOptions::after_connect)) are discarded
+    /// as well.
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -29,22 +50,56 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given options, such as the isolation level.
+    ///
+    /// Returns an error if an option is not supported by the database or if this connection
+    /// is already in a transaction (options cannot be set on a savepoint).
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized;
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
-    /// return an error, the transaction will be committed.
-    fn transaction<'c: 'f, 'f, T, E, F, Fut>(&'c mut self, f: F) -> BoxFuture<'f, Result<T, E>>
+    /// return an error, the transaction will be committed. The result of the function is
+    /// returned in both cases.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::postgres::{PgConnection, PgRow};
+    /// use sqlx::Connection;
+    ///
+    /// # async fn example() -> sqlx::Result<()> {
+    /// let mut conn: PgConnection = todo!();
+    ///
+    /// let rows: Vec<PgRow> = conn.transaction(|tx| Box::pin(async move {
+    ///     sqlx::query("INSERT INTO logs (message) VALUES ('hello')")
+    ///         .execute(&mut *tx)
+    ///         .await?;
+    ///
+    ///     sqlx::query("SELECT * FROM logs").fetch_all(&mut *tx).await
+    /// })).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn transaction<'a, F, R, E>(&'a mut self, callback: F) -> BoxFuture<'a, Result<R, E>>
     where
+        for<'c> F: FnOnce(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, E>>
+            + 'a
+            + Send
+            + Sync,
         Self: Sized,
-        T: Send,
-        F: FnOnce(&mut <Self::Database as Database>::Connection) -> Fut + Send + 'f,
+        R: Send,
         E: From<Error> + Send,
-        Fut: Future<Output = Result<T, E>> + Send,
     {
         Box::pin(async move {
             let mut tx = self.begin().await?;
 
-            match f(&mut tx).await {
+            match callback(&mut tx).await {
                 Ok(r) => {
                     // no error occurred, commit the transaction
                     tx.commit().await?;
@@ -62,6 +117,225 @@ pub trait Connection: Send {
         })
     }
 
+    /// Execute the function inside a transaction, retrying it in a new transaction if it fails
+    /// with a serialization failure.
+    ///
+    /// With the `SERIALIZABLE` (or, in MySQL, `REPEATABLE READ`) isolation level, a transaction
+    /// may be aborted because it conflicts with a concurrent transaction, either while a
+    /// statement is executed or when it is committed; see
+    /// [`Error::is_serialization_failure`]. This begins a transaction and runs the function
+    /// like [`transaction`](#method.transaction) does, but on such an error the transaction is
+    /// rolled back and the function is run again on a new transaction, up to `max_attempts`
+    /// times in total. The delay before each retry starts at `backoff` and doubles after every
+    /// attempt, up to one second (or `backoff`, if that is longer). Any other error is returned
+    /// immediately, as is the error of an attempt whose transaction failed to roll back.
+    ///
+    /// Returns [`Error::Configuration`] without beginning a transaction if `max_attempts` is 0.
+    ///
+    /// The function may be run more than once, so it should not have side effects outside of
+    /// the transaction.
+    ///
+    /// To set the isolation level of each transaction, use
+    /// [`transaction_retrying_with`](#method.transaction_retrying_with).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::postgres::PgConnection;
+    /// use sqlx::Connection;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> sqlx::Result<()> {
+    /// let mut conn: PgConnection = todo!();
+    ///
+    /// let balance: i64 = conn
+    ///     .transaction_retrying(5, Duration::from_millis(10), |tx| Box::pin(async move {
+    ///         sqlx::query("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+    ///             .execute(&mut *tx)
+    ///             .await?;
+    ///
+    ///         sqlx::query_scalar("SELECT balance FROM accounts WHERE id = 1")
+    ///             .fetch_one(&mut *tx)
+    ///             .await
+    ///     }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn transaction_retrying<'a, F, R>(
+        &'a mut self,
+        max_attempts: u32,
+        backoff: Duration,
+        callback: F,
+    ) -> BoxFuture<'a, Result<R, Error>>
+    where
+        for<'c> F: FnMut(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, Error>>
+            + 'a
+            + Send
+            + Sync,
+        Self: Sized,
+        R: Send,
+    {
+        self.transaction_retrying_with(
+            TransactionOptions::default(),
+            max_attempts,
+            backoff,
+            callback,
+        )
+    }
+
+    /// Execute the function inside a transaction begun with the given options, such as the
+    /// isolation level, retrying it in a new transaction if it fails with a serialization
+    /// failure.
+    ///
+    /// Every attempt begins its transaction with [`begin_with`](#method.begin_with); see
+    /// [`transaction_retrying`](#method.transaction_retrying) for how the function is retried.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::postgres::PgConnection;
+    /// use sqlx::{Connection, IsolationLevel, TransactionOptions};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> sqlx::Result<()> {
+    /// let mut conn: PgConnection = todo!();
+    ///
+    /// let options = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    ///
+    /// let balance: i64 = conn
+    ///     .transaction_retrying_with(options, 5, Duration::from_millis(10), |tx| Box::pin(async move {
+    ///         sqlx::query("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+    ///             .execute(&mut *tx)
+    ///             .await?;
+    ///
+    ///         sqlx::query_scalar("SELECT balance FROM accounts WHERE id = 1")
+    ///             .fetch_one(&mut *tx)
+    ///             .await
+    ///     }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn transaction_retrying_with<'a, F, R>(
+        &'a mut self,
+        options: TransactionOptions,
+        max_attempts: u32,
+        backoff: Duration,
+        mut callback: F,
+    ) -> BoxFuture<'a, Result<R, Error>>
+    where
+        for<'c> F: FnMut(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, Error>>
+            + 'a
+            + Send
+            + Sync,
+        Self: Sized,
+        R: Send,
+    {
+        Box::pin(async move {
+            if max_attempts == 0 {
+                return Err(Error::Configuration(
+                    "transaction_retrying(): max_attempts must be at least 1".into(),
+                ));
+            }
+
+            let mut attempt = 1;
+            let mut backoff = backoff;
+
+            loop {
+                let mut tx = self.begin_with(options).await?;
+
+                let res = match callback(&mut tx).await {
+                    // a serialization failure may also be reported when committing
+                    Ok(r) => tx.commit().await.map(|_| r),
+
+                    Err(e) => {
+                        if let Err(rollback_error) = tx.rollback().await {
+                            // the state of the connection is unknown, so don't retry on it
+                            log::warn!(
+                                "failed to roll back transaction after error ({}): {}",
+                                e,
+                                rollback_error
+                            );
+
+                            return Err(e);
+                        }
+
+                        Err(e)
+                    }
+                };
+
+                match res {
+                    Err(e) if e.is_serialization_failure() && attempt < max_attempts => {
+                        log::debug!(
+                            "retrying transaction in {:?} after attempt {}: {}",
+                            backoff,
+                            attempt,
+                            e
+                        );
+
+                        sleep(backoff).await;
+
+                        attempt += 1;
+                        backoff = backoff
+                            .checked_mul(2)
+                            .map_or(MAX_RETRY_BACKOFF, |doubled| doubled.min(MAX_RETRY_BACKOFF))
+                            .max(backoff);
+                    }
+
+                    res => return res,
+                }
+            }
+        })
+    }
+
+    /// Prepare the SQL statement on this connection to execute it many times, with different
+    /// arguments each time.
+    ///
+    /// The returned [`Statement`] carries the information about the parameters and columns
+    /// of the statement. On **PostgreSQL** and **MySQL**, it also holds on to the statement
+    /// prepared here, outside of the statement cache of the connection, until it is dropped.
+    /// Other databases look the statement up in the statement cache when it is executed.
+    ///
+    /// As no arguments are known yet, **PostgreSQL** infers the types of the parameters when
+    /// preparing the statement; cast a parameter (e.g., `$1::int4`) to choose its type.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sqlx::postgres::PgConnection;
+    /// use sqlx::Connection;
+    ///
+    /// # async fn example() -> sqlx::Result<()> {
+    /// let mut conn: PgConnection = todo!();
+    ///
+    /// let statement = conn.prepare("INSERT INTO logs (message) VALUES ($1)").await?;
+    ///
+    /// for message in &["hello", "world"] {
+    ///     statement.query().bind(message).execute(&mut conn).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn prepare<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Statement<'q, Self::Database>, Error>>
+    where
+        Self: Sized,
+        for<'c> &'c mut Self: Executor<'c, Database = Self::Database>,
+    {
+        Box::pin(async move {
+            let info = self.describe(sql).await?;
+
+            Ok(Statement {
+                sql,
+                info,
+                id: None,
+            })
+        })
+    }
+
     /// The number of statements currently cached in the connection.
     fn cached_statements_size(&self) -> usize
     where
@@ -72,6 +346,10 @@ pub trait Connection: Send {
 
     /// Removes all statements from the cache, closing them on the server if
     /// needed.
+    ///
+    /// This is useful after a schema change (e.g., running migrations) on a long-lived
+    /// connection, as cached statements may still refer to the previous column types.
+    /// If the connection does not cache statements, this does nothing.
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>>
     where
         Self::Database: HasStatementCache,
@@ -79,6 +357,15 @@ pub trait Connection: Send {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Shrink the internal read and write buffers of this connection back to their
+    /// initial capacity.
+    ///
+    /// The buffers grow to fit the largest message sent or received and are otherwise kept
+    /// for the life of the connection; a long-lived connection that once handled a very large
+    /// row can use this to release that memory. The next large query will need to reallocate.
+    /// If the connection does not buffer messages, this does nothing.
+    fn shrink_buffers(&mut self) {}
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
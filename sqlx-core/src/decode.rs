@@ -1,7 +1,9 @@
 //! Provides [`Decode`](trait.Decode.html) for decoding values from the database.
 
 use crate::database::{Database, HasValueRef};
-use crate::error::BoxDynError;
+use crate::error::{mismatched_types, BoxDynError};
+use crate::type_info::TypeInfo;
+use crate::types::Type;
 use crate::value::ValueRef;
 
 /// A type that can be decoded from the database.
@@ -76,3 +78,144 @@ where
         }
     }
 }
+
+/// A type that can be decoded from a value of its own SQL type or, through a widening conversion
+/// that cannot lose information, from a value of a narrower numeric type.
+///
+/// This is used by [`Row::try_get_lossy`]. In addition to the SQL types it is
+/// [compatible][Type::compatible] with, each type is decoded from the SQL types compatible with:
+///
+/// | Rust type | Widened from |
+/// | --------- | ------------ |
+/// | `i32` | `i16` |
+/// | `i64` | `i32`, `i16` |
+/// | `f64` | `f32`, `i64`, `i32`, `i16` |
+///
+/// Whether every value of a narrower type fits depends on the target type:
+///
+/// * `i32` and `i64` hold every `i16` and `i32` value.
+/// * `f64` holds every `f32`, `i32` and `i16` value exactly, but not every `i64`. An `i64` is
+///   decoded only if `f64` represents it exactly, which is the case for every value within
+///   ±2<sup>53</sup> but only some beyond it; any other value is an error rather than being
+///   rounded.
+///
+/// [`Row::try_get_lossy`]: crate::row::Row::try_get_lossy
+pub trait DecodeLossy<'r, DB: Database>: Sized {
+    /// Decode a new value of this type using a raw value from the database, widening it if
+    /// the value is of a narrower SQL type.
+    fn decode_lossy(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError>;
+}
+
+impl<'r, DB, T> DecodeLossy<'r, DB> for Option<T>
+where
+    DB: Database,
+    T: DecodeLossy<'r, DB>,
+{
+    fn decode_lossy(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(T::decode_lossy(value)?))
+        }
+    }
+}
+
+// returns the type of the value if it needs to be checked; as with `Row::try_get`, the type of
+// a NULL value is not checked
+fn checked_type_info<'r, DB: Database>(
+    value: &<DB as HasValueRef<'r>>::ValueRef,
+) -> Option<DB::TypeInfo> {
+    let ty = value.type_info();
+
+    if value.is_null() || ty.is_null() {
+        None
+    } else {
+        Some(ty.clone())
+    }
+}
+
+impl<'r, DB> DecodeLossy<'r, DB> for i32
+where
+    DB: Database,
+    i32: Decode<'r, DB> + Type<DB>,
+    i16: Decode<'r, DB> + Type<DB>,
+{
+    fn decode_lossy(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        match checked_type_info::<DB>(&value) {
+            Some(ty) if !i32::compatible(&ty) => {
+                if i16::compatible(&ty) {
+                    Ok(i16::decode(value)?.into())
+                } else {
+                    Err(mismatched_types::<DB, i32>(&ty))
+                }
+            }
+
+            _ => i32::decode(value),
+        }
+    }
+}
+
+impl<'r, DB> DecodeLossy<'r, DB> for i64
+where
+    DB: Database,
+    i64: Decode<'r, DB> + Type<DB>,
+    i32: Decode<'r, DB> + Type<DB>,
+    i16: Decode<'r, DB> + Type<DB>,
+{
+    fn decode_lossy(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        match checked_type_info::<DB>(&value) {
+            Some(ty) if !i64::compatible(&ty) => {
+                if i32::compatible(&ty) {
+                    Ok(i32::decode(value)?.into())
+                } else if i16::compatible(&ty) {
+                    Ok(i16::decode(value)?.into())
+                } else {
+                    Err(mismatched_types::<DB, i64>(&ty))
+                }
+            }
+
+            _ => i64::decode(value),
+        }
+    }
+}
+
+impl<'r, DB> DecodeLossy<'r, DB> for f64
+where
+    DB: Database,
+    f64: Decode<'r, DB> + Type<DB>,
+    f32: Decode<'r, DB> + Type<DB>,
+    i64: Decode<'r, DB> + Type<DB>,
+    i32: Decode<'r, DB> + Type<DB>,
+    i16: Decode<'r, DB> + Type<DB>,
+{
+    fn decode_lossy(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        match checked_type_info::<DB>(&value) {
+            Some(ty) if !f64::compatible(&ty) => {
+                if f32::compatible(&ty) {
+                    Ok(f32::decode(value)?.into())
+                } else if i64::compatible(&ty) {
+                    // `i64` is checked before the narrower integers as, depending on the
+                    // database, they may also be compatible with (and truncate) 64-bit values
+                    let int = i64::decode(value)?;
+                    let float = int as f64;
+
+                    if float as i128 != i128::from(int) {
+                        return Err(
+                            format!("{} cannot be represented exactly as `f64`", int).into()
+                        );
+                    }
+
+                    Ok(float)
+                } else if i32::compatible(&ty) {
+                    Ok(i32::decode(value)?.into())
+                } else if i16::compatible(&ty) {
+                    Ok(i16::decode(value)?.into())
+                } else {
+                    Err(mismatched_types::<DB, f64>(&ty))
+                }
+            }
+
+            _ => f64::decode(value),
+        }
+    }
+}
@@ -78,6 +78,18 @@ pub enum Error {
     #[error("error occurred while decoding: {0}")]
     Decode(#[source] BoxDynError),
 
+    /// A query was given more arguments than the database can accept in one statement.
+    #[error("too many arguments for query: {count} (the maximum is {max})")]
+    TooManyArguments { count: usize, max: usize },
+
+    /// A query did not complete within its [`Query::timeout`].
+    ///
+    /// PostgreSQL cancels such a query itself and returns a [`Error::Database`] instead.
+    ///
+    /// [`Query::timeout`]: crate::query::Query::timeout
+    #[error("query timed out while waiting for results")]
+    QueryTimedOut,
+
     /// A [`Pool::acquire`] timed out due to connections not becoming available or
     /// because another task encountered too many errors while trying to open a new connection.
     ///
@@ -112,6 +124,106 @@ impl Error {
         }
     }
 
+    /// Returns `true` if this is a database error reporting that the transaction was aborted
+    /// because of a conflict with a concurrent transaction, and so may succeed if retried.
+    ///
+    /// This matches the SQLSTATE codes `40001` (`serialization_failure`, which MySQL also
+    /// uses for deadlocks) and `40P01` (`deadlock_detected` in PostgreSQL).
+    ///
+    /// See [`Connection::transaction_retrying`](crate::connection::Connection::transaction_retrying).
+    pub fn is_serialization_failure(&self) -> bool {
+        match self.as_database_error().and_then(|e| e.code()) {
+            Some(code) => code == "40001" || code == "40P01",
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the connection to the database failed or was lost.
+    ///
+    /// This is the case for [`Error::Io`] and [`Error::Tls`], and for database errors with a
+    /// SQLSTATE code of class `08` (connection exception, e.g. `08006` or MySQL's `08S01`) or
+    /// the PostgreSQL codes `57P01` (`admin_shutdown`), `57P02` (`crash_shutdown`) and `57P03`
+    /// (`cannot_connect_now`).
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            Error::Io(_) | Error::Tls(_) => true,
+
+            _ => match self.sqlstate() {
+                Some(code) => {
+                    code.starts_with("08") || code == "57P01" || code == "57P02" || code == "57P03"
+                }
+
+                None => false,
+            },
+        }
+    }
+
+    /// Returns `true` if this is a database error reporting that a constraint (e.g. a unique
+    /// index, foreign key, `NOT NULL` or `CHECK` constraint) was violated.
+    ///
+    /// This is the case for a SQLSTATE code of class `23` (integrity constraint violation, e.g.
+    /// `23505` in PostgreSQL or `23000` in MySQL) and, for SQLite, the `SQLITE_CONSTRAINT`
+    /// result code and its extended codes.
+    ///
+    /// Use [`DatabaseError::constraint`] to find out which constraint was violated.
+    pub fn is_constraint_violation(&self) -> bool {
+        if let Some(code) = self.sqlstate() {
+            return code.starts_with("23");
+        }
+
+        #[cfg(feature = "sqlite")]
+        if let Some(code) = self.sqlite_primary_code() {
+            return code == libsqlite3_sys::SQLITE_CONSTRAINT;
+        }
+
+        false
+    }
+
+    /// Returns `true` if the operation failed for a transient reason and may succeed if it
+    /// is retried (on a new connection, for a connection error).
+    ///
+    /// This is the case for:
+    ///
+    ///  * serialization failures and deadlocks, see [`is_serialization_failure`];
+    ///  * connection errors, see [`is_connection_error`];
+    ///  * [`Error::PoolTimedOut`];
+    ///  * for SQLite, the `SQLITE_BUSY` and `SQLITE_LOCKED` result codes (and their extended
+    ///    codes), returned when the database is locked by another connection.
+    ///
+    /// Any other error, such as a syntax error or a constraint violation, will fail again.
+    ///
+    /// [`is_serialization_failure`]: #method.is_serialization_failure
+    /// [`is_connection_error`]: #method.is_connection_error
+    pub fn is_retryable(&self) -> bool {
+        if self.is_serialization_failure()
+            || self.is_connection_error()
+            || matches!(self, Error::PoolTimedOut)
+        {
+            return true;
+        }
+
+        #[cfg(feature = "sqlite")]
+        if let Some(code) = self.sqlite_primary_code() {
+            return code == libsqlite3_sys::SQLITE_BUSY || code == libsqlite3_sys::SQLITE_LOCKED;
+        }
+
+        false
+    }
+
+    // the code of a database error if it is a SQLSTATE code (SQLite uses numeric result codes)
+    fn sqlstate(&self) -> Option<Cow<'_, str>> {
+        self.as_database_error()
+            .and_then(|e| e.code())
+            .filter(|code| code.len() == 5)
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn sqlite_primary_code(&self) -> Option<std::os::raw::c_int> {
+        self.as_database_error()?
+            .try_downcast_ref::<crate::sqlite::SqliteError>()
+            .map(|e| e.primary_code())
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub(crate) fn protocol(err: impl Display) -> Self {
@@ -151,6 +263,31 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         None
     }
 
+    /// The name of the constraint that was violated, if the error is about a constraint and
+    /// the database reports it.
+    ///
+    /// This allows handling a specific violation (e.g. of a unique index on `email`) without
+    /// matching on the message.
+    fn constraint(&self) -> Option<&str> {
+        None
+    }
+
+    /// The name of the table the error is about, if the database reports it.
+    fn table(&self) -> Option<&str> {
+        None
+    }
+
+    /// The name of the column the error is about, if the database reports it.
+    fn column(&self) -> Option<&str> {
+        None
+    }
+
+    /// The name of the schema containing the object the error is about, if the database
+    /// reports it.
+    fn schema(&self) -> Option<&str> {
+        None
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static);
 
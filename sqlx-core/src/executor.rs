@@ -1,11 +1,14 @@
 use crate::database::{Database, HasArguments};
 use crate::error::Error;
-use crate::statement::StatementInfo;
+use crate::statement::{PreparedId, StatementInfo};
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -38,6 +41,9 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute multiple queries and return the rows affected from each query, in a stream.
+    ///
+    /// One item is produced for each statement in the query string, in order, as the results
+    /// arrive from the database.
     fn execute_many<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -75,6 +81,47 @@ pub trait Executor<'c>: Send + Debug + Sized {
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of up to
+    /// `chunk_size` rows.
+    ///
+    /// Rows are still streamed from the database; a chunk is produced as soon as it is full.
+    /// The last chunk may be smaller and no chunks are produced for an empty result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is `0`.
+    fn fetch_chunked<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<<Self::Database as Database>::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        assert!(chunk_size > 0, "`chunk_size` must be greater than 0");
+
+        let mut rows = self.fetch(query);
+
+        Box::pin(try_stream! {
+            let mut chunk = Vec::with_capacity(chunk_size);
+
+            while let Some(row) = rows.try_next().await? {
+                chunk.push(row);
+
+                if chunk.len() == chunk_size {
+                    r#yield!(mem::replace(&mut chunk, Vec::with_capacity(chunk_size)));
+                }
+            }
+
+            if !chunk.is_empty() {
+                r#yield!(chunk);
+            }
+
+            Ok(())
+        })
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     fn fetch_many<'e, 'q: 'e, E: 'q>(
@@ -161,6 +208,24 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
     /// prepare the query. Returning `Some(Default::default())` is an empty arguments object that
     /// will be prepared (and cached) before execution.
     fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments>;
+
+    /// Returns the maximum amount of time the query may run for.
+    ///
+    /// See [`Query::timeout`](crate::query::Query::timeout).
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the statement prepared by [`Connection::prepare`] to execute, if this query
+    /// was made from one.
+    ///
+    /// [`Connection::prepare`]: crate::connection::Connection::prepare
+    #[doc(hidden)]
+    #[inline]
+    fn prepared(&self) -> Option<&Arc<PreparedId>> {
+        None
+    }
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
@@ -188,3 +253,29 @@ impl<'q, DB: Database> Execute<'q, DB> for (&'q str, Option<<DB as HasArguments<
         self.1.take()
     }
 }
+
+// for databases that are not told about a query timeout; waits for `future` until `deadline`
+// and, if it passes first, hands the future back unfinished so the driver can keep reading the
+// response while it cancels the query on the server
+#[cfg(any(feature = "mysql", feature = "mssql"))]
+pub(crate) async fn until_deadline<F: std::future::Future + Unpin>(
+    deadline: Option<std::time::Instant>,
+    future: F,
+) -> Result<F::Output, F> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(future.await),
+    };
+
+    let remaining = deadline
+        .checked_duration_since(std::time::Instant::now())
+        .unwrap_or_else(|| Duration::from_secs(0));
+
+    let sleep = sqlx_rt::sleep(remaining);
+    futures_util::pin_mut!(sleep);
+
+    match future::select(future, sleep).await {
+        future::Either::Left((output, _)) => Ok(output),
+        future::Either::Right(((), future)) => Err(future),
+    }
+}
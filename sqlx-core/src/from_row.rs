@@ -19,10 +19,63 @@ use crate::row::Row;
 /// }
 /// ```
 ///
+/// ### Default
+///
+/// A field marked `#[sqlx(default)]` is set to `Default::default()` when its column is not
+/// in the row, so the same struct can be used for queries that select different columns. A
+/// column that is present but `NULL` is still decoded as usual: it is `None` for an `Option`
+/// and an error otherwise.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     #[sqlx(default)]
+///     email: Option<String>,
+/// }
+/// ```
+///
+/// ### Flattening
+///
+/// A field marked `#[sqlx(flatten)]` is built from the same row by the `FromRow` implementation
+/// of its own type. This is useful to decode the result of a join into the structs of each
+/// table. Add `#[sqlx(prefix = "..")]` to read the columns of a flattened struct with a
+/// prefix in front of their names, e.g. to tell apart the `id` columns of two tables.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Customer {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct OrderWithCustomer {
+///     id: i32,
+///     total: i64,
+///     // reads the `customer_id` and `customer_name` columns
+///     #[sqlx(flatten, prefix = "customer_")]
+///     customer: Customer,
+/// }
+/// ```
+///
+/// A prefix is only understood by derived implementations; other implementations of `FromRow`
+/// ignore it.
+///
 /// [`query_as`]: crate::query_as
 /// [`Row::try_get`]: crate::row::Row::try_get
 pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
+
+    /// Builds `Self` from the columns of `row` whose names start with `prefix`.
+    ///
+    /// Used by the derive for fields marked `#[sqlx(flatten, prefix = "..")]`.
+    #[doc(hidden)]
+    fn from_row_with_prefix(row: &'r R, prefix: &str) -> Result<Self, Error> {
+        let _ = prefix;
+
+        Self::from_row(row)
+    }
 }
 
 // implement FromRow for tuples of types that implement Decode
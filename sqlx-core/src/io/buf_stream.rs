@@ -11,6 +11,9 @@ use crate::io::write_and_flush::WriteAndFlush;
 use crate::io::{decode::Decode, encode::Encode};
 use std::io::Cursor;
 
+const WBUF_CAPACITY: usize = 512;
+const RBUF_CAPACITY: usize = 4096;
+
 pub struct BufStream<S>
 where
     S: AsyncRead + AsyncWrite + Unpin,
@@ -23,6 +26,10 @@ where
 
     // we read into the read buffer using 100% safe code
     rbuf: BytesMut,
+
+    // how many bytes at the start of the read buffer were read from the stream and not yet
+    // consumed; the rest of the buffer is zero-filled space to read into
+    rbuf_filled: usize,
 }
 
 impl<S> BufStream<S>
@@ -32,8 +39,9 @@ where
     pub fn new(stream: S) -> Self {
         Self {
             stream,
-            wbuf: Vec::with_capacity(512),
-            rbuf: BytesMut::with_capacity(4096),
+            wbuf: Vec::with_capacity(WBUF_CAPACITY),
+            rbuf: BytesMut::with_capacity(RBUF_CAPACITY),
+            rbuf_filled: 0,
         }
     }
 
@@ -69,14 +77,30 @@ where
     where
         T: Decode<'de, C>,
     {
-        // zero-fills the space in the read buffer
-        self.rbuf.resize(cnt, 0);
+        self.peek(cnt).await?;
+        self.rbuf_filled -= cnt;
+
+        T::decode_with(self.rbuf.split_to(cnt).freeze(), context)
+    }
+
+    /// Read from the stream until at least `cnt` bytes are buffered and return them, without
+    /// consuming them.
+    ///
+    /// Like [`read`](Self::read), this may be cancelled (its future dropped) at any point without
+    /// losing data; the bytes read so far stay buffered for the next read.
+    pub async fn peek(&mut self, cnt: usize) -> Result<&[u8], Error> {
+        if self.rbuf.len() < cnt {
+            // zero-fills the space in the read buffer
+            self.rbuf.resize(cnt, 0);
+        }
 
-        let mut read = 0;
-        while cnt > read {
+        while cnt > self.rbuf_filled {
             // read in bytes from the stream into the read buffer starting
             // from the offset we last read from
-            let n = self.stream.read(&mut self.rbuf[read..]).await?;
+            let n = self
+                .stream
+                .read(&mut self.rbuf[self.rbuf_filled..cnt])
+                .await?;
 
             if n == 0 {
                 // a zero read when we had space in the read buffer
@@ -87,10 +111,22 @@ where
                 return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into());
             }
 
-            read += n;
+            self.rbuf_filled += n;
         }
 
-        T::decode_with(self.rbuf.split_to(cnt).freeze(), context)
+        Ok(&self.rbuf[..cnt])
+    }
+
+    /// Replace the read and write buffers with new ones of the initial capacity, if they
+    /// have grown beyond that and hold no pending data.
+    pub fn shrink_buffers(&mut self) {
+        if self.wbuf.is_empty() && self.wbuf.capacity() > WBUF_CAPACITY {
+            self.wbuf = Vec::with_capacity(WBUF_CAPACITY);
+        }
+
+        if self.rbuf_filled == 0 && self.rbuf.capacity() > RBUF_CAPACITY {
+            self.rbuf = BytesMut::with_capacity(RBUF_CAPACITY);
+        }
     }
 }
 
@@ -56,8 +56,10 @@ pub mod done;
 pub mod executor;
 pub mod from_row;
 mod io;
+mod logging;
 mod net;
 pub mod query_as;
+pub mod query_builder;
 pub mod query_scalar;
 pub mod row;
 pub mod statement;
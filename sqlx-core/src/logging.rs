@@ -1,44 +1,239 @@
 use std::time::Duration;
 
-pub(crate) const SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
-
-/// Logs the query and execution time of a statement as it runs.
-macro_rules! log_execution {
-    ( $query:expr, $block:expr ) => {{
-        // TODO: Log bound parameters
-        let query_string = $query.query_string();
-        let timer = std::time::Instant::now();
-        let result = $block;
-        let elapsed = timer.elapsed();
-        if elapsed >= crate::logging::SLOW_QUERY_THRESHOLD {
-            log::warn!(
+use either::Either;
+
+use crate::done::Done;
+
+/// Settings for recording the execution of statements on a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct LogSettings {
+    // statements that run for at least this long are reported at WARN
+    pub(crate) slow_statement_threshold: Duration,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        LogSettings {
+            slow_statement_threshold: Duration::from_secs(1),
+        }
+    }
+}
+
+pub(crate) use logger::QueryLogger;
+
+// With the `tracing` feature, each statement that is executed gets a `sqlx::query` span, which is
+// entered whenever the future or stream executing the statement is polled. Once the results have
+// been consumed (or the stream of results is dropped), the span is completed with the number of
+// rows and the time elapsed, and an event is emitted: at DEBUG normally, or at WARN if the
+// statement was slow.
+
+#[cfg(feature = "tracing")]
+mod logger {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Instant;
+
+    use futures_core::future::BoxFuture;
+    use futures_core::stream::{BoxStream, Stream};
+    use tracing::{field, Level, Span};
+
+    use super::{parse_query_summary, LogSettings};
+
+    pub(crate) struct QueryLogger<'q> {
+        sql: &'q str,
+        span: Span,
+        slow_statement_threshold: std::time::Duration,
+        rows_returned: u64,
+        rows_affected: u64,
+        start: Instant,
+    }
+
+    impl<'q> QueryLogger<'q> {
+        pub(crate) fn new(sql: &'q str, cached: bool, settings: &LogSettings) -> Self {
+            let span = tracing::span!(
                 target: "sqlx::query",
-                "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
-                elapsed,
-                sqlformat::format(
-                    query_string,
-                    &sqlformat::QueryParams::None,
-                    sqlformat::FormatOptions::default()
-                )
+                Level::DEBUG,
+                "sqlx::query",
+                summary = %parse_query_summary(sql),
+                cached,
+                rows_returned = field::Empty,
+                rows_affected = field::Empty,
+                elapsed = field::Empty,
             );
-        } else {
-            log::debug!(
-                target: "sqlx::query",
-                "{} ..., elapsed: {:.3?}\n\n{}\n",
-                crate::logging::parse_query_summary(query_string),
-                elapsed,
-                sqlformat::format(
-                    query_string,
+
+            Self {
+                sql,
+                span,
+                slow_statement_threshold: settings.slow_statement_threshold,
+                rows_returned: 0,
+                rows_affected: 0,
+                start: Instant::now(),
+            }
+        }
+
+        pub(crate) fn increment_rows_returned(&mut self) {
+            self.rows_returned += 1;
+        }
+
+        pub(crate) fn increase_rows_affected(&mut self, n: u64) {
+            self.rows_affected += n;
+        }
+
+        pub(crate) fn span(&self) -> QuerySpan {
+            QuerySpan(self.span.clone())
+        }
+    }
+
+    /// The span of a statement, to enter while the statement is executed.
+    pub(crate) struct QuerySpan(Span);
+
+    impl QuerySpan {
+        pub(crate) fn instrument_future<'a, T: 'a>(
+            self,
+            inner: BoxFuture<'a, T>,
+        ) -> BoxFuture<'a, T> {
+            Box::pin(Instrumented {
+                inner,
+                span: self.0,
+            })
+        }
+
+        pub(crate) fn instrument_stream<'a, T: 'a>(
+            self,
+            inner: BoxStream<'a, T>,
+        ) -> BoxStream<'a, T> {
+            Box::pin(Instrumented {
+                inner,
+                span: self.0,
+            })
+        }
+    }
+
+    // enters the span whenever the inner future or stream is polled
+    struct Instrumented<T> {
+        inner: T,
+        span: Span,
+    }
+
+    impl<T: Future + Unpin> Future for Instrumented<T> {
+        type Output = T::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = &mut *self;
+            let _entered = this.span.enter();
+
+            Pin::new(&mut this.inner).poll(cx)
+        }
+    }
+
+    impl<T: Stream + Unpin> Stream for Instrumented<T> {
+        type Item = T::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = &mut *self;
+            let _entered = this.span.enter();
+
+            Pin::new(&mut this.inner).poll_next(cx)
+        }
+    }
+
+    impl Drop for QueryLogger<'_> {
+        fn drop(&mut self) {
+            let elapsed = self.start.elapsed();
+
+            self.span.record("rows_returned", self.rows_returned);
+            self.span.record("rows_affected", self.rows_affected);
+            self.span.record("elapsed", field::debug(elapsed));
+
+            let summary = parse_query_summary(self.sql);
+
+            if elapsed >= self.slow_statement_threshold {
+                let sql = sqlformat::format(
+                    self.sql,
                     &sqlformat::QueryParams::None,
-                    sqlformat::FormatOptions::default()
-                )
-            );
+                    sqlformat::FormatOptions::default(),
+                );
+
+                tracing::event!(
+                    target: "sqlx::query",
+                    parent: &self.span,
+                    Level::WARN,
+                    elapsed = ?elapsed,
+                    "slow statement: {} ...\n\n{}\n",
+                    summary,
+                    sql
+                );
+            } else {
+                tracing::event!(
+                    target: "sqlx::query",
+                    parent: &self.span,
+                    Level::DEBUG,
+                    elapsed = ?elapsed,
+                    "{} ...",
+                    summary
+                );
+            }
         }
-        result
-    }};
+    }
 }
 
+#[cfg(not(feature = "tracing"))]
+mod logger {
+    use std::marker::PhantomData;
+
+    use futures_core::future::BoxFuture;
+    use futures_core::stream::BoxStream;
+
+    use super::LogSettings;
+
+    pub(crate) struct QueryLogger<'q>(PhantomData<&'q str>);
+
+    impl<'q> QueryLogger<'q> {
+        #[inline]
+        pub(crate) fn new(_sql: &'q str, _cached: bool, _settings: &LogSettings) -> Self {
+            QueryLogger(PhantomData)
+        }
+
+        #[inline]
+        pub(crate) fn increment_rows_returned(&mut self) {}
+
+        #[inline]
+        pub(crate) fn increase_rows_affected(&mut self, _n: u64) {}
+
+        #[inline]
+        pub(crate) fn span(&self) -> QuerySpan {
+            QuerySpan
+        }
+    }
+
+    pub(crate) struct QuerySpan;
+
+    impl QuerySpan {
+        #[inline]
+        pub(crate) fn instrument_future<'a, T>(self, inner: BoxFuture<'a, T>) -> BoxFuture<'a, T> {
+            inner
+        }
+
+        #[inline]
+        pub(crate) fn instrument_stream<'a, T>(self, inner: BoxStream<'a, T>) -> BoxStream<'a, T> {
+            inner
+        }
+    }
+}
+
+impl QueryLogger<'_> {
+    /// Count a result of the statement.
+    #[inline]
+    pub(crate) fn record<D: Done, R>(&mut self, v: &Either<D, R>) {
+        match v {
+            Either::Left(done) => self.increase_rows_affected(done.rows_affected()),
+            Either::Right(_) => self.increment_rows_returned(),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
 pub(crate) fn parse_query_summary(query: &str) -> String {
     // For now, just take the first 3 words
     query
@@ -47,3 +242,80 @@ pub(crate) fn parse_query_summary(query: &str) -> String {
         .collect::<Vec<&str>>()
         .join(" ")
 }
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures_util::FutureExt;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::subscriber::with_default;
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::{LogSettings, QueryLogger};
+
+    // records, for every event, the name of the span it was emitted in
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<RecorderState>>);
+
+    #[derive(Default)]
+    struct RecorderState {
+        names: Vec<&'static str>,
+        entered: Vec<u64>,
+        events: Vec<Option<&'static str>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut state = self.0.lock().unwrap();
+            state.names.push(span.metadata().name());
+
+            Id::from_u64(state.names.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {
+            let mut state = self.0.lock().unwrap();
+            let current = state.entered.last().map(|&id| state.names[id as usize - 1]);
+
+            state.events.push(current);
+        }
+
+        fn enter(&self, span: &Id) {
+            self.0.lock().unwrap().entered.push(span.into_u64());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.lock().unwrap().entered.pop();
+        }
+    }
+
+    #[test]
+    fn it_enters_the_span_of_the_query_when_polled() {
+        let recorder = Recorder::default();
+
+        with_default(recorder.clone(), || {
+            let logger = QueryLogger::new("SELECT 1", false, &LogSettings::default());
+
+            let executed = logger.span().instrument_future(Box::pin(async {
+                tracing::info!("executing");
+            }));
+
+            assert!(executed.now_or_never().is_some());
+
+            tracing::info!("executed");
+        });
+
+        let state = recorder.0.lock().unwrap();
+
+        assert_eq!(state.events[0], Some("sqlx::query"));
+        assert_eq!(state.events[1], None);
+    }
+}
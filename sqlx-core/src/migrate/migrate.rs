@@ -18,14 +18,22 @@ pub trait MigrateDatabase {
 }
 
 // 'e = Executor
+// `table_name` is the name of the table that tracks the applied migrations, see
+// [`Migrator::set_table_name`](crate::migrate::Migrator::set_table_name)
 pub trait Migrate {
     // ensure migrations table exists
     // will create or migrate it if needed
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>>;
 
     // Return the current version and if the database is "dirty".
     // "dirty" means there is a partially applied migration that failed.
-    fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>>;
+    fn version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>>;
 
     // Should acquire a database lock so that only one migration process
     // can run at a time. [`Migrate`] will call this function before applying
@@ -36,10 +44,17 @@ pub trait Migrate {
     // migrations have been run.
     fn unlock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
 
+    // return the versions of the successfully applied migrations, in ascending order
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<i64>, MigrateError>>;
+
     // validate the migration
     // checks that it does exist on the database and that the checksum matches
     fn validate<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>>;
 
@@ -48,6 +63,57 @@ pub trait Migrate {
     // returns the time taking to run the migration SQL
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
+
+    // run the down SQL from an applied migration in a DDL transaction
+    // delete its row from the [_migrations] table on completion
+    // returns the time taking to run the migration SQL
+    fn revert<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
+}
+
+// quotes each part of a table name that may be qualified with a schema, e.g. `schema.table`
+pub(crate) fn quote_table_name(table_name: &str, quote: char) -> String {
+    let mut quoted = String::with_capacity(table_name.len() + 4);
+
+    for (i, part) in table_name.split('.').enumerate() {
+        if i > 0 {
+            quoted.push('.');
+        }
+
+        quoted.push(quote);
+
+        for c in part.chars() {
+            // the quote character is escaped by doubling it
+            if c == quote {
+                quoted.push(quote);
+            }
+
+            quoted.push(c);
+        }
+
+        quoted.push(quote);
+    }
+
+    quoted
+}
+
+#[test]
+fn test_quote_table_name() {
+    assert_eq!(
+        quote_table_name("_sqlx_migrations", '"'),
+        r#""_sqlx_migrations""#
+    );
+
+    assert_eq!(
+        quote_table_name("schema.__migrations", '"'),
+        r#""schema"."__migrations""#
+    );
+
+    assert_eq!(quote_table_name("my`table", '`'), "`my``table`");
 }
@@ -5,6 +5,8 @@ pub struct Migration {
     pub(crate) version: i64,
     pub(crate) description: Cow<'static, str>,
     pub(crate) sql: Cow<'static, str>,
+    // the SQL to revert the migration, if any
+    pub(crate) down_sql: Option<Cow<'static, str>>,
     pub(crate) checksum: Cow<'static, [u8]>,
 }
 
@@ -16,4 +18,10 @@ impl Migration {
     pub fn description(&self) -> &str {
         &*self.description
     }
+
+    /// Returns `true` if the migration has a `down` script and can be reverted with
+    /// [`Migrator::undo`](crate::migrate::Migrator::undo).
+    pub fn is_reversible(&self) -> bool {
+        self.down_sql.is_some()
+    }
 }
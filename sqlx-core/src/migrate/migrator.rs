@@ -1,11 +1,14 @@
 use crate::acquire::Acquire;
 use crate::migrate::{Migrate, MigrateError, Migration, MigrationSource};
+use std::borrow::Cow;
 use std::ops::Deref;
 use std::slice;
 
 #[derive(Debug)]
 pub struct Migrator {
     migrations: Vec<Migration>,
+    ignore_checksums: bool,
+    table_name: Cow<'static, str>,
 }
 
 impl Migrator {
@@ -32,9 +35,32 @@ impl Migrator {
     {
         Ok(Self {
             migrations: source.resolve().await.map_err(MigrateError::Source)?,
+            ignore_checksums: false,
+            table_name: Cow::Borrowed("_sqlx_migrations"),
         })
     }
 
+    /// Sets the name of the table that tracks the applied migrations; defaults to
+    /// `_sqlx_migrations`.
+    ///
+    /// The name may be qualified with a schema (or, for SQLite, the name of an attached
+    /// database), e.g. `"migrations.__applied"`. Each part is quoted as an identifier. The schema
+    /// must already exist; the table is created if needed.
+    pub fn set_table_name(&mut self, table_name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// Accept previously applied migrations that have been modified since, instead of failing
+    /// with [`MigrateError::VersionMismatch`]; defaults to `false`.
+    ///
+    /// Use this when a migration was changed on purpose, e.g. to fix a comment. The modified
+    /// migration is not applied again.
+    pub fn set_ignore_checksums(&mut self, ignore_checksums: bool) -> &mut Self {
+        self.ignore_checksums = ignore_checksums;
+        self
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -42,6 +68,10 @@ impl Migrator {
 
     /// Run any pending migrations against the database; and, validate previously applied migrations
     /// against the current migration source to detect accidental changes in previously-applied migrations.
+    ///
+    /// The database is locked for the duration of the run (with an advisory lock on PostgreSQL
+    /// and `GET_LOCK` on MySQL). Concurrent runs, e.g. from several instances of an application
+    /// starting at once, wait for the lock and then only validate what has been applied meanwhile.
     pub async fn run<'a, A>(&self, migrator: A) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
@@ -52,11 +82,52 @@ impl Migrator {
         // lock the database for exclusive access by the migrator
         conn.lock().await?;
 
+        let res = self.run_locked(&mut *conn).await;
+
+        // unlock the migrator to allow other migrators to run,
+        // also on failure as the lock would otherwise be held for the life of the connection
+        let unlocked = conn.unlock().await;
+
+        res.and(unlocked)
+    }
+
+    /// Revert the applied migrations newer than `target` against the database, running their
+    /// down scripts in reverse order. The migration with the version `target` (if any) is kept;
+    /// pass `0` to revert all migrations.
+    ///
+    /// Down scripts are given as a `<VERSION>_<DESCRIPTION>.down.sql` file next to the
+    /// `<VERSION>_<DESCRIPTION>.up.sql` of the migration, or after a `-- +migrate Down` line in
+    /// the `<VERSION>_<DESCRIPTION>.sql` file.
+    ///
+    /// Only migrations recorded as applied are reverted. Nothing is reverted if one of them is
+    /// missing in the resolved migrations, has been modified since it was applied, or has no
+    /// down script. The database is locked as in [`run`](Self::run).
+    pub async fn undo<'a, A>(&self, migrator: A, target: i64) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        let mut conn = migrator.acquire().await?;
+
+        // lock the database for exclusive access by the migrator
+        conn.lock().await?;
+
+        let res = self.undo_locked(&mut *conn, target).await;
+
+        let unlocked = conn.unlock().await;
+
+        res.and(unlocked)
+    }
+
+    async fn run_locked<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + ?Sized,
+    {
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        conn.ensure_migrations_table(&self.table_name).await?;
 
-        let (version, dirty) = conn.version().await?.unwrap_or((0, false));
+        let (version, dirty) = conn.version(&self.table_name).await?.unwrap_or((0, false));
 
         if dirty {
             return Err(MigrateError::Dirty(version));
@@ -64,16 +135,68 @@ impl Migrator {
 
         for migration in self.iter() {
             if migration.version() > version {
-                conn.apply(migration).await?;
+                conn.apply(&self.table_name, migration).await?;
             } else {
-                conn.validate(migration).await?;
+                self.validate(conn, migration).await?;
             }
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        conn.unlock().await?;
+        Ok(())
+    }
+
+    async fn undo_locked<C>(&self, conn: &mut C, target: i64) -> Result<(), MigrateError>
+    where
+        C: Migrate + ?Sized,
+    {
+        conn.ensure_migrations_table(&self.table_name).await?;
+
+        let (version, dirty) = conn.version(&self.table_name).await?.unwrap_or((0, false));
+
+        if dirty {
+            return Err(MigrateError::Dirty(version));
+        }
+
+        let mut to_revert = Vec::new();
+
+        // newest first
+        for &applied in conn
+            .list_applied_migrations(&self.table_name)
+            .await?
+            .iter()
+            .rev()
+        {
+            if applied <= target {
+                break;
+            }
+
+            let migration = self
+                .iter()
+                .find(|migration| migration.version() == applied)
+                .ok_or(MigrateError::VersionMissing(applied))?;
+
+            self.validate(conn, migration).await?;
+
+            if !migration.is_reversible() {
+                return Err(MigrateError::Irreversible(applied));
+            }
+
+            to_revert.push(migration);
+        }
+
+        for migration in to_revert {
+            conn.revert(&self.table_name, migration).await?;
+        }
 
         Ok(())
     }
+
+    async fn validate<C>(&self, conn: &mut C, migration: &Migration) -> Result<(), MigrateError>
+    where
+        C: Migrate + ?Sized,
+    {
+        match conn.validate(&self.table_name, migration).await {
+            Err(MigrateError::VersionMismatch(_)) if self.ignore_checksums => Ok(()),
+            res => res,
+        }
+    }
 }
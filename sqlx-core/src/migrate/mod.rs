@@ -5,6 +5,7 @@ mod migrator;
 mod source;
 
 pub use error::MigrateError;
+pub(crate) use migrate::quote_table_name;
 pub use migrate::{Migrate, MigrateDatabase};
 pub use migration::Migration;
 pub use migrator::Migrator;
@@ -5,6 +5,7 @@ use futures_util::TryStreamExt;
 use sha2::{Digest, Sha384};
 use sqlx_rt::fs;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 
@@ -12,11 +13,31 @@ pub trait MigrationSource<'s>: Debug {
     fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>>;
 }
 
+// the line separating the up and down scripts of a migration in a single file
+const DOWN_DELIMITER: &str = "-- +migrate down";
+
+#[derive(Debug, PartialEq)]
+enum MigrationPart {
+    // <VERSION>_<DESCRIPTION>.sql, optionally with a down script after [DOWN_DELIMITER]
+    Full,
+    // <VERSION>_<DESCRIPTION>.up.sql
+    Up,
+    // <VERSION>_<DESCRIPTION>.down.sql
+    Down,
+}
+
+#[derive(Default)]
+struct MigrationFiles {
+    description: String,
+    up: Option<String>,
+    down: Option<String>,
+}
+
 impl<'s> MigrationSource<'s> for &'s Path {
     fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>> {
         Box::pin(async move {
             let mut s = fs::read_dir(self.canonicalize()?).await?;
-            let mut migrations = Vec::new();
+            let mut files = BTreeMap::<i64, MigrationFiles>::new();
 
             while let Some(entry) = s.try_next().await? {
                 if !entry.metadata().await?.is_file() {
@@ -27,37 +48,59 @@ impl<'s> MigrationSource<'s> for &'s Path {
                 let file_name = entry.file_name();
                 let file_name = file_name.to_string_lossy();
 
-                let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
+                let (version, description, part) = match parse_file_name(&file_name) {
+                    Some(parsed) => parsed?,
 
-                if parts.len() != 2 || !parts[1].ends_with(".sql") {
                     // not of the format: <VERSION>_<DESCRIPTION>.sql; ignore
-                    continue;
-                }
+                    None => continue,
+                };
 
-                let version: i64 = parts[0].parse()?;
+                let sql = fs::read_to_string(&entry.path()).await?;
+                let files = files.entry(version).or_default();
 
-                // remove the `.sql` and replace `_` with ` `
-                let description = parts[1]
-                    .trim_end_matches(".sql")
-                    .replace('_', " ")
-                    .to_owned();
+                let duplicate = match part {
+                    MigrationPart::Full => {
+                        let (up, down) = split_down(&sql);
+                        let duplicate = files.up.is_some() || files.down.is_some();
 
-                let sql = fs::read_to_string(&entry.path()).await?;
+                        files.up = Some(up.to_owned());
+                        files.down = down.map(ToOwned::to_owned);
 
-                let checksum = Vec::from(Sha384::digest(sql.as_bytes()).as_slice());
+                        duplicate
+                    }
 
-                migrations.push(Migration {
-                    version,
-                    description: Cow::Owned(description),
-                    sql: Cow::Owned(sql),
-                    checksum: Cow::Owned(checksum),
-                })
+                    MigrationPart::Up => files.up.replace(sql).is_some(),
+                    MigrationPart::Down => files.down.replace(sql).is_some(),
+                };
+
+                if duplicate {
+                    return Err(
+                        format!("found more than one migration for version {}", version).into(),
+                    );
+                }
+
+                files.description = description;
             }
 
-            // ensure that we are sorted by `VERSION ASC`
-            migrations.sort_by_key(|m| m.version);
+            // the map iterates in `VERSION ASC`
+            files
+                .into_iter()
+                .map(|(version, files)| {
+                    let sql = files.up.ok_or_else(|| {
+                        format!("migration {} has a down script but no up script", version)
+                    })?;
+
+                    let checksum = Vec::from(Sha384::digest(sql.as_bytes()).as_slice());
 
-            Ok(migrations)
+                    Ok(Migration {
+                        version,
+                        description: Cow::Owned(files.description),
+                        sql: Cow::Owned(sql),
+                        down_sql: files.down.map(Cow::Owned),
+                        checksum: Cow::Owned(checksum),
+                    })
+                })
+                .collect()
         })
     }
 }
@@ -67,3 +110,96 @@ impl MigrationSource<'static> for PathBuf {
         Box::pin(async move { self.as_path().resolve().await })
     }
 }
+
+// parses `<VERSION>_<DESCRIPTION>.sql` (or `.up.sql` and `.down.sql`) into the version and
+// the description, with `_` replaced by ` `
+fn parse_file_name(file_name: &str) -> Option<Result<(i64, String, MigrationPart), BoxDynError>> {
+    let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
+
+    if parts.len() != 2 || !parts[1].ends_with(".sql") {
+        return None;
+    }
+
+    let version: i64 = match parts[0].parse() {
+        Ok(version) => version,
+        Err(error) => return Some(Err(error.into())),
+    };
+
+    let description = parts[1].trim_end_matches(".sql");
+
+    let (description, part) = if description.ends_with(".up") {
+        (description.trim_end_matches(".up"), MigrationPart::Up)
+    } else if description.ends_with(".down") {
+        (description.trim_end_matches(".down"), MigrationPart::Down)
+    } else {
+        (description, MigrationPart::Full)
+    };
+
+    Some(Ok((version, description.replace('_', " "), part)))
+}
+
+// splits the SQL of a migration into its up and down scripts
+fn split_down(sql: &str) -> (&str, Option<&str>) {
+    let mut offset = 0;
+
+    while offset < sql.len() {
+        // the end of the line, including its newline
+        let end = sql[offset..]
+            .find('\n')
+            .map_or(sql.len(), |i| offset + i + 1);
+
+        if sql[offset..end].trim().eq_ignore_ascii_case(DOWN_DELIMITER) {
+            return (&sql[..offset], Some(&sql[end..]));
+        }
+
+        offset = end;
+    }
+
+    (sql, None)
+}
+
+#[test]
+fn test_parse_file_name() {
+    let parse = |name| parse_file_name(name).map(Result::unwrap);
+
+    assert_eq!(
+        parse("0001_create_users.sql"),
+        Some((1, "create users".to_owned(), MigrationPart::Full))
+    );
+
+    assert_eq!(
+        parse("20200101_create_users.up.sql"),
+        Some((20200101, "create users".to_owned(), MigrationPart::Up))
+    );
+
+    assert_eq!(
+        parse("20200101_create_users.down.sql"),
+        Some((20200101, "create users".to_owned(), MigrationPart::Down))
+    );
+
+    assert!(parse("README.md").is_none());
+    assert!(parse("0001.sql").is_none());
+    assert!(parse_file_name("first_migration.sql").unwrap().is_err());
+}
+
+#[test]
+fn test_split_down() {
+    let sql = "CREATE TABLE users (id INT);\n-- +migrate Down\nDROP TABLE users;\n";
+
+    assert_eq!(
+        split_down(sql),
+        (
+            "CREATE TABLE users (id INT);\n",
+            Some("DROP TABLE users;\n")
+        )
+    );
+
+    assert_eq!(
+        split_down("CREATE TABLE users (id INT);"),
+        ("CREATE TABLE users (id INT);", None)
+    );
+
+    // the delimiter must be on its own line
+    let sql = "SELECT 1; -- +migrate Down\n";
+    assert_eq!(split_down(sql), (sql, None));
+}
@@ -4,6 +4,7 @@ use crate::mssql::database::Mssql;
 use crate::mssql::io::MssqlBufMutExt;
 use crate::mssql::protocol::rpc::StatusFlags;
 use crate::types::Type;
+use std::fmt::{self, Write};
 
 #[derive(Default)]
 pub struct MssqlArguments {
@@ -115,4 +116,12 @@ impl<'q> Arguments<'q> for MssqlArguments {
     {
         self.add(value)
     }
+
+    fn len(&self) -> usize {
+        self.ordinal
+    }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "@p{}", self.ordinal + 1)
+    }
 }
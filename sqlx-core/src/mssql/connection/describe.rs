@@ -94,5 +94,6 @@ pub async fn describe(
         parameters: Some(Either::Right(num_params)),
         columns: (*conn.stream.columns).clone(),
         nullable,
+        parameter_names: None,
     })
 }
@@ -74,6 +74,9 @@ impl MssqlConnection {
             }
         }
 
-        Ok(Self { stream })
+        Ok(Self {
+            stream,
+            log_settings: options.log_settings.clone(),
+        })
     }
 }
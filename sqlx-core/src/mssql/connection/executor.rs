@@ -1,5 +1,6 @@
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{until_deadline, Execute, Executor};
+use crate::logging::QueryLogger;
 use crate::mssql::connection::describe::describe;
 use crate::mssql::protocol::done::Status;
 use crate::mssql::protocol::message::Message;
@@ -11,8 +12,9 @@ use crate::statement::StatementInfo;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
-use futures_util::{FutureExt, TryStreamExt};
+use futures_util::{pin_mut, FutureExt, TryStreamExt};
 use std::sync::Arc;
+use std::time::Instant;
 
 impl MssqlConnection {
     async fn run(&mut self, query: &str, arguments: Option<MssqlArguments>) -> Result<(), Error> {
@@ -58,6 +60,29 @@ impl MssqlConnection {
 
         Ok(())
     }
+
+    // sends an attention signal to cancel the running request and reads the rest of its
+    // response, up to the DONE that acknowledges the attention
+    async fn cancel(&mut self) -> Result<(), Error> {
+        self.stream
+            .write_packet(PacketType::AttentionSignal, &[][..]);
+        self.stream.flush().await?;
+
+        loop {
+            match self.stream.recv_message().await {
+                Ok(Message::Done(done)) if done.status.contains(Status::DONE_ATTN) => break,
+
+                // the cancelled request may end with an error
+                Ok(_) | Err(Error::Database(_)) => {}
+
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.stream.pending_done_count = 0;
+
+        Ok(())
+    }
 }
 
 impl<'c> Executor<'c> for &'c mut MssqlConnection {
@@ -73,19 +98,44 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
     {
         let s = query.query();
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+
+        let mut logger = QueryLogger::new(s, false, &self.log_settings);
+
+        logger.span().instrument_stream(Box::pin(try_stream! {
+            let deadline = timeout.map(|timeout| Instant::now() + timeout);
 
-        Box::pin(try_stream! {
             self.run(s, arguments).await?;
 
             loop {
-                let message = self.stream.recv_message().await?;
+                let received = {
+                    let recv = self.stream.recv_message();
+                    pin_mut!(recv);
+
+                    until_deadline(deadline, recv).await.ok()
+                };
+
+                let message = match received {
+                    Some(message) => message?,
+
+                    // receiving is cancel-safe, any part of a packet read so far stays buffered
+                    // for the attention that follows
+                    None => {
+                        self.cancel().await?;
+
+                        return Err(Error::QueryTimedOut);
+                    }
+                };
 
                 match message {
                     Message::Row(row) => {
                         let columns = Arc::clone(&self.stream.columns);
                         let column_names = Arc::clone(&self.stream.column_names);
 
-                        r#yield!(Either::Right(MssqlRow { row, column_names, columns }));
+                        let v = Either::Right(MssqlRow { row, column_names, columns });
+                        logger.record(&v);
+
+                        r#yield!(v);
                     }
 
                     Message::Done(done) | Message::DoneProc(done) => {
@@ -94,9 +144,12 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
                         }
 
                         if done.status.contains(Status::DONE_COUNT) {
-                            r#yield!(Either::Left(MssqlDone {
+                            let v = Either::Left(MssqlDone {
                                 rows_affected: done.affected_rows,
-                            }));
+                            });
+                            logger.record(&v);
+
+                            r#yield!(v);
                         }
 
                         if !done.status.contains(Status::DONE_MORE) {
@@ -106,9 +159,12 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
 
                     Message::DoneInProc(done) => {
                         if done.status.contains(Status::DONE_COUNT) {
-                            r#yield!(Either::Left(MssqlDone {
+                            let v = Either::Left(MssqlDone {
                                 rows_affected: done.affected_rows,
-                            }));
+                            });
+                            logger.record(&v);
+
+                            r#yield!(v);
                         }
                     }
 
@@ -117,7 +173,7 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
             }
 
             Ok(())
-        })
+        }))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -1,9 +1,10 @@
 use crate::connection::Connection;
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::logging::LogSettings;
 use crate::mssql::connection::stream::MssqlStream;
 use crate::mssql::{Mssql, MssqlConnectOptions};
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use futures_util::{future::ready, FutureExt, TryFutureExt};
 use std::fmt::{self, Debug, Formatter};
@@ -16,6 +17,7 @@ mod stream;
 
 pub struct MssqlConnection {
     pub(crate) stream: MssqlStream,
+    log_settings: LogSettings,
 }
 
 impl Debug for MssqlConnection {
@@ -46,6 +48,20 @@ impl Connection for MssqlConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
+    fn shrink_buffers(&mut self) {
+        self.stream.shrink_buffers();
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
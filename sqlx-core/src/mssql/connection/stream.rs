@@ -1,11 +1,11 @@
 use std::ops::{Deref, DerefMut};
 
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use sqlx_rt::TcpStream;
 
 use crate::error::Error;
 use crate::ext::ustr::UStr;
-use crate::io::{BufStream, Encode};
+use crate::io::{BufStream, Decode, Encode};
 use crate::mssql::protocol::col_meta_data::ColMetaData;
 use crate::mssql::protocol::done::{Done, Status as DoneStatus};
 use crate::mssql::protocol::env_change::EnvChange;
@@ -94,7 +94,8 @@ impl MssqlStream {
     pub(super) async fn recv_packet(&mut self) -> Result<(PacketHeader, Bytes), Error> {
         // TODO: Support packet chunking for large packet sizes
 
-        let header: PacketHeader = self.inner.read(8).await?;
+        // the header is only peeked at so a cancelled read leaves the whole packet buffered
+        let header = PacketHeader::decode(Bytes::copy_from_slice(self.inner.peek(8).await?))?;
 
         // NOTE: From what I can tell, the response type from the server should ~always~
         //       be TabularResult. Here we expect that and die otherwise.
@@ -105,8 +106,8 @@ impl MssqlStream {
             ));
         }
 
-        let payload_len = (header.length - 8) as usize;
-        let payload: Bytes = self.inner.read(payload_len).await?;
+        let mut payload: Bytes = self.inner.read(header.length as usize).await?;
+        payload.advance(8);
 
         Ok((header, payload))
     }
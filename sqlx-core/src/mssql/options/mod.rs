@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use crate::logging::LogSettings;
+
 mod connect;
 mod parse;
 
@@ -8,6 +12,7 @@ pub struct MssqlConnectOptions {
     pub(crate) username: String,
     pub(crate) database: String,
     pub(crate) password: Option<String>,
+    pub(crate) log_settings: LogSettings,
 }
 
 impl Default for MssqlConnectOptions {
@@ -24,6 +29,7 @@ impl MssqlConnectOptions {
             database: String::from("master"),
             username: String::from("sa"),
             password: None,
+            log_settings: Default::default(),
         }
     }
 
@@ -51,4 +57,14 @@ impl MssqlConnectOptions {
         self.database = database.to_owned();
         self
     }
+
+    /// Sets the execution time at or above which a statement is reported as slow.
+    ///
+    /// When the `tracing` feature is enabled, every statement executed on the connection
+    /// is recorded in a `sqlx::query` span and statements that run at least this long
+    /// additionally emit an event at the `WARN` level. The default threshold is 1 second.
+    pub fn slow_statement_threshold(mut self, threshold: Duration) -> Self {
+        self.log_settings.slow_statement_threshold = threshold;
+        self
+    }
 }
@@ -7,7 +7,7 @@ use crate::executor::Executor;
 use crate::mssql::protocol::packet::PacketType;
 use crate::mssql::protocol::sql_batch::SqlBatch;
 use crate::mssql::{Mssql, MssqlConnection};
-use crate::transaction::TransactionManager;
+use crate::transaction::{TransactionManager, TransactionOptions};
 
 /// Implementation of [`TransactionManager`] for MSSQL.
 pub struct MssqlTransactionManager;
@@ -15,11 +15,27 @@ pub struct MssqlTransactionManager;
 impl TransactionManager for MssqlTransactionManager {
     type Database = Mssql;
 
-    fn begin(conn: &mut MssqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+    fn begin_with(
+        conn: &mut MssqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.stream.transaction_depth;
 
-            let query = if depth == 0 {
+            options.validate(depth)?;
+
+            if options.read_only {
+                return Err(Error::Configuration(
+                    "read-only transactions are not supported by MSSQL".into(),
+                ));
+            }
+
+            let query = if let Some(level) = options.isolation_level {
+                Cow::Owned(format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}; BEGIN TRAN ",
+                    level.as_sql()
+                ))
+            } else if depth == 0 {
                 Cow::Borrowed("BEGIN TRAN ")
             } else {
                 Cow::Owned(format!("SAVE TRAN _sqlx_savepoint_{}", depth))
@@ -42,4 +42,8 @@ impl<'q> Arguments<'q> for MySqlArguments {
     {
         self.add(value)
     }
+
+    fn len(&self) -> usize {
+        self.types.len()
+    }
 }
@@ -18,6 +18,7 @@ impl MySqlConnection {
         // https://mariadb.com/kb/en/connection/
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
+        let server_version = parse_server_version(&handshake.server_version);
 
         let mut plugin = handshake.auth_plugin;
         let mut nonce = handshake.auth_plugin_data;
@@ -99,9 +100,51 @@ impl MySqlConnection {
         Ok(Self {
             stream,
             transaction_depth: 0,
+            server_version,
+            connection_id: handshake.connection_id,
+            options: options.clone(),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            closed_statements: Default::default(),
+            cache_describe: StatementCache::new(if options.describe_cache {
+                options.statement_cache_capacity
+            } else {
+                0
+            }),
+            log_settings: options.log_settings.clone(),
             scratch_row_columns: Default::default(),
             scratch_row_column_names: Default::default(),
         })
     }
 }
+
+// parses a server version such as `8.0.21`, `5.7.31-log` or `5.5.5-10.4.12-MariaDB-1:10.4.12`
+fn parse_server_version(s: &str) -> Option<(u32, u32, u32)> {
+    // MariaDB prefixes its version with `5.5.5-` for the sake of old clients
+    let s = match s.strip_prefix("5.5.5-") {
+        Some(rest) if rest.contains("MariaDB") => rest,
+        _ => s,
+    };
+
+    let end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or_else(|| s.len());
+
+    let mut parts = s[..end].split('.').map(str::parse::<u32>);
+
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch))) => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(parse_server_version("8.0.21"), Some((8, 0, 21)));
+    assert_eq!(parse_server_version("5.7.31-log"), Some((5, 7, 31)));
+    assert_eq!(
+        parse_server_version("5.5.5-10.4.12-MariaDB-1:10.4.12+maria~bionic"),
+        Some((10, 4, 12))
+    );
+    assert_eq!(parse_server_version("5.5.5"), Some((5, 5, 5)));
+    assert_eq!(parse_server_version("8.0"), None);
+}
@@ -1,15 +1,20 @@
+use std::io;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
+use futures_util::future::join;
 use futures_util::{pin_mut, TryStreamExt};
 
+use crate::connection::Connection;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{until_deadline, Execute, Executor};
 use crate::ext::ustr::UStr;
+use crate::logging::QueryLogger;
 use crate::mysql::connection::stream::Busy;
 use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::response::Status;
@@ -19,12 +24,76 @@ use crate::mysql::protocol::statement::{
 use crate::mysql::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
 use crate::mysql::protocol::Packet;
 use crate::mysql::{
-    MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlDone, MySqlRow, MySqlTypeInfo,
-    MySqlValueFormat,
+    MySql, MySqlArguments, MySqlColumn, MySqlConnectOptions, MySqlConnection, MySqlDone, MySqlRow,
+    MySqlTypeInfo, MySqlValueFormat,
 };
 use crate::statement::StatementInfo;
 
 impl MySqlConnection {
+    // closes the statements prepared by [Connection::prepare] that were dropped since
+    pub(super) async fn close_dropped_statements(&mut self) -> Result<(), Error> {
+        while let Some(statement) = self.closed_statements.pop() {
+            self.stream.send_packet(StmtClose { statement }).await?;
+        }
+
+        Ok(())
+    }
+
+    // prepares the query as a new statement, outside of the statement cache, and describes it
+    pub(super) async fn prepare_and_describe(
+        &mut self,
+        query: &str,
+    ) -> Result<(u32, StatementInfo<MySql>), Error> {
+        self.stream.wait_until_ready().await?;
+        self.close_dropped_statements().await?;
+
+        self.stream.send_packet(Prepare { query }).await?;
+
+        let ok: PrepareOk = self.stream.recv().await?;
+
+        let mut columns = Vec::with_capacity(ok.columns as usize);
+        let mut nullable = Vec::with_capacity(ok.columns as usize);
+
+        if ok.params > 0 {
+            for _ in 0..ok.params {
+                let _ = self.stream.recv_packet().await?;
+            }
+
+            self.stream.maybe_recv_eof().await?;
+        }
+
+        // the column definitions are berefit the type information from the
+        // to-be-bound parameters; we will receive the output column definitions
+        // once more on execute so we wait for that
+
+        if ok.columns > 0 {
+            for ordinal in 0..(ok.columns as usize) {
+                let def: ColumnDefinition = self.stream.recv().await?;
+                let ty = MySqlTypeInfo::from_column(&def);
+                let alias = def.alias()?;
+
+                nullable.push(Some(!def.flags.contains(ColumnFlags::NOT_NULL)));
+
+                columns.push(MySqlColumn {
+                    ordinal,
+                    name: UStr::new(if alias.is_empty() { def.name()? } else { alias }),
+                    type_info: ty,
+                })
+            }
+
+            self.stream.maybe_recv_eof().await?;
+        }
+
+        let info = StatementInfo {
+            parameters: Some(Either::Right(ok.params as usize)),
+            columns,
+            nullable,
+            parameter_names: None,
+        };
+
+        Ok((ok.statement_id, info))
+    }
+
     async fn prepare(&mut self, query: &str) -> Result<u32, Error> {
         if let Some(statement) = self.cache_statement.get_mut(query) {
             return Ok(*statement);
@@ -60,6 +129,11 @@ impl MySqlConnection {
             self.stream.maybe_recv_eof().await?;
         }
 
+        if !self.cache_statement.is_enabled() {
+            // caching is disabled; the statement is closed in [run] once it has been executed
+            return Ok(ok.statement_id);
+        }
+
         // in case of the cache being full, close the least recently used statement
         if let Some(statement) = self.cache_statement.insert(query, ok.statement_id) {
             self.stream.send_packet(StmtClose { statement }).await?;
@@ -112,12 +186,27 @@ impl MySqlConnection {
         &'c mut self,
         query: &str,
         arguments: Option<MySqlArguments>,
+        prepared: Option<u32>,
     ) -> Result<impl Stream<Item = Result<Either<MySqlDone, MySqlRow>, Error>> + 'c, Error> {
+        // the number of parameters of a statement is encoded in 16 bits
+        if let Some(arguments) = &arguments {
+            if arguments.types.len() > u16::MAX as usize {
+                return Err(Error::TooManyArguments {
+                    count: arguments.types.len(),
+                    max: u16::MAX as usize,
+                });
+            }
+        }
+
         self.stream.wait_until_ready().await?;
+        self.close_dropped_statements().await?;
         self.stream.busy = Busy::Result;
 
         let format = if let Some(arguments) = arguments {
-            let statement = self.prepare(query).await?;
+            let statement = match prepared {
+                Some(statement) => statement,
+                None => self.prepare(query).await?,
+            };
 
             // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
             self.stream
@@ -127,6 +216,13 @@ impl MySqlConnection {
                 })
                 .await?;
 
+            if prepared.is_none() && !self.cache_statement.is_enabled() {
+                // the statement will not be used again; the server handles commands in order
+                // so it is safe to close it before reading the result of the execute
+                // https://dev.mysql.com/doc/internals/en/com-stmt-close.html
+                self.stream.send_packet(StmtClose { statement }).await?;
+            }
+
             MySqlValueFormat::Binary
         } else {
             // https://dev.mysql.com/doc/internals/en/com-query.html
@@ -207,6 +303,25 @@ impl MySqlConnection {
     }
 }
 
+// bounds opening the connection `KILL QUERY` is sent from, so an unreachable server cannot hang
+// the cancellation; the same as the default connect timeout of a pool
+const KILL_QUERY_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// asks the server, from a second connection, to cancel the query running on `connection_id`
+async fn kill_query(options: &MySqlConnectOptions, connection_id: u32) -> Result<(), Error> {
+    let connect = MySqlConnection::establish(options);
+
+    let mut conn = match sqlx_rt::timeout(KILL_QUERY_CONNECT_TIMEOUT, connect).await {
+        Ok(conn) => conn?,
+        Err(_) => return Err(io::Error::from(io::ErrorKind::TimedOut).into()),
+    };
+
+    conn.execute(&*format!("KILL QUERY {}", connection_id))
+        .await?;
+
+    conn.close().await
+}
+
 impl<'c> Executor<'c> for &'c mut MySqlConnection {
     type Database = MySql;
 
@@ -220,17 +335,81 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     {
         let s = query.query();
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+        // held until the statement has been executed, so it is not closed before
+        let prepared = query.prepared().cloned();
+        let id = prepared
+            .as_ref()
+            .and_then(|id| id.id_on(&self.closed_statements));
 
-        Box::pin(try_stream! {
-            let s = self.run(s, arguments).await?;
-            pin_mut!(s);
+        let cached = arguments.is_some() && (id.is_some() || self.cache_statement.contains_key(s));
+        let mut logger = QueryLogger::new(s, cached, &self.log_settings);
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
+        logger.span().instrument_stream(Box::pin(try_stream! {
+            let _prepared = prepared;
+            let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+            // only needed to cancel the query once the deadline passes
+            let kill = deadline.map(|_| (self.options.clone(), self.connection_id));
+            let mut killed = None;
+
+            {
+                let s = self.run(s, arguments, id).await?;
+                pin_mut!(s);
+
+                loop {
+                    let next = match &kill {
+                        Some((options, connection_id)) if killed.is_none() => {
+                            match until_deadline(deadline, s.try_next()).await {
+                                Ok(next) => next,
+
+                                // the read in flight keeps going while the query is killed
+                                // so none of the response is lost
+                                Err(next) => {
+                                    let (next, result) =
+                                        join(next, kill_query(options, *connection_id)).await;
+
+                                    killed = Some(result);
+                                    next
+                                }
+                            }
+                        }
+
+                        _ => s.try_next().await,
+                    };
+
+                    match next {
+                        // the rest of the result of a killed query is discarded
+                        Ok(Some(_)) if killed.is_some() => {}
+
+                        Ok(Some(v)) => {
+                            logger.record(&v);
+                            r#yield!(v);
+                        }
+
+                        Ok(None) => break,
+
+                        // the query ends with ER_QUERY_INTERRUPTED, unless it finished first
+                        Err(Error::Database(_)) if killed.is_some() => break,
+
+                        Err(error) => return Err(error),
+                    }
+                }
+            }
+
+            if let Some(result) = killed {
+                match self.stream.wait_until_ready().await {
+                    Ok(()) | Err(Error::Database(_)) => {}
+                    Err(error) => return Err(error),
+                }
+
+                result?;
+
+                return Err(Error::QueryTimedOut);
             }
 
             Ok(())
-        })
+        }))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -266,48 +445,20 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         let query = query.query();
 
         Box::pin(async move {
-            self.stream.send_packet(Prepare { query }).await?;
-
-            let ok: PrepareOk = self.stream.recv().await?;
-
-            let mut columns = Vec::with_capacity(ok.columns as usize);
-            let mut nullable = Vec::with_capacity(ok.columns as usize);
-
-            if ok.params > 0 {
-                for _ in 0..ok.params {
-                    let _ = self.stream.recv_packet().await?;
-                }
-
-                self.stream.maybe_recv_eof().await?;
+            if let Some(info) = self.cache_describe.get_mut(query) {
+                return Ok(info.clone());
             }
 
-            // the column definitions are berefit the type information from the
-            // to-be-bound parameters; we will receive the output column definitions
-            // once more on execute so we wait for that
-
-            if ok.columns > 0 {
-                for ordinal in 0..(ok.columns as usize) {
-                    let def: ColumnDefinition = self.stream.recv().await?;
-                    let ty = MySqlTypeInfo::from_column(&def);
-                    let alias = def.alias()?;
-
-                    nullable.push(Some(!def.flags.contains(ColumnFlags::NOT_NULL)));
+            let (statement, info) = self.prepare_and_describe(query).await?;
 
-                    columns.push(MySqlColumn {
-                        ordinal,
-                        name: UStr::new(if alias.is_empty() { def.name()? } else { alias }),
-                        type_info: ty,
-                    })
-                }
+            // the statement was only prepared to be described
+            self.stream.send_packet(StmtClose { statement }).await?;
 
-                self.stream.maybe_recv_eof().await?;
+            if self.cache_describe.is_enabled() {
+                self.cache_describe.insert(query, info.clone());
             }
 
-            Ok(StatementInfo {
-                parameters: Some(Either::Right(ok.params as usize)),
-                columns,
-                nullable,
-            })
+            Ok(info)
         })
     }
 }
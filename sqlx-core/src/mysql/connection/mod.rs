@@ -8,10 +8,13 @@ use hashbrown::HashMap;
 use crate::common::StatementCache;
 use crate::connection::Connection;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
+use crate::logging::LogSettings;
 use crate::mysql::protocol::statement::StmtClose;
-use crate::mysql::protocol::text::{Ping, Quit};
-use crate::mysql::{MySql, MySqlColumn, MySqlConnectOptions};
+use crate::mysql::protocol::text::{Ping, Quit, ResetConnection};
+use crate::mysql::{MySql, MySqlCapabilities, MySqlColumn, MySqlConnectOptions};
+use crate::statement::{ClosedStatements, PreparedId, Statement, StatementInfo};
 
 mod auth;
 mod establish;
@@ -19,7 +22,7 @@ mod executor;
 mod stream;
 mod tls;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 pub(crate) use stream::{Busy, MySqlStream};
 
 const COLLATE_UTF8MB4_UNICODE_CI: u8 = 224;
@@ -27,6 +30,14 @@ const COLLATE_UTF8MB4_UNICODE_CI: u8 = 224;
 const MAX_PACKET_SIZE: u32 = 1024;
 
 /// A connection to a MySQL database.
+///
+/// A query may produce more than one result set, e.g. a string of several statements or a
+/// `CALL` to a stored procedure. [`Executor::fetch_many`] yields the rows of every result set in
+/// order, each followed by a [`MySqlDone`]; a stored procedure additionally ends with the
+/// `MySqlDone` of the `CALL` itself.
+///
+/// [`Executor::fetch_many`]: crate::executor::Executor::fetch_many
+/// [`MySqlDone`]: crate::mysql::MySqlDone
 pub struct MySqlConnection {
     // underlying TCP stream,
     // wrapped in a potentially TLS stream,
@@ -36,9 +47,26 @@ pub struct MySqlConnection {
     // transaction status
     pub(crate) transaction_depth: usize,
 
+    // server version from the initial handshake as `(major, minor, patch)`
+    server_version: Option<(u32, u32, u32)>,
+
+    // id of this connection on the server and the options to open another one with,
+    // to cancel a timed out query with `KILL QUERY`
+    connection_id: u32,
+    options: MySqlConnectOptions,
+
     // cache by query string to the statement id
     cache_statement: StatementCache<u32>,
 
+    // statements prepared by [Connection::prepare] that are to be closed
+    closed_statements: Arc<ClosedStatements>,
+
+    // cache the results of [describe] by query string
+    cache_describe: StatementCache<StatementInfo<MySql>>,
+
+    // settings for recording executed statements
+    log_settings: LogSettings,
+
     // working memory for the active row's column information
     // this allows us to re-use these allocations unless the user is persisting the
     // Row type past a stream iteration (clone-on-write)
@@ -46,6 +74,26 @@ pub struct MySqlConnection {
     scratch_row_column_names: Arc<HashMap<UStr, usize>>,
 }
 
+impl MySqlConnection {
+    /// The version of the connected server as `(major, minor, patch)`, e.g. `(8, 0, 21)`.
+    ///
+    /// This is parsed from the version string sent when the connection was established and is
+    /// `None` if it could not be understood. For MariaDB, this is the MariaDB version rather than
+    /// the `5.5.5-` prefix it reports for compatibility.
+    pub fn server_version(&self) -> Option<(u32, u32, u32)> {
+        self.server_version
+    }
+
+    /// The capabilities of this connection, as negotiated with the server when the connection
+    /// was established.
+    ///
+    /// These are the capabilities that both the server and SQLx support, e.g.,
+    /// [`MySqlCapabilities::SSL`] if the connection is encrypted.
+    pub fn capabilities(&self) -> MySqlCapabilities {
+        self.stream.capabilities
+    }
+}
+
 impl Debug for MySqlConnection {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("MySqlConnection").finish()
@@ -76,6 +124,48 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            // the server deallocates all prepared statements of the session
+            self.cache_statement.clear();
+            self.cache_describe.clear();
+
+            self.stream.send_packet(ResetConnection).await?;
+            self.stream.recv_ok().await?;
+
+            // the statements of earlier [Connection::prepare] calls are gone as well
+            self.closed_statements = Arc::default();
+
+            // so are the session parameters configured when the connection was opened
+            self.configure_session().await
+        })
+    }
+
+    fn prepare<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Statement<'q, MySql>, Error>>
+    where
+        Self: Sized,
+        for<'c> &'c mut Self: Executor<'c, Database = Self::Database>,
+    {
+        Box::pin(async move {
+            let (id, info) = self.prepare_and_describe(sql).await?;
+
+            Ok(Statement {
+                sql,
+                info,
+                id: Some(Arc::new(PreparedId::new(id, &self.closed_statements))),
+            })
+        })
+    }
+
+    fn shrink_buffers(&mut self) {
+        self.stream.shrink_buffers();
+    }
+
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
     }
@@ -86,6 +176,10 @@ impl Connection for MySqlConnection {
 
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            self.cache_describe.clear();
+
             while let Some(statement) = self.cache_statement.remove_lru() {
                 self.stream.send_packet(StmtClose { statement }).await?;
             }
@@ -105,4 +199,14 @@ impl Connection for MySqlConnection {
     {
         Transaction::begin(self)
     }
+
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
 }
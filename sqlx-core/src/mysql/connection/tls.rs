@@ -1,6 +1,6 @@
 use sqlx_rt::{
     fs,
-    native_tls::{Certificate, TlsConnector},
+    native_tls::{Certificate, Identity, TlsConnector},
 };
 
 use crate::error::Error;
@@ -67,6 +67,25 @@ async fn upgrade(stream: &mut MySqlStream, options: &MySqlConnectOptions) -> Res
         }
     }
 
+    // authenticate with a client certificate
+    match (&options.ssl_cert, &options.ssl_key) {
+        (Some(cert), Some(key)) => {
+            let cert = fs::read(cert).await?;
+            let key = fs::read(key).await?;
+            let identity = Identity::from_pkcs8(&cert, &key).map_err(Error::tls)?;
+
+            builder.identity(identity);
+        }
+
+        (None, None) => {}
+
+        _ => {
+            return Err(Error::Configuration(
+                "`ssl_cert` and `ssl_key` must be set together".into(),
+            ));
+        }
+    }
+
     #[cfg(not(feature = "runtime-async-std"))]
     let connector = builder.build().map_err(Error::tls)?;
 
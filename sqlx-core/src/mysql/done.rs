@@ -9,6 +9,12 @@ pub struct MySqlDone {
 }
 
 impl MySqlDone {
+    /// Returns the value generated for an `AUTO_INCREMENT` column by the last `INSERT`, or `0`
+    /// if no value was generated.
+    ///
+    /// For a multiple-row `INSERT`, this is the value generated for the _first_ row, as with
+    /// `LAST_INSERT_ID()`. For multiple statements, this is the value generated by the last
+    /// statement that generated one.
     pub fn last_insert_id(&self) -> u64 {
         self.last_insert_id
     }
@@ -26,7 +32,12 @@ impl Extend<MySqlDone> for MySqlDone {
     fn extend<T: IntoIterator<Item = MySqlDone>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
-            self.last_insert_id = elem.last_insert_id;
+
+            // statements that do not generate a value (e.g. a `SELECT` after the `INSERT`)
+            // report 0, which must not replace the generated value
+            if elem.last_insert_id != 0 {
+                self.last_insert_id = elem.last_insert_id;
+            }
         }
     }
 }
@@ -40,3 +51,22 @@ impl From<MySqlDone> for crate::any::AnyDone {
         }
     }
 }
+
+#[test]
+fn test_extend_keeps_last_insert_id() {
+    let mut done = MySqlDone::default();
+
+    done.extend(vec![
+        MySqlDone {
+            rows_affected: 2,
+            last_insert_id: 5,
+        },
+        MySqlDone {
+            rows_affected: 1,
+            last_insert_id: 0,
+        },
+    ]);
+
+    assert_eq!(done.rows_affected(), 3);
+    assert_eq!(done.last_insert_id(), 5);
+}
@@ -27,6 +27,93 @@ impl MySqlDatabaseError {
     pub fn message(&self) -> &str {
         &self.0.error_message
     }
+
+    /// The name of the violated constraint, parsed from the message of a duplicate key, foreign
+    /// key or check constraint error.
+    ///
+    /// For a duplicate key, this is the name of the unique index (`PRIMARY` for the
+    /// primary key).
+    pub fn constraint(&self) -> Option<&str> {
+        let message = self.message();
+
+        match self.number() {
+            // Duplicate entry 'a@b.c' for key 'users.email'
+            // (before MySQL 8.0.19, the key is not prefixed with the table)
+            ER_DUP_ENTRY => {
+                let key = duplicate_key(message)?;
+
+                Some(key.rsplit('.').next().unwrap_or(key))
+            }
+
+            // .. a foreign key constraint fails (`db`.`posts`, CONSTRAINT `fk_user` FOREIGN KEY ..
+            ER_ROW_IS_REFERENCED_2 | ER_NO_REFERENCED_ROW_2 => {
+                between(message, "CONSTRAINT `", '`')
+            }
+
+            // Check constraint 'positive_balance' is violated.
+            ER_CHECK_CONSTRAINT_VIOLATED => between(message, "constraint '", '\''),
+
+            _ => None,
+        }
+    }
+
+    /// The name of the table, parsed from the message of a duplicate key (MySQL 8.0.19+) or
+    /// foreign key error.
+    pub fn table(&self) -> Option<&str> {
+        let message = self.message();
+
+        match self.number() {
+            ER_DUP_ENTRY => {
+                let key = duplicate_key(message)?;
+
+                key.rfind('.').map(|dot| &key[..dot])
+            }
+
+            ER_ROW_IS_REFERENCED_2 | ER_NO_REFERENCED_ROW_2 => {
+                let table = between(message, "fails (", ',')?;
+                let table = table.rsplit('.').next().unwrap_or(table);
+
+                Some(table.trim_matches('`'))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// The name of the column, parsed from the message of an error about a `NULL` value in a
+    /// `NOT NULL` column.
+    pub fn column(&self) -> Option<&str> {
+        match self.number() {
+            // Column 'email' cannot be null
+            ER_BAD_NULL_ERROR => between(self.message(), "Column '", '\''),
+
+            _ => None,
+        }
+    }
+}
+
+// https://dev.mysql.com/doc/mysql-errors/8.0/en/server-error-reference.html
+const ER_BAD_NULL_ERROR: u16 = 1048;
+const ER_DUP_ENTRY: u16 = 1062;
+const ER_ROW_IS_REFERENCED_2: u16 = 1451;
+const ER_NO_REFERENCED_ROW_2: u16 = 1452;
+const ER_CHECK_CONSTRAINT_VIOLATED: u16 = 3819;
+
+// the text after the first `prefix` in `message`, up to `end`
+fn between<'a>(message: &'a str, prefix: &str, end: char) -> Option<&'a str> {
+    let start = message.find(prefix)? + prefix.len();
+    let len = message[start..].find(end)?;
+
+    Some(&message[start..start + len])
+}
+
+fn duplicate_key(message: &str) -> Option<&str> {
+    // the duplicate value comes first and may contain anything, so search from the end
+    const PREFIX: &str = " for key '";
+
+    let start = message.rfind(PREFIX)? + PREFIX.len();
+
+    Some(message[start..].trim_end_matches('\''))
 }
 
 impl Debug for MySqlDatabaseError {
@@ -62,6 +149,21 @@ impl DatabaseError for MySqlDatabaseError {
         self.code().map(Cow::Borrowed)
     }
 
+    #[inline]
+    fn constraint(&self) -> Option<&str> {
+        self.constraint()
+    }
+
+    #[inline]
+    fn table(&self) -> Option<&str> {
+        self.table()
+    }
+
+    #[inline]
+    fn column(&self) -> Option<&str> {
+        self.column()
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
         self
@@ -77,3 +179,41 @@ impl DatabaseError for MySqlDatabaseError {
         self
     }
 }
+
+#[cfg(test)]
+fn database_error(error_code: u16, error_message: &str) -> MySqlDatabaseError {
+    MySqlDatabaseError(ErrPacket {
+        error_code,
+        sql_state: None,
+        error_message: error_message.to_owned(),
+    })
+}
+
+#[test]
+fn test_parse_constraint_details() {
+    let e = database_error(
+        1062,
+        "Duplicate entry 'a' for key 'b' for key 'users.email'",
+    );
+    assert_eq!(e.constraint(), Some("email"));
+    assert_eq!(e.table(), Some("users"));
+
+    let e = database_error(1062, "Duplicate entry '1' for key 'PRIMARY'");
+    assert_eq!(e.constraint(), Some("PRIMARY"));
+    assert_eq!(e.table(), None);
+
+    let e = database_error(
+        1452,
+        "Cannot add or update a child row: a foreign key constraint fails \
+         (`blog`.`posts`, CONSTRAINT `fk_user` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`))",
+    );
+    assert_eq!(e.constraint(), Some("fk_user"));
+    assert_eq!(e.table(), Some("posts"));
+
+    let e = database_error(3819, "Check constraint 'positive_balance' is violated.");
+    assert_eq!(e.constraint(), Some("positive_balance"));
+
+    let e = database_error(1048, "Column 'email' cannot be null");
+    assert_eq!(e.column(), Some("email"));
+    assert_eq!(e.constraint(), None);
+}
@@ -1,6 +1,7 @@
 use crate::connection::ConnectOptions;
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::migrate::quote_table_name;
 use crate::migrate::MigrateError;
 use crate::migrate::Migration;
 use crate::migrate::{Migrate, MigrateDatabase};
@@ -76,12 +77,17 @@ impl MigrateDatabase for MySql {
 }
 
 impl Migrate for MySqlConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
             // language=MySQL
-            self.execute(
+            self.execute(&*format!(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+CREATE TABLE IF NOT EXISTS {} (
     version BIGINT PRIMARY KEY,
     description TEXT NOT NULL,
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -90,19 +96,26 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     execution_time BIGINT NOT NULL
 );
                 "#,
-            )
+                table_name
+            ))
             .await?;
 
             Ok(())
         })
     }
 
-    fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+    fn version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
             // language=SQL
-            let row = query_as(
-                "SELECT version, NOT success FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
-            )
+            let row = query_as(&*format!(
+                "SELECT version, NOT success FROM {} ORDER BY version DESC LIMIT 1",
+                table_name
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -118,8 +131,7 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             // create an application lock over the database
             // this function will not return until the lock is acquired
 
-            // https://www.postgresql.org/docs/current/explicit-locking.html#ADVISORY-LOCKS
-            // https://www.postgresql.org/docs/current/functions-admin.html#FUNCTIONS-ADVISORY-LOCKS-TABLE
+            // https://dev.mysql.com/doc/refman/8.0/en/locking-functions.html
 
             // language=MySQL
             let _ = query("SELECT GET_LOCK(?, -1)")
@@ -146,17 +158,41 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<i64>, MigrateError>> {
+        Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
+            // language=MySQL
+            let versions = query_scalar(&*format!(
+                "SELECT version FROM {} WHERE success ORDER BY version",
+                table_name
+            ))
+            .fetch_all(self)
+            .await?;
+
+            Ok(versions)
+        })
+    }
+
     fn validate<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
             // language=SQL
-            let checksum: Option<Vec<u8>> =
-                query_scalar("SELECT checksum FROM _sqlx_migrations WHERE version = ?")
-                    .bind(migration.version)
-                    .fetch_optional(self)
-                    .await?;
+            let checksum: Option<Vec<u8>> = query_scalar(&*format!(
+                "SELECT checksum FROM {} WHERE version = ?",
+                table_name
+            ))
+            .bind(migration.version)
+            .fetch_optional(self)
+            .await?;
 
             if let Some(checksum) = checksum {
                 return if checksum == &*migration.checksum {
@@ -172,9 +208,12 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
             let start = Instant::now();
 
             let res = self.execute(&*migration.sql).await;
@@ -182,12 +221,13 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=MySQL
-            let _ = query(
+            let _ = query(&*format!(
                 r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
     VALUES ( ?, ?, ?, ?, ? )
                 "#,
-            )
+                table_name
+            ))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(res.is_ok())
@@ -201,6 +241,47 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             Ok(elapsed)
         })
     }
+
+    fn revert<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let table_name = quote_table_name(table_name, '`');
+
+            let down_sql = migration
+                .down_sql
+                .as_deref()
+                .ok_or(MigrateError::Irreversible(migration.version))?;
+
+            // without transactional DDL, the migration is marked as partially applied ("dirty")
+            // until the down script has completed
+
+            // language=MySQL
+            let _ = query(&*format!(
+                "UPDATE {} SET success = FALSE WHERE version = ?",
+                table_name
+            ))
+            .bind(migration.version)
+            .execute(&mut *self)
+            .await?;
+
+            let start = Instant::now();
+
+            let _ = self.execute(down_sql).await?;
+
+            let elapsed = start.elapsed();
+
+            // language=MySQL
+            let _ = query(&*format!("DELETE FROM {} WHERE version = ?", table_name))
+                .bind(migration.version)
+                .execute(self)
+                .await?;
+
+            Ok(elapsed)
+        })
+    }
 }
 
 async fn current_database(conn: &mut MySqlConnection) -> Result<String, MigrateError> {
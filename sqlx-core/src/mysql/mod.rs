@@ -25,6 +25,7 @@ pub use database::MySql;
 pub use done::MySqlDone;
 pub use error::MySqlDatabaseError;
 pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use protocol::Capabilities as MySqlCapabilities;
 pub use row::MySqlRow;
 pub use transaction::MySqlTransactionManager;
 pub use type_info::MySqlTypeInfo;
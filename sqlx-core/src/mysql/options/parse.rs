@@ -42,6 +42,14 @@ impl FromStr for MySqlConnectOptions {
                     options = options.ssl_ca(&*value);
                 }
 
+                "ssl-cert" => {
+                    options = options.ssl_cert(&*value);
+                }
+
+                "ssl-key" => {
+                    options = options.ssl_key(&*value);
+                }
+
                 "statement-cache-capacity" => {
                     options =
                         options.statement_cache_capacity(value.parse().map_err(Error::config)?);
@@ -58,3 +66,17 @@ impl FromStr for MySqlConnectOptions {
         Ok(options)
     }
 }
+
+#[test]
+fn it_parses_ssl_options_correctly() {
+    use crate::mysql::MySqlSslMode;
+
+    let uri = "mysql://localhost/db?ssl-mode=VERIFY_IDENTITY&ssl-ca=ca.crt\
+               &ssl-cert=client.crt&ssl-key=client.key";
+    let opts = MySqlConnectOptions::from_str(uri).unwrap();
+
+    assert!(matches!(opts.ssl_mode, MySqlSslMode::VerifyIdentity));
+    assert_eq!(Some("ca.crt".into()), opts.ssl_ca);
+    assert_eq!(Some("client.crt".into()), opts.ssl_cert);
+    assert_eq!(Some("client.key".into()), opts.ssl_key);
+}
@@ -7,6 +7,6 @@ mod row;
 pub(crate) mod statement;
 pub(crate) mod text;
 
-pub(crate) use capabilities::Capabilities;
+pub use capabilities::Capabilities;
 pub(crate) use packet::Packet;
 pub(crate) use row::Row;
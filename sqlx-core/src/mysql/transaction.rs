@@ -7,7 +7,7 @@ use crate::mysql::protocol::text::Query;
 use crate::mysql::{MySql, MySqlConnection};
 use crate::transaction::{
     begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    TransactionManager, TransactionOptions,
 };
 
 /// Implementation of [`TransactionManager`] for MySQL.
@@ -16,11 +16,40 @@ pub struct MySqlTransactionManager;
 impl TransactionManager for MySqlTransactionManager {
     type Database = MySql;
 
-    fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            options.validate(depth)?;
+
+            if options.deferrable {
+                return Err(Error::Configuration(
+                    "deferrable transactions are not supported by MySQL".into(),
+                ));
+            }
+
+            if options.is_default() {
+                conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            } else {
+                // the isolation level applies only to the next transaction started
+                if let Some(level) = options.isolation_level {
+                    conn.execute(&*format!(
+                        "SET TRANSACTION ISOLATION LEVEL {}",
+                        level.as_sql()
+                    ))
+                    .await?;
+                }
+
+                if options.read_only {
+                    conn.execute("START TRANSACTION READ ONLY").await?;
+                } else {
+                    conn.execute("START TRANSACTION").await?;
+                }
+            }
+
             conn.transaction_depth = depth + 1;
 
             Ok(())
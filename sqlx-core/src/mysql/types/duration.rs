@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use bytes::Buf;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::mysql::protocol::text::ColumnType;
+use crate::mysql::type_info::MySqlTypeInfo;
+use crate::mysql::{MySql, MySqlValueFormat, MySqlValueRef};
+use crate::types::Type;
+
+// A `std::time::Duration` maps to a MySQL `TIME` used as an elapsed time, which covers
+// `0` to `838:59:59.999999`. Negative `TIME` values cannot be decoded and sub-microsecond
+// precision is truncated on encode.
+
+impl Type<MySql> for Duration {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::binary(ColumnType::Time)
+    }
+}
+
+impl Encode<'_, MySql> for Duration {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        let len = Encode::<MySql>::size_hint(self) - 1;
+        buf.push(len as u8);
+
+        // a Duration is not negative
+        buf.push(0);
+
+        let secs = self.as_secs();
+
+        // days : int<4>
+        buf.extend(
+            &u32::try_from(secs / 86_400)
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        );
+
+        buf.push(((secs % 86_400) / 3600) as u8);
+        buf.push(((secs % 3600) / 60) as u8);
+        buf.push((secs % 60) as u8);
+
+        if len > 8 {
+            buf.extend(&self.subsec_micros().to_le_bytes());
+        }
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        if self.subsec_micros() == 0 {
+            // if micro_seconds is 0, length is 8 and micro_seconds is not sent
+            9
+        } else {
+            // otherwise length is 12
+            13
+        }
+    }
+}
+
+impl<'r> Decode<'r, MySql> for Duration {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            MySqlValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                // data length, expecting 0, 8 or 12 (fractional seconds)
+                let len = buf.get_u8();
+
+                // MySQL specifies that if all of hours, minutes, seconds, microseconds
+                // are 0 then the length is 0 and no further data is send
+                // https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
+                if len == 0 {
+                    return Ok(Duration::default());
+                }
+
+                if buf.get_u8() != 0 {
+                    return Err("negative MySQL `TIME` cannot be decoded as `Duration`".into());
+                }
+
+                let days = buf.get_u32_le();
+                let hours = buf.get_u8();
+                let minutes = buf.get_u8();
+                let seconds = buf.get_u8();
+
+                let micros = if len > 8 { buf.get_u32_le() } else { 0 };
+
+                let secs = u64::from(days) * 86_400
+                    + u64::from(hours) * 3600
+                    + u64::from(minutes) * 60
+                    + u64::from(seconds);
+
+                Ok(Duration::from_secs(secs) + Duration::from_micros(micros.into()))
+            }
+
+            MySqlValueFormat::Text => parse_time(value.as_str()?),
+        }
+    }
+}
+
+// parses a `TIME` in text format, `HHH:MM:SS[.ffffff]`
+fn parse_time(s: &str) -> Result<Duration, BoxDynError> {
+    if s.starts_with('-') {
+        return Err("negative MySQL `TIME` cannot be decoded as `Duration`".into());
+    }
+
+    let (whole, fraction) = match s.find('.') {
+        Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    };
+
+    let mut parts = whole.splitn(3, ':');
+    let mut next = || -> Result<u64, BoxDynError> {
+        Ok(parts
+            .next()
+            .ok_or_else(|| format!("invalid MySQL `TIME`: {:?}", s))?
+            .parse()?)
+    };
+
+    let secs = next()? * 3600 + next()? * 60 + next()?;
+
+    let micros = match fraction {
+        // right-pad to 6 digits so `.5` is read as 500000 microseconds
+        Some(fraction) if fraction.len() <= 6 => {
+            fraction.parse::<u64>()? * 10_u64.pow(6 - fraction.len() as u32)
+        }
+
+        Some(_) => return Err(format!("invalid MySQL `TIME`: {:?}", s).into()),
+
+        None => 0,
+    };
+
+    Ok(Duration::from_secs(secs) + Duration::from_micros(micros))
+}
+
+#[test]
+fn test_parse_time() {
+    assert_eq!(parse_time("00:00:00").unwrap(), Duration::default());
+    assert_eq!(
+        parse_time("838:59:59").unwrap(),
+        Duration::from_secs(838 * 3600 + 59 * 60 + 59)
+    );
+    assert_eq!(
+        parse_time("01:02:03.5").unwrap(),
+        Duration::from_secs(3723) + Duration::from_millis(500)
+    );
+    assert_eq!(
+        parse_time("00:00:00.000001").unwrap(),
+        Duration::from_micros(1)
+    );
+
+    assert!(parse_time("-01:00:00").is_err());
+    assert!(parse_time("01:00").is_err());
+}
+
+#[test]
+fn test_encode_duration() {
+    let mut buf = Vec::new();
+    let _ = Encode::<MySql>::encode(Duration::from_secs(86_400 + 3723), &mut buf);
+
+    assert_eq!(buf, [8, 0, 1, 0, 0, 0, 1, 2, 3]);
+
+    let mut buf = Vec::new();
+    let _ = Encode::<MySql>::encode(Duration::from_micros(1), &mut buf);
+
+    assert_eq!(buf, [12, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+}
@@ -17,6 +17,7 @@
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, `String`                      | VARCHAR, CHAR, TEXT                                  |
 //! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `std::time::Duration`                 | TIME                                                 |
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
@@ -55,13 +56,17 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `rust_decimal::Decimal`               | DECIMAL                                              |
 //!
-//! ### [`json`](https://crates.io/crates/json)
+//! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
 //!
 //! | Rust type                             | MySQL type(s)                                        |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `json::JsonValue`             | JSON
+//! | [`Json<T>`]                           | JSON                                                 |
+//! | `serde_json::Value`                   | JSON                                                 |
+//! | `&serde_json::value::RawValue`        | JSON                                                 |
+//!
+//! [`Json<T>`]: crate::types::Json
 //!
 //! # Nullable
 //!
@@ -71,6 +76,7 @@
 
 mod bool;
 mod bytes;
+mod duration;
 mod float;
 mod int;
 mod str;
@@ -108,8 +108,12 @@ impl<'r> Decode<'r, MySql> for Time {
                 // We need to zero-pad
                 // TODO: Ask [time] to add a parse % for less-than-fixed-9 nanos
 
-                let s = if s.len() < 20 {
-                    Cow::Owned(format!("{:0<19}", s))
+                let s = if s.len() < 19 {
+                    if s.contains('.') {
+                        Cow::Owned(format!("{:0<18}", s))
+                    } else {
+                        Cow::Owned(format!("{}.000000000", s))
+                    }
                 } else {
                     Cow::Borrowed(s)
                 };
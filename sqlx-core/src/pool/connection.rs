@@ -6,7 +6,7 @@ use sqlx_rt::spawn;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// A connection managed by a [`Pool`][crate::pool::Pool].
 ///
@@ -19,6 +19,12 @@ pub struct PoolConnection<DB: Database> {
 pub(super) struct Live<DB: Database> {
     pub(super) raw: DB::Connection,
     pub(super) created: Instant,
+    // `max_lifetime` with jitter applied, computed once when the connection is opened
+    pub(super) max_lifetime: Option<Duration>,
+    // `true` until the connection is first returned to (or placed into) the idle queue
+    pub(super) fresh: bool,
+    // number of queries executed through the `PoolConnection`
+    pub(super) num_queries: u64,
 }
 
 pub(super) struct Idle<DB: Database> {
@@ -26,6 +32,37 @@ pub(super) struct Idle<DB: Database> {
     pub(super) since: Instant,
 }
 
+/// Information about a pooled connection, passed to
+/// [`PoolOptions::before_acquire`][crate::pool::PoolOptions::before_acquire].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ConnectionMeta {
+    /// When the connection was opened.
+    pub created_at: Instant,
+
+    /// When the connection was last returned to the pool.
+    pub last_used_at: Instant,
+
+    /// The number of queries executed on the connection since it was opened.
+    ///
+    /// Queries executed through a [`PoolConnection`], the [`Pool`][crate::pool::Pool] itself or
+    /// a [`Transaction`][crate::transaction::Transaction] begun on a `PoolConnection` are
+    /// counted, but not those executed on the connection a `PoolConnection` dereferences to.
+    pub num_queries: u64,
+}
+
+impl ConnectionMeta {
+    /// The time elapsed since the connection was opened.
+    pub fn age(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
+    /// The time elapsed since the connection was last returned to the pool.
+    pub fn idle_for(&self) -> Duration {
+        self.last_used_at.elapsed()
+    }
+}
+
 /// RAII wrapper for connections being handled by functions that may drop them
 pub(super) struct Floating<'p, C> {
     inner: C,
@@ -56,35 +93,53 @@ impl<DB: Database> DerefMut for PoolConnection<DB> {
 }
 
 impl<DB: Database> PoolConnection<DB> {
-    // explicitly release a connection from the pool
-    pub fn release(mut self) -> DB::Connection {
-        self.live.take().expect("PoolConnection double-dropped").raw
+    /// Detach this connection from the [`Pool`][crate::pool::Pool], taking ownership of it.
+    ///
+    /// The pool no longer counts the connection towards
+    /// [`PoolOptions::max_connections`][crate::pool::PoolOptions::max_connections] and may open
+    /// a replacement. The connection is never returned to the pool; it is up to the caller to
+    /// [`close`][Connection::close] it.
+    pub fn detach(mut self) -> DB::Connection {
+        let live = self.live.take().expect("PoolConnection double-dropped");
+
+        live.float(&self.pool).detach()
+    }
+
+    #[deprecated = "renamed to `.detach()`, which also lets the pool open a replacement"]
+    pub fn release(self) -> DB::Connection {
+        self.detach()
+    }
+
+    /// Returns `true` if this connection was opened for this checkout, or `false` if it was
+    /// reused from the idle connections of the pool.
+    ///
+    /// Connections opened in the background (e.g., to maintain
+    /// [`PoolOptions::min_connections`]) are idle before they are handed out, so are not fresh.
+    ///
+    /// [`PoolOptions::min_connections`]: crate::pool::PoolOptions::min_connections
+    pub fn is_fresh(&self) -> bool {
+        self.live.as_ref().expect(DEREF_ERR).fresh
+    }
+
+    // count a query executed through this connection, for `ConnectionMeta::num_queries`
+    pub(crate) fn count_query(&mut self) {
+        self.live.as_mut().expect(DEREF_ERR).num_queries += 1;
     }
 }
 
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
 impl<DB: Database> Drop for PoolConnection<DB> {
     fn drop(&mut self) {
-        if let Some(mut live) = self.live.take() {
+        if let Some(live) = self.live.take() {
             let pool = self.pool.clone();
 
-            if live.raw.should_flush() {
+            if live.raw.should_flush() || pool.should_check_release() {
                 spawn(async move {
-                    // flush the connection (will immediately return if not needed) before
-                    // we fully release to the pool
-                    if let Err(e) = live.raw.flush().await {
-                        log::error!("error occurred while flushing the connection: {}", e);
-
-                        // we now consider the connection to be broken
-                        // close the connection and drop from the pool
-                        let _ = live.float(&pool).into_idle().close().await;
-                    } else {
-                        // after we have flushed successfully, release to the pool
-                        pool.release(live.float(&pool));
-                    }
+                    // flush and check the connection before we fully release to the pool
+                    pool.release_checked(live.float(&pool)).await;
                 });
             } else {
-                // nothing to flush, release immediately outside of a spawn
+                // nothing to flush or check, release immediately outside of a spawn
                 pool.release(live.float(&pool));
             }
         }
@@ -99,7 +154,9 @@ impl<DB: Database> Live<DB> {
         }
     }
 
-    pub fn into_idle(self) -> Idle<DB> {
+    pub fn into_idle(mut self) -> Idle<DB> {
+        self.fresh = false;
+
         Idle {
             live: self,
             since: Instant::now(),
@@ -107,6 +164,16 @@ impl<DB: Database> Live<DB> {
     }
 }
 
+impl<DB: Database> Idle<DB> {
+    pub fn metadata(&self) -> ConnectionMeta {
+        ConnectionMeta {
+            created_at: self.live.created,
+            last_used_at: self.since,
+            num_queries: self.live.num_queries,
+        }
+    }
+}
+
 impl<DB: Database> Deref for Idle<DB> {
     type Target = Live<DB>;
 
@@ -129,11 +196,18 @@ impl<'s, C> Floating<'s, C> {
 }
 
 impl<'s, DB: Database> Floating<'s, Live<DB>> {
-    pub fn new_live(conn: DB::Connection, guard: DecrementSizeGuard<'s>) -> Self {
+    pub fn new_live(
+        conn: DB::Connection,
+        max_lifetime: Option<Duration>,
+        guard: DecrementSizeGuard<'s>,
+    ) -> Self {
         Self {
             inner: Live {
                 raw: conn,
                 created: Instant::now(),
+                max_lifetime,
+                fresh: true,
+                num_queries: 0,
             },
             guard,
         }
@@ -154,6 +228,11 @@ impl<'s, DB: Database> Floating<'s, Live<DB>> {
         }
     }
 
+    pub fn detach(self) -> DB::Connection {
+        // the size guard is dropped with `self`
+        self.inner.raw
+    }
+
     pub fn into_idle(self) -> Floating<'s, Idle<DB>> {
         Floating {
             inner: self.inner.into_idle(),
@@ -170,10 +249,6 @@ impl<'s, DB: Database> Floating<'s, Idle<DB>> {
         }
     }
 
-    pub async fn ping(&mut self) -> Result<(), Error> {
-        self.live.raw.ping().await
-    }
-
     pub fn into_live(self) -> Floating<'s, Live<DB>> {
         Floating {
             inner: self.inner.live,
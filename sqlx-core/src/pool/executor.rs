@@ -26,6 +26,8 @@ where
 
         Box::pin(try_stream! {
             let mut conn = pool.acquire().await?;
+            conn.count_query();
+
             let mut s = conn.fetch_many(query);
 
             while let Some(v) = s.try_next().await? {
@@ -45,7 +47,12 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            let mut conn = pool.acquire().await?;
+            conn.count_query();
+
+            conn.fetch_optional(query).await
+        })
     }
 
     #[doc(hidden)]
@@ -84,6 +91,7 @@ macro_rules! impl_executor_for_pool_connection {
                 'c: 'e,
                 E: crate::executor::Execute<'q, $DB>,
             {
+                self.count_query();
                 (**self).fetch_many(query)
             }
 
@@ -96,6 +104,7 @@ macro_rules! impl_executor_for_pool_connection {
                 'c: 'e,
                 E: crate::executor::Execute<'q, $DB>,
             {
+                self.count_query();
                 (**self).fetch_optional(query)
             }
 
@@ -4,23 +4,32 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::{deadline_as_timeout, PoolOptions};
-use crossbeam_queue::{ArrayQueue, SegQueue};
+use crossbeam_queue::SegQueue;
 use futures_core::task::{Poll, Waker};
 use futures_util::future;
 use sqlx_rt::{sleep, spawn, timeout};
 use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::mem;
 use std::ptr;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub(crate) struct SharedPool<DB: Database> {
     pub(super) connect_options: <DB::Connection as Connection>::Options,
-    pub(super) idle_conns: ArrayQueue<Idle<DB>>,
-    waiters: SegQueue<Waker>,
+    pub(super) idle_conns: SegQueue<Idle<DB>>,
+    waiters: Waiters,
     pub(super) size: AtomicU32,
+    max_connections: AtomicU32,
+    connect_count: AtomicU64,
+    closed_max_lifetime: AtomicU64,
+    closed_idle_timeout: AtomicU64,
+    closed_broken: AtomicU64,
+    is_started: AtomicBool,
     is_closed: AtomicBool,
+    is_replenishing: AtomicBool,
     pub(super) options: PoolOptions<DB>,
 }
 
@@ -34,35 +43,113 @@ impl<DB: Database> SharedPool<DB> {
         self.idle_conns.len()
     }
 
+    pub(super) fn num_waiting(&self) -> usize {
+        self.waiters.len()
+    }
+
+    pub(super) fn connect_count(&self) -> u64 {
+        self.connect_count.load(Ordering::Acquire)
+    }
+
+    /// The number of connections closed as they exceeded `max_lifetime`.
+    pub(super) fn closed_max_lifetime(&self) -> u64 {
+        self.closed_max_lifetime.load(Ordering::Acquire)
+    }
+
+    /// The number of idle connections closed as they exceeded `idle_timeout`.
+    pub(super) fn closed_idle_timeout(&self) -> u64 {
+        self.closed_idle_timeout.load(Ordering::Acquire)
+    }
+
+    /// The number of connections discarded as they failed to flush, a health check, or a reset.
+    pub(super) fn closed_broken(&self) -> u64 {
+        self.closed_broken.load(Ordering::Acquire)
+    }
+
+    pub(super) fn max_connections(&self) -> u32 {
+        self.max_connections.load(Ordering::Acquire)
+    }
+
+    pub(super) fn set_max_connections(&self, max: u32) {
+        // never drop below `min_connections` (or zero, as then nothing could ever be acquired)
+        let max = max.max(self.options.min_connections).max(1);
+        let prev = self.max_connections.swap(max, Ordering::AcqRel);
+
+        // wake tasks that can now open a new connection
+        for _ in prev..max {
+            if !self.waiters.wake_one() {
+                break;
+            }
+        }
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
 
     pub(super) async fn close(&self) {
         self.is_closed.store(true, Ordering::Release);
-        while let Ok(waker) = self.waiters.pop() {
-            waker.wake();
-        }
+        while self.waiters.wake_one() {}
 
         // ensure we wait until the pool is actually closed
         while self.size() > 0 {
-            let _ = self
-                .idle_conns
-                .pop()
-                .map(|idle| Floating::from_idle(idle, self));
+            if let Ok(idle) = self.idle_conns.pop() {
+                // disconnect cleanly; the size is decremented as the connection is closed
+                let _ = Floating::from_idle(idle, self).close().await;
+            }
 
             // yield to avoid starving the executor
             sqlx_rt::yield_now().await;
         }
     }
 
+    /// Take an idle connection without waiting or opening a new connection.
+    ///
+    /// Connections past `max_lifetime` or `idle_timeout` are closed and skipped.
     #[inline]
     pub(super) fn try_acquire(&self) -> Option<Floating<'_, Live<DB>>> {
+        Some(self.try_pop_idle()?.into_live())
+    }
+
+    /// As `try_acquire`, but idle connections are checked (`test_before_acquire`,
+    /// `before_acquire`) and rejected connections are skipped.
+    pub(super) async fn try_acquire_checked(
+        &self,
+    ) -> Result<Option<Floating<'_, Live<DB>>>, Error> {
+        while let Some(conn) = self.try_pop_idle() {
+            if let Some(live) = check_conn(conn, self).await? {
+                return Ok(Some(live));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn try_pop_idle(&self) -> Option<Floating<'_, Idle<DB>>> {
         // don't cut in line
         if self.options.fair && !self.waiters.is_empty() {
             return None;
         }
-        Some(self.pop_idle()?.into_live())
+
+        while let Some(conn) = self.pop_idle() {
+            if is_beyond_lifetime(&conn) {
+                self.closed_max_lifetime.fetch_add(1, Ordering::AcqRel);
+            } else if is_beyond_idle(&conn, &self.options) {
+                self.closed_idle_timeout.fetch_add(1, Ordering::AcqRel);
+            } else {
+                return Some(conn);
+            }
+
+            // closing is left to a task so the caller doesn't wait on it
+            // (the size is decremented right away)
+            let conn = conn.into_live().detach();
+
+            spawn(async move {
+                let _ = conn.close().await;
+            });
+        }
+
+        None
     }
 
     fn pop_idle(&self) -> Option<Floating<'_, Idle<DB>>> {
@@ -73,21 +160,88 @@ impl<DB: Database> SharedPool<DB> {
         Some(Floating::from_idle(self.idle_conns.pop().ok()?, self))
     }
 
-    pub(super) fn release(&self, mut floating: Floating<'_, Live<DB>>) {
-        if let Some(test) = &self.options.after_release {
-            if !test(&mut floating.raw) {
-                // drop the connection and do not return to the pool
+    /// Returns `true` if a connection must be checked before it is returned to the pool
+    /// (`test_before_release`, `recycling_method`, `after_release`).
+    pub(super) fn should_check_release(&self) -> bool {
+        self.options.test_before_release
+            || self.options.reset_before_release
+            || self.options.after_release.is_some()
+    }
+
+    /// Flush and check a connection before returning it to the pool.
+    ///
+    /// Connections that fail the checks are closed instead.
+    pub(super) async fn release_checked(&self, mut floating: Floating<'_, Live<DB>>) {
+        // flush the connection (will immediately return if not needed) before
+        // we fully release to the pool
+        if let Err(e) = floating.raw.flush().await {
+            log::error!("error occurred while flushing the connection: {}", e);
+            self.closed_broken.fetch_add(1, Ordering::AcqRel);
+
+            // we now consider the connection to be broken
+            // close the connection and drop from the pool
+            let _ = floating.into_idle().close().await;
+            return;
+        }
+
+        if self.options.test_before_release {
+            if let Err(e) = check_health(&mut floating.raw, &self.options).await {
+                log::info!("health check on released connection returned error: {}", e);
+                self.closed_broken.fetch_add(1, Ordering::AcqRel);
+
+                let _ = floating.into_idle().close().await;
+                return;
+            }
+        }
+
+        if self.options.reset_before_release {
+            if let Err(e) = floating.raw.reset().await {
+                log::info!("reset of released connection returned error: {}", e);
+                self.closed_broken.fetch_add(1, Ordering::AcqRel);
+
+                let _ = floating.into_idle().close().await;
                 return;
             }
         }
 
-        self.idle_conns
-            .push(floating.into_idle().into_leakable())
-            .expect("BUG: connection queue overflow in release()");
+        if let Some(test) = &self.options.after_release {
+            match test(&mut floating.raw).await {
+                Ok(true) => {}
+
+                Ok(false) => {
+                    // connection was rejected by user-defined hook
+                    let _ = floating.into_idle().close().await;
+                    return;
+                }
+
+                Err(e) => {
+                    // there is no caller to return the error to
+                    log::warn!("after_release hook returned error: {}", e);
 
-        if let Ok(waker) = self.waiters.pop() {
-            waker.wake();
+                    let _ = floating.into_idle().close().await;
+                    return;
+                }
+            }
         }
+
+        self.release(floating);
+    }
+
+    pub(super) fn release(&self, floating: Floating<'_, Live<DB>>) {
+        if self.size() > self.max_connections() {
+            // the pool was shrunk while this connection was checked out; close it so the pool
+            // size drifts down towards the new maximum (the size is decremented right away)
+            let conn = floating.detach();
+
+            spawn(async move {
+                let _ = conn.close().await;
+            });
+
+            return;
+        }
+
+        self.idle_conns.push(floating.into_idle().into_leakable());
+        self.waiters.wake_one();
     }
 
     /// Try to atomically increment the pool size for a new connection.
@@ -100,7 +254,7 @@ impl<DB: Database> SharedPool<DB> {
 
         let mut size = self.size();
 
-        while size < self.options.max_connections {
+        while size < self.max_connections() {
             let new_size = self.size.compare_and_swap(size, size + 1, Ordering::AcqRel);
 
             if new_size == size {
@@ -116,8 +270,10 @@ impl<DB: Database> SharedPool<DB> {
     /// Wait for a connection, if either `size` drops below `max_connections` so we can
     /// open a new connection, or if an idle connection is returned to the pool.
     ///
+    /// If `fair` is `false`, we are woken before the tasks waiting in a fair `acquire`.
+    ///
     /// Returns an error if `deadline` elapses before we are woken.
-    async fn wait_for_conn(&self, deadline: Instant) -> Result<(), Error> {
+    async fn wait_for_conn(&self, deadline: Instant, fair: bool) -> Result<(), Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
@@ -130,7 +286,7 @@ impl<DB: Database> SharedPool<DB> {
             future::poll_fn(|ctx| -> Poll<()> {
                 if !waker_pushed {
                     // only push the waker once
-                    self.waiters.push(ctx.waker().to_owned());
+                    self.waiters.push(ctx.waker().to_owned(), fair);
                     waker_pushed = true;
                     Poll::Pending
                 } else {
@@ -148,25 +304,52 @@ impl<DB: Database> SharedPool<DB> {
     ) -> Arc<Self> {
         let pool = Self {
             connect_options,
-            idle_conns: ArrayQueue::new(options.max_connections as usize),
-            waiters: SegQueue::new(),
+            idle_conns: SegQueue::new(),
+            waiters: Waiters::default(),
             size: AtomicU32::new(0),
+            max_connections: AtomicU32::new(options.max_connections),
+            connect_count: AtomicU64::new(0),
+            closed_max_lifetime: AtomicU64::new(0),
+            closed_idle_timeout: AtomicU64::new(0),
+            closed_broken: AtomicU64::new(0),
+            is_started: AtomicBool::new(false),
             is_closed: AtomicBool::new(false),
+            is_replenishing: AtomicBool::new(false),
             options,
         };
 
-        let pool = Arc::new(pool);
+        Arc::new(pool)
+    }
+
+    /// Spawn the background tasks that maintain the pool. Only the first call has any effect.
+    ///
+    /// This is deferred until first use for lazy pools so that creating a pool
+    /// performs no I/O (and does not require a running runtime).
+    ///
+    /// Returns `true` if this call started the pool.
+    pub(super) fn start(self: &Arc<Self>) -> bool {
+        if self.is_started.swap(true, Ordering::AcqRel) {
+            return false;
+        }
 
-        spawn_reaper(&pool);
+        spawn_reaper(self);
 
-        pool
+        true
     }
 
+    /// Acquire a connection, waiting until `deadline` at the latest.
+    ///
+    /// If `fair` is `false`, an idle connection is taken even if other tasks are waiting.
     #[allow(clippy::needless_lifetimes)]
-    pub(super) async fn acquire<'s>(&'s self) -> Result<Floating<'s, Live<DB>>, Error> {
-        let start = Instant::now();
-        let deadline = start + self.options.connect_timeout;
-        let mut waited = !self.options.fair;
+    pub(super) async fn acquire<'s>(
+        &'s self,
+        deadline: Instant,
+        fair: bool,
+    ) -> Result<Floating<'s, Live<DB>>, Error> {
+        let mut waited = !fair;
+
+        // time spent in the queue of waiters, reported to `on_acquire_timing`
+        let mut wait_time = Duration::from_secs(0);
 
         // Unless the pool has been closed ...
         while !self.is_closed() {
@@ -175,7 +358,10 @@ impl<DB: Database> SharedPool<DB> {
                 // Attempt to immediately acquire a connection. This will return Some
                 // if there is an idle connection in our channel.
                 if let Some(conn) = self.pop_idle() {
-                    if let Some(live) = check_conn(conn, &self.options).await {
+                    let checked = within_deadline::<DB, _, _>(deadline, check_conn(conn, self));
+
+                    if let Some(live) = checked.await? {
+                        self.report_acquire_timing(wait_time);
                         return Ok(live);
                     }
                 }
@@ -183,17 +369,17 @@ impl<DB: Database> SharedPool<DB> {
 
             if let Some(guard) = self.try_increment_size() {
                 // pool has slots available; open a new connection
-                match self.connection(deadline, guard).await {
-                    Ok(Some(conn)) => return Ok(conn),
-                    // [size] is internally decremented on _retry_ and _error_
-                    Ok(None) => continue,
-                    Err(e) => return Err(e),
-                }
+                // [size] is internally decremented on _error_
+                let live = within_deadline::<DB, _, _>(deadline, self.connection(guard)).await?;
+                self.report_acquire_timing(wait_time);
+                return Ok(live);
             }
 
             // Wait for a connection to become available (or we are allowed to open a new one)
             // Returns an error if `deadline` passes
-            self.wait_for_conn(deadline).await?;
+            let enqueued = Instant::now();
+            self.wait_for_conn(deadline, fair).await?;
+            wait_time += enqueued.elapsed();
 
             waited = true;
         }
@@ -201,53 +387,166 @@ impl<DB: Database> SharedPool<DB> {
         Err(Error::PoolClosed)
     }
 
+    fn report_acquire_timing(&self, wait_time: Duration) {
+        if let Some(callback) = &self.options.on_acquire_timing {
+            callback(wait_time);
+        }
+    }
+
+    fn needs_replenish(&self) -> bool {
+        self.size() < self.options.min_connections
+            || (self.options.min_idle > 0 && self.num_idle() < self.options.min_idle as usize)
+    }
+
+    /// Open new connections in the background if the pool has fewer than `min_connections`
+    /// or fewer than `min_idle` of them are idle. Does nothing if this is already in progress.
+    pub(super) fn replenish_idle_in_background(self: &Arc<Self>) {
+        if !self.needs_replenish() || self.is_replenishing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let pool = Arc::clone(self);
+
+        spawn(async move {
+            if let Err(error) = pool.replenish_connections().await {
+                log::warn!("error while replenishing idle connections: {}", error);
+            }
+
+            pool.is_replenishing.store(false, Ordering::Release);
+        });
+    }
+
+    /// Open new connections until the pool holds at least `min_connections` and at least
+    /// `min_idle` of them are idle.
+    ///
+    /// Stops early (without an error) if the pool is full.
+    pub(super) async fn replenish_connections(&self) -> Result<(), Error> {
+        while !self.is_closed() && self.needs_replenish() {
+            let guard = match self.try_increment_size() {
+                Some(guard) => guard,
+                None => break,
+            };
+
+            let conn = self.connection(guard).await?;
+            self.idle_conns.push(conn.into_idle().into_leakable());
+            self.waiters.wake_one();
+        }
+
+        Ok(())
+    }
+
+    /// Open a new connection, bounded by `connect_timeout`.
+    ///
+    /// Attempts that fail in a way where it is okay to retry are retried with exponential
+    /// backoff until `connect_timeout` elapses.
     pub(super) async fn connection<'s>(
         &'s self,
-        deadline: Instant,
         guard: DecrementSizeGuard<'s>,
-    ) -> Result<Option<Floating<'s, Live<DB>>>, Error> {
-        if self.is_closed() {
-            return Err(Error::PoolClosed);
-        }
+    ) -> Result<Floating<'s, Live<DB>>, Error> {
+        let deadline = Instant::now() + self.options.connect_timeout;
+        let mut backoff = self.options.min_connect_backoff;
 
-        let timeout = super::deadline_as_timeout::<DB>(deadline)?;
+        loop {
+            if self.is_closed() {
+                return Err(Error::PoolClosed);
+            }
 
-        // result here is `Result<Result<C, Error>, TimeoutError>`
-        match sqlx_rt::timeout(timeout, self.connect_options.connect()).await {
-            // successfully established connection
-            Ok(Ok(mut raw)) => {
-                if let Some(callback) = &self.options.after_connect {
-                    callback(&mut raw).await?;
-                }
+            let timeout = deadline_as_timeout::<DB>(deadline)?;
+
+            // result here is `Result<Result<C, Error>, TimeoutError>`
+            match sqlx_rt::timeout(timeout, self.connect_options.connect()).await {
+                // successfully established connection
+                Ok(Ok(mut raw)) => {
+                    if let Some(callback) = &self.options.after_connect {
+                        if let Err(e) = callback(&mut raw).await {
+                            // don't let a half-configured connection into the pool
+                            let _ = raw.close().await;
+                            return Err(e);
+                        }
+                    }
 
-                Ok(Some(Floating::new_live(raw, guard)))
-            }
+                    self.connect_count.fetch_add(1, Ordering::AcqRel);
+
+                    let max_lifetime = jittered_max_lifetime(&self.options);
 
-            // an IO error while connecting is assumed to be the system starting up
-            Ok(Err(Error::Io(_))) => Ok(None),
+                    return Ok(Floating::new_live(raw, max_lifetime, guard));
+                }
+
+                Ok(Err(e)) if is_transient_connect_error(&e) => {
+                    // give up with the last error if the next attempt would be past the deadline
+                    if Instant::now() + backoff >= deadline {
+                        return Err(e);
+                    }
 
-            // TODO: Handle other database "boot period"s
+                    log::debug!("retrying connection in {:?}: {}", backoff, e);
 
-            // [postgres] the database system is starting up
-            // TODO: Make this check actually check if this is postgres
-            Ok(Err(Error::Database(error))) if error.code().as_deref() == Some("57P03") => Ok(None),
+                    sleep(backoff).await;
+                    backoff = cmp::min(backoff * 2, self.options.max_connect_backoff);
+                }
 
-            // Any other error while connection should immediately
-            // terminate and bubble the error up
-            Ok(Err(e)) => Err(e),
+                // Any other error while connection should immediately
+                // terminate and bubble the error up
+                Ok(Err(e)) => return Err(e),
 
-            // timed out
-            Err(_) => Err(Error::PoolTimedOut),
+                // timed out
+                Err(_) => return Err(Error::PoolTimedOut),
+            }
         }
     }
 }
 
 // NOTE: Function names here are bizzare. Helpful help would be appreciated.
 
-fn is_beyond_lifetime<DB: Database>(live: &Live<DB>, options: &PoolOptions<DB>) -> bool {
+/// Applies `max_lifetime_jitter` to `max_lifetime` so connections opened together
+/// are not all retired together.
+fn jittered_max_lifetime<DB: Database>(options: &PoolOptions<DB>) -> Option<Duration> {
+    let max_lifetime = options.max_lifetime?;
+
+    if options.max_lifetime_jitter <= 0.0 {
+        return Some(max_lifetime);
+    }
+
+    // `RandomState` is randomly seeded on every construction; this saves us a dependency
+    let random = RandomState::new().build_hasher().finish();
+
+    // scale to `[-1.0, 1.0]`
+    let factor = (random as f64 / u64::MAX as f64) * 2.0 - 1.0;
+
+    Some(max_lifetime.mul_f64(1.0 + f64::from(options.max_lifetime_jitter) * factor))
+}
+
+/// Returns `true` if a connection attempt that failed with this error may succeed if retried.
+fn is_transient_connect_error(error: &Error) -> bool {
+    match error {
+        // an IO error while connecting is assumed to be the system starting up or failing over
+        Error::Io(_) => true,
+
+        // TODO: Handle other database "boot period"s
+
+        // [postgres] the database system is starting up
+        // TODO: Make this check actually check if this is postgres
+        Error::Database(error) => error.code().as_deref() == Some("57P03"),
+
+        _ => false,
+    }
+}
+
+/// Run `future` until `deadline`, returning [`Error::PoolTimedOut`] if it passes first.
+///
+/// A connection that was being checked or opened when the deadline passed is discarded.
+async fn within_deadline<DB, T, F>(deadline: Instant, future: F) -> Result<T, Error>
+where
+    DB: Database,
+    F: std::future::Future<Output = Result<T, Error>>,
+{
+    timeout(deadline_as_timeout::<DB>(deadline)?, future)
+        .await
+        .map_err(|_| Error::PoolTimedOut)?
+}
+
+fn is_beyond_lifetime<DB: Database>(live: &Live<DB>) -> bool {
     // check if connection was within max lifetime (or not set)
-    options
-        .max_lifetime
+    live.max_lifetime
         .map_or(false, |max| live.created.elapsed() > max)
 }
 
@@ -258,37 +557,63 @@ fn is_beyond_idle<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<DB>) -> b
         .map_or(false, |timeout| idle.since.elapsed() > timeout)
 }
 
+/// Ping the connection, or run the query set with `health_check_query`.
+async fn check_health<DB: Database>(
+    conn: &mut DB::Connection,
+    options: &PoolOptions<DB>,
+) -> Result<(), Error> {
+    match &options.health_check {
+        Some(check) => check(conn).await,
+        None => conn.ping().await,
+    }
+}
+
 async fn check_conn<'s: 'p, 'p, DB: Database>(
     mut conn: Floating<'s, Idle<DB>>,
-    options: &'p PoolOptions<DB>,
-) -> Option<Floating<'s, Live<DB>>> {
+    pool: &'p SharedPool<DB>,
+) -> Result<Option<Floating<'s, Live<DB>>>, Error> {
+    let options = &pool.options;
+
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
-    if is_beyond_lifetime(&conn, options) {
+    if is_beyond_lifetime(&conn) {
+        pool.closed_max_lifetime.fetch_add(1, Ordering::AcqRel);
+
         // we're closing the connection either way
         // close the connection but don't really care about the result
         let _ = conn.close().await;
-        return None;
-    } else if options.test_before_acquire {
+        return Ok(None);
+    }
+
+    // connections that were used recently are assumed to still be live
+    if options.test_before_acquire && conn.since.elapsed() >= options.max_idle_before_test {
         // Check that the connection is still live
-        if let Err(e) = conn.ping().await {
+        if let Err(e) = check_health(&mut conn.live.raw, options).await {
             // an error here means the other end has hung up or we lost connectivity
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
-            log::info!("ping on idle connection returned error: {}", e);
+            log::info!("health check on idle connection returned error: {}", e);
+            pool.closed_broken.fetch_add(1, Ordering::AcqRel);
+
             // connection is broken so don't try to close nicely
-            return None;
+            return Ok(None);
         }
-    } else if let Some(test) = &options.before_acquire {
-        match test(&mut conn.live.raw).await {
+    }
+
+    if let Some(test) = &options.before_acquire {
+        let meta = conn.metadata();
+
+        match test(&mut conn.live.raw, meta).await {
             Ok(false) => {
                 // connection was rejected by user-defined hook
-                return None;
+                let _ = conn.close().await;
+                return Ok(None);
             }
 
             Err(error) => {
-                log::info!("in `before_acquire`: {}", error);
-                return None;
+                // the hook failed; we can't trust this connection and the caller should know why
+                let _ = conn.close().await;
+                return Err(error);
             }
 
             Ok(true) => {}
@@ -296,7 +621,7 @@ async fn check_conn<'s: 'p, 'p, DB: Database>(
     }
 
     // No need to re-connect; connection is alive or we don't care
-    Some(conn.into_live())
+    Ok(Some(conn.into_live()))
 }
 
 /// if `max_lifetime` or `idle_timeout` is set, spawn a task that reaps senescent connections
@@ -313,29 +638,47 @@ fn spawn_reaper<DB: Database>(pool: &Arc<SharedPool<DB>>) {
 
     spawn(async move {
         while !pool.is_closed.load(Ordering::Acquire) {
-            // reap at most the current size minus the minimum idle
-            let max_reaped = pool.size().saturating_sub(pool.options.min_connections);
+            // wait a period first to let the pool finish opening its initial connections
+            sleep(period).await;
+
+            // reap idle connections down to at most the minimum,
+            // keeping at least `min_idle` of them
+            let mut max_idle_reaped = cmp::min(
+                pool.size().saturating_sub(pool.options.min_connections),
+                (pool.num_idle() as u32).saturating_sub(pool.options.min_idle),
+            );
 
             // collect connections to reap
-            let (reap, keep) = (0..max_reaped)
+            let (reap, keep) = (0..pool.num_idle())
                 // only connections waiting in the queue
                 .filter_map(|_| pool.pop_idle())
                 .partition::<Vec<_>, _>(|conn| {
-                    is_beyond_idle(conn, &pool.options) || is_beyond_lifetime(conn, &pool.options)
+                    if is_beyond_lifetime(conn) {
+                        // expired connections are always retired;
+                        // `min_connections` and `min_idle` are restored below
+                        pool.closed_max_lifetime.fetch_add(1, Ordering::AcqRel);
+                        true
+                    } else if max_idle_reaped > 0 && is_beyond_idle(conn, &pool.options) {
+                        max_idle_reaped -= 1;
+                        pool.closed_idle_timeout.fetch_add(1, Ordering::AcqRel);
+                        true
+                    } else {
+                        false
+                    }
                 });
 
             for conn in keep {
                 // return these connections to the pool first
-                pool.idle_conns
-                    .push(conn.into_leakable())
-                    .expect("BUG: connection queue overflow in spawn_reaper");
+                pool.idle_conns.push(conn.into_leakable());
             }
 
             for conn in reap {
                 let _ = conn.close().await;
             }
 
-            sleep(period).await;
+            if let Err(error) = pool.replenish_connections().await {
+                log::warn!("error while replenishing connections: {}", error);
+            }
         }
     });
 }
@@ -346,7 +689,7 @@ fn spawn_reaper<DB: Database>(pool: &Arc<SharedPool<DB>>) {
 /// (where the pool thinks it has more connections than it does).
 pub(in crate::pool) struct DecrementSizeGuard<'a> {
     size: &'a AtomicU32,
-    waiters: &'a SegQueue<Waker>,
+    waiters: &'a Waiters,
     dropped: bool,
 }
 
@@ -374,8 +717,43 @@ impl Drop for DecrementSizeGuard<'_> {
         assert!(!self.dropped, "double-dropped!");
         self.dropped = true;
         self.size.fetch_sub(1, Ordering::SeqCst);
-        if let Ok(waker) = self.waiters.pop() {
-            waker.wake();
+        self.waiters.wake_one();
+    }
+}
+
+// the tasks waiting for a connection; those in an unfair acquire are woken first
+#[derive(Default)]
+struct Waiters {
+    unfair: SegQueue<Waker>,
+    fair: SegQueue<Waker>,
+}
+
+impl Waiters {
+    fn push(&self, waker: Waker, fair: bool) {
+        if fair {
+            self.fair.push(waker);
+        } else {
+            self.unfair.push(waker);
         }
     }
+
+    // returns `false` if no task was waiting
+    fn wake_one(&self) -> bool {
+        match self.unfair.pop().or_else(|_| self.fair.pop()) {
+            Ok(waker) => {
+                waker.wake();
+                true
+            }
+
+            Err(_) => false,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.unfair.is_empty() && self.fair.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.unfair.len() + self.fair.len()
+    }
 }
@@ -2,12 +2,27 @@ use crate::database::Database;
 use crate::pool::PoolConnection;
 use std::ops::{Deref, DerefMut};
 
-pub(crate) enum MaybePoolConnection<'c, DB: Database> {
-    #[allow(dead_code)]
+/// The connection a [`Transaction`] was started on: either a borrowed connection or a
+/// connection acquired from a pool.
+///
+/// Returned by [`Transaction::commit_and_return`].
+///
+/// [`Transaction`]: crate::transaction::Transaction
+/// [`Transaction::commit_and_return`]: crate::transaction::Transaction::commit_and_return
+pub enum MaybePoolConnection<'c, DB: Database> {
     Connection(&'c mut DB::Connection),
     PoolConnection(PoolConnection<DB>),
 }
 
+impl<'c, DB: Database> MaybePoolConnection<'c, DB> {
+    // count a query executed through a transaction on a pooled connection
+    pub(crate) fn count_query(&mut self) {
+        if let MaybePoolConnection::PoolConnection(conn) = self {
+            conn.count_query();
+        }
+    }
+}
+
 impl<'c, DB: Database> Deref for MaybePoolConnection<'c, DB> {
     type Target = DB::Connection;
 
@@ -49,7 +49,8 @@ use self::inner::SharedPool;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
+use futures_core::future::BoxFuture;
 use std::fmt;
 use std::future::Future;
 use std::sync::Arc;
@@ -65,9 +66,9 @@ mod connection;
 mod inner;
 mod options;
 
-pub use self::connection::PoolConnection;
-pub(crate) use self::maybe::MaybePoolConnection;
-pub use self::options::PoolOptions;
+pub use self::connection::{ConnectionMeta, PoolConnection};
+pub use self::maybe::MaybePoolConnection;
+pub use self::options::{PoolOptions, RecyclingMethod};
 
 /// An asynchronous pool of SQLx database connections.
 pub struct Pool<DB: Database>(pub(crate) Arc<SharedPool<DB>>);
@@ -103,17 +104,108 @@ impl<DB: Database> Pool<DB> {
 
     /// Retrieves a connection from the pool.
     ///
-    /// Waits for at most the configured connection timeout before returning an error.
+    /// Waits for at most [`PoolOptions::acquire_timeout`] before returning
+    /// [`Error::PoolTimedOut`].
     pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_with_deadline(Instant::now() + self.0.options.acquire_timeout)
+    }
+
+    /// Retrieves a connection from the pool, waiting until `deadline` at the latest.
+    ///
+    /// This behaves like [`acquire`] but, instead of the configured
+    /// [`PoolOptions::acquire_timeout`], waits in the queue for a connection until the given
+    /// instant before returning [`Error::PoolTimedOut`]. This allows an existing deadline (e.g.,
+    /// of the request being handled) to be used for the whole operation.
+    ///
+    /// The deadline also bounds checking an idle connection before it is handed out and opening
+    /// a new connection (which is bounded by [`PoolOptions::connect_timeout`] as well).
+    ///
+    /// [`acquire`]: #method.acquire
+    pub fn acquire_with_deadline(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_inner(deadline, self.0.options.fair)
+    }
+
+    /// Retrieves a connection from the pool, taking an idle connection even if other tasks are
+    /// already waiting for one.
+    ///
+    /// A fair pool (the default) hands out connections in the order they were requested; under
+    /// heavy load, a call that must complete quickly, such as a liveness check, may then wait
+    /// behind a long queue of other tasks. This skips the queue to take the next idle
+    /// connection, if there is one. Otherwise, it waits ahead of the tasks in [`acquire`], and
+    /// is given the next connection that is returned or may be opened. It still respects
+    /// [`PoolOptions::max_connections`] and the checks of [`PoolOptions::test_before_acquire`],
+    /// and waits for at most [`PoolOptions::acquire_timeout`].
+    ///
+    /// Every connection taken this way is one that a waiting task does not get, so using this
+    /// for more than the occasional high-priority call starves the tasks in the queue and
+    /// defeats the fairness of the pool.
+    ///
+    /// [`acquire`]: #method.acquire
+    pub fn acquire_unfair(
+        &self,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_inner(Instant::now() + self.0.options.acquire_timeout, false)
+    }
+
+    fn acquire_inner(
+        &self,
+        deadline: Instant,
+        fair: bool,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.start();
+
         let shared = self.0.clone();
-        async move { shared.acquire().await.map(|conn| conn.attach(&shared)) }
+        async move {
+            let conn = shared.acquire(deadline, fair).await?.attach(&shared);
+
+            // keep `min_idle` connections ready for the next acquire
+            shared.replenish_idle_in_background();
+
+            Ok(conn)
+        }
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
-    /// Returns `None` immediately if there are no idle connections available in the pool.
+    /// Returns `None` if there are no idle connections available in the pool (or, if the pool
+    /// is fair, other tasks are already waiting for one). This never waits for a connection
+    /// to be returned and never opens a new connection.
+    ///
+    /// The idle connection is handed out without being checked by
+    /// [`PoolOptions::test_before_acquire`] or [`PoolOptions::before_acquire`]; use
+    /// [`try_acquire_checked`][Self::try_acquire_checked] for that.
     pub fn try_acquire(&self) -> Option<PoolConnection<DB>> {
-        self.0.try_acquire().map(|conn| conn.attach(&self.0))
+        self.start();
+
+        let conn = self.0.try_acquire().map(|conn| conn.attach(&self.0));
+        self.0.replenish_idle_in_background();
+
+        conn
+    }
+
+    /// Attempts to retrieve a connection from the pool if there is one available, checking it
+    /// as [`acquire`][Self::acquire] does.
+    ///
+    /// As [`try_acquire`][Self::try_acquire], this never waits for a connection to be returned
+    /// and never opens a new connection. An idle connection is checked with
+    /// [`PoolOptions::test_before_acquire`] and [`PoolOptions::before_acquire`] before it is
+    /// returned, so this may perform I/O on the connection itself; rejected connections are
+    /// skipped.
+    pub async fn try_acquire_checked(&self) -> Result<Option<PoolConnection<DB>>, Error> {
+        self.start();
+
+        let conn = self
+            .0
+            .try_acquire_checked()
+            .await?
+            .map(|conn| conn.attach(&self.0));
+
+        self.0.replenish_idle_in_background();
+
+        Ok(conn)
     }
 
     /// Retrieves a new connection and immediately begins a new transaction.
@@ -121,10 +213,83 @@ impl<DB: Database> Pool<DB> {
         Ok(Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await?)
     }
 
+    /// Retrieves a new connection and immediately begins a new transaction with the given
+    /// options.
+    ///
+    /// See [`Connection::begin_with`](crate::connection::Connection::begin_with).
+    pub async fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'static, DB>, Error> {
+        Transaction::begin_with(
+            MaybePoolConnection::PoolConnection(self.acquire().await?),
+            options,
+        )
+        .await
+    }
+
+    /// Retrieves a new connection and executes the function inside a transaction on it.
+    ///
+    /// The transaction is committed if the function returns `Ok` and rolled back if it returns
+    /// an error; the connection is then returned to the pool.
+    ///
+    /// See [`Connection::transaction`](crate::connection::Connection::transaction).
+    pub async fn transaction<F, R, E>(&self, callback: F) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut Transaction<'_, DB>) -> BoxFuture<'c, Result<R, E>> + Send + Sync,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        self.acquire().await?.transaction(callback).await
+    }
+
+    /// Retrieves a new connection and executes the function inside a transaction on it,
+    /// retrying it on serialization failures.
+    ///
+    /// See [`Connection::transaction_retrying`](crate::connection::Connection::transaction_retrying).
+    pub async fn transaction_retrying<F, R>(
+        &self,
+        max_attempts: u32,
+        backoff: Duration,
+        callback: F,
+    ) -> Result<R, Error>
+    where
+        for<'c> F:
+            FnMut(&'c mut Transaction<'_, DB>) -> BoxFuture<'c, Result<R, Error>> + Send + Sync,
+        R: Send,
+    {
+        self.acquire()
+            .await?
+            .transaction_retrying(max_attempts, backoff, callback)
+            .await
+    }
+
+    /// Retrieves a new connection and executes the function inside a transaction begun with
+    /// the given options, retrying it on serialization failures.
+    ///
+    /// See [`Connection::transaction_retrying_with`](crate::connection::Connection::transaction_retrying_with).
+    pub async fn transaction_retrying_with<F, R>(
+        &self,
+        options: TransactionOptions,
+        max_attempts: u32,
+        backoff: Duration,
+        callback: F,
+    ) -> Result<R, Error>
+    where
+        for<'c> F:
+            FnMut(&'c mut Transaction<'_, DB>) -> BoxFuture<'c, Result<R, Error>> + Send + Sync,
+        R: Send,
+    {
+        self.acquire()
+            .await?
+            .transaction_retrying_with(options, max_attempts, backoff, callback)
+            .await
+    }
+
     /// Attempts to retrieve a new connection and immediately begins a new transaction if there
     /// is one available.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
-        match self.try_acquire() {
+        match self.try_acquire_checked().await? {
             Some(conn) => Transaction::begin(MaybePoolConnection::PoolConnection(conn))
                 .await
                 .map(Some),
@@ -133,10 +298,86 @@ impl<DB: Database> Pool<DB> {
         }
     }
 
+    /// Returns the number of connections currently owned by the pool (idle or checked out).
+    pub fn size(&self) -> u32 {
+        self.0.size()
+    }
+
+    /// Returns the number of connections sitting idle in the pool, ready to be acquired.
+    pub fn idle(&self) -> usize {
+        self.0.num_idle()
+    }
+
+    /// Returns the number of connections sitting idle in the pool; an alias of [`idle`].
+    ///
+    /// [`idle`]: #method.idle
+    pub fn num_idle(&self) -> usize {
+        self.idle()
+    }
+
+    /// Returns the number of tasks waiting in [`acquire`] for a connection.
+    ///
+    /// This is an approximation as tasks that gave up waiting may still be counted until
+    /// the next connection is released.
+    ///
+    /// [`acquire`]: #method.acquire
+    pub fn num_waiting(&self) -> usize {
+        self.0.num_waiting()
+    }
+
+    /// Returns the total number of connections this pool has ever opened.
+    ///
+    /// This only ever increases; comparing it over time shows how often connections are being
+    /// replaced (e.g., due to [`PoolOptions::max_lifetime`] or to restore
+    /// [`PoolOptions::min_connections`]).
+    pub fn connect_count(&self) -> u64 {
+        self.0.connect_count()
+    }
+
+    /// Returns a snapshot of the current pool statistics, e.g., for periodic logging.
+    pub fn status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.size(),
+            num_idle: self.idle(),
+            num_waiting: self.num_waiting(),
+            closed_max_lifetime: self.0.closed_max_lifetime(),
+            closed_idle_timeout: self.0.closed_idle_timeout(),
+            closed_broken: self.0.closed_broken(),
+        }
+    }
+
+    /// Returns the maximum number of connections this pool will maintain.
+    pub fn max_connections(&self) -> u32 {
+        self.0.max_connections()
+    }
+
+    /// Changes the maximum number of connections this pool will maintain.
+    ///
+    /// Growing the pool takes effect immediately and tasks waiting in [`acquire`] are woken
+    /// so they may open new connections.
+    ///
+    /// Shrinking the pool does not close any connections that are checked out. Instead,
+    /// connections are closed as they are returned to the pool until the pool size drifts
+    /// down to the new maximum.
+    ///
+    /// The new maximum is clamped to be at least [`PoolOptions::min_connections`] (and
+    /// at least `1`).
+    ///
+    /// [`acquire`]: #method.acquire
+    /// [`PoolOptions::min_connections`]: crate::pool::PoolOptions::min_connections
+    pub fn set_max_connections(&self, max: u32) {
+        self.0.set_max_connections(max);
+    }
+
     /// Ends the use of a connection pool. Prevents any new connections
     /// and will close all active connections when they are returned to the pool.
     ///
-    /// Does not resolve until all connections are closed.
+    /// Any task waiting in [`acquire`] (and any future call) will return [`Error::PoolClosed`].
+    ///
+    /// Does not resolve until all connections are closed. Calling this more than once
+    /// (e.g., from multiple shutdown paths) is safe.
+    ///
+    /// [`acquire`]: #method.acquire
     pub async fn close(&self) {
         self.0.close().await;
     }
@@ -145,6 +386,45 @@ impl<DB: Database> Pool<DB> {
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
     }
+
+    /// Starts a lazy pool on first use; opening `min_connections` in the background.
+    fn start(&self) {
+        if self.0.start() {
+            let shared = Arc::clone(&self.0);
+
+            sqlx_rt::spawn(async move {
+                if let Err(error) = shared.replenish_connections().await {
+                    log::warn!("error while opening `min_connections`: {}", error);
+                }
+            });
+        }
+    }
+}
+
+/// A snapshot of the statistics of a [`Pool`], returned from [`Pool::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolStatus {
+    /// The number of connections currently owned by the pool (idle or checked out).
+    pub size: u32,
+
+    /// The number of connections sitting idle in the pool.
+    pub num_idle: usize,
+
+    /// The (approximate) number of tasks waiting for a connection.
+    pub num_waiting: usize,
+
+    /// The total number of connections closed because they were older than
+    /// [`PoolOptions::max_lifetime`].
+    pub closed_max_lifetime: u64,
+
+    /// The total number of idle connections closed by [`PoolOptions::idle_timeout`].
+    pub closed_idle_timeout: u64,
+
+    /// The total number of connections discarded because they were broken: they failed to
+    /// flush, failed a health check ([`PoolOptions::test_before_acquire`],
+    /// [`PoolOptions::test_before_release`]), or failed to reset.
+    pub closed_broken: u64,
 }
 
 /// Returns a new [Pool] tied to the same shared connection pool.
@@ -154,10 +434,20 @@ impl<DB: Database> Clone for Pool<DB> {
     }
 }
 
+#[cfg(feature = "any")]
+impl Pool<crate::any::Any> {
+    /// Returns the database driver used by the connections of this pool, as determined by the
+    /// scheme of the connection URL.
+    pub fn any_kind(&self) -> crate::any::AnyKind {
+        self.0.connect_options.kind()
+    }
+}
+
 impl<DB: Database> fmt::Debug for Pool<DB> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Pool")
             .field("size", &self.0.size())
+            .field("max_connections", &self.0.max_connections())
             .field("num_idle", &self.0.num_idle())
             .field("is_closed", &self.0.is_closed())
             .field("options", &self.0.options)
@@ -1,39 +1,109 @@
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::pool::inner::SharedPool;
-use crate::pool::Pool;
+use crate::pool::{ConnectionMeta, Pool};
 use futures_core::future::BoxFuture;
-use sqlx_rt::spawn;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+
+/// How a connection is checked (and cleaned) when it is reused from the pool.
+///
+/// Set with [`PoolOptions::recycling_method`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecyclingMethod {
+    /// Idle connections are handed out without any check.
+    Fast,
+
+    /// Idle connections are checked with [`Connection::ping`] (or the query set with
+    /// [`PoolOptions::health_check_query`]) before they are handed out. This is the default.
+    Verified,
+
+    /// As `Verified`, and connections are additionally [reset](Connection::reset) when they are
+    /// returned to the pool, discarding session settings, temporary tables, and prepared
+    /// statements.
+    Clean,
+}
+
+// the shortest delay between attempts to open a connection; doubling a zero delay would
+// retry in a busy loop
+const MIN_CONNECT_BACKOFF: Duration = Duration::from_millis(1);
 
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) max_idle_before_test: Duration,
     pub(crate) after_connect: Option<
-        Box<
+        Arc<
             dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>> + 'static + Send + Sync,
         >,
     >,
     pub(crate) before_acquire: Option<
-        Box<
+        Arc<
+            dyn Fn(&mut DB::Connection, ConnectionMeta) -> BoxFuture<'_, Result<bool, Error>>
+                + 'static
+                + Send
+                + Sync,
+        >,
+    >,
+    pub(crate) test_before_release: bool,
+    pub(crate) reset_before_release: bool,
+    pub(crate) health_check: Option<
+        Arc<
+            dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>> + 'static + Send + Sync,
+        >,
+    >,
+    pub(crate) after_release: Option<
+        Arc<
             dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<bool, Error>>
                 + 'static
                 + Send
                 + Sync,
         >,
     >,
-    pub(crate) after_release:
-        Option<Box<dyn Fn(&mut DB::Connection) -> bool + 'static + Send + Sync>>,
+    pub(crate) on_acquire_timing: Option<Arc<dyn Fn(Duration) + 'static + Send + Sync>>,
     pub(crate) max_connections: u32,
     pub(crate) connect_timeout: Duration,
+    pub(crate) min_connect_backoff: Duration,
+    pub(crate) max_connect_backoff: Duration,
+    pub(crate) acquire_timeout: Duration,
     pub(crate) min_connections: u32,
+    pub(crate) min_idle: u32,
     pub(crate) max_lifetime: Option<Duration>,
+    pub(crate) max_lifetime_jitter: f32,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
 }
 
+// NOTE: a manual impl is required as `#[derive(Clone)]` would add a `DB: Clone` bound
+impl<DB: Database> Clone for PoolOptions<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            test_before_acquire: self.test_before_acquire,
+            max_idle_before_test: self.max_idle_before_test,
+            after_connect: self.after_connect.clone(),
+            before_acquire: self.before_acquire.clone(),
+            test_before_release: self.test_before_release,
+            reset_before_release: self.reset_before_release,
+            health_check: self.health_check.clone(),
+            after_release: self.after_release.clone(),
+            on_acquire_timing: self.on_acquire_timing.clone(),
+            max_connections: self.max_connections,
+            connect_timeout: self.connect_timeout,
+            min_connect_backoff: self.min_connect_backoff,
+            max_connect_backoff: self.max_connect_backoff,
+            acquire_timeout: self.acquire_timeout,
+            min_connections: self.min_connections,
+            min_idle: self.min_idle,
+            max_lifetime: self.max_lifetime,
+            max_lifetime_jitter: self.max_lifetime_jitter,
+            idle_timeout: self.idle_timeout,
+            fair: self.fair,
+        }
+    }
+}
+
 impl<DB: Database> Default for PoolOptions<DB> {
     fn default() -> Self {
         Self::new()
@@ -45,18 +115,30 @@ impl<DB: Database> PoolOptions<DB> {
         Self {
             after_connect: None,
             test_before_acquire: true,
+            max_idle_before_test: Duration::from_secs(0),
             before_acquire: None,
+            test_before_release: false,
+            reset_before_release: false,
+            health_check: None,
             after_release: None,
+            on_acquire_timing: None,
             max_connections: 10,
             min_connections: 0,
+            min_idle: 0,
             connect_timeout: Duration::from_secs(30),
+            min_connect_backoff: Duration::from_millis(10),
+            max_connect_backoff: Duration::from_secs(1),
+            acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
+            max_lifetime_jitter: 0.0,
             fair: true,
         }
     }
 
     /// Set the maximum number of connections that this pool should maintain.
+    ///
+    /// This can be changed after the pool is created with [`Pool::set_max_connections`].
     pub fn max_connections(mut self, max: u32) -> Self {
         self.max_connections = max;
         self
@@ -64,12 +146,54 @@ impl<DB: Database> PoolOptions<DB> {
 
     /// Set the amount of time to attempt connecting to the database.
     ///
-    /// If this timeout elapses, [`Pool::acquire`] will return an error.
+    /// This only bounds establishing a new connection (including any TLS handshake and
+    /// authentication). If this timeout elapses, [`Pool::acquire`] will return an error.
+    ///
+    /// Attempts that fail because the database could not be reached (or is still starting up)
+    /// are retried within this timeout; see [`connect_retry_backoff`].
+    ///
+    /// See [`acquire_timeout`] to bound the time spent waiting for a connection to become
+    /// available.
+    ///
+    /// [`acquire_timeout`]: #method.acquire_timeout
+    /// [`connect_retry_backoff`]: #method.connect_retry_backoff
     pub fn connect_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = timeout;
         self
     }
 
+    /// Set the delays between attempts to open a new connection after a transient failure.
+    ///
+    /// An attempt that fails with an I/O error (e.g. the connection was refused during a
+    /// failover) or because the database is still starting up is retried after `min`, with
+    /// the delay doubling on each further failure up to `max`. Retries stop once
+    /// [`connect_timeout`] has elapsed, at which point the last error is returned. Any other
+    /// error, such as failed authentication, is returned immediately.
+    ///
+    /// Defaults to `10ms` and `1s`. A `min` below `1ms` is raised to `1ms`, as the delay
+    /// could otherwise never grow.
+    ///
+    /// [`connect_timeout`]: #method.connect_timeout
+    pub fn connect_retry_backoff(mut self, min: Duration, max: Duration) -> Self {
+        let min = min.max(MIN_CONNECT_BACKOFF);
+
+        self.min_connect_backoff = min;
+        self.max_connect_backoff = max.max(min);
+        self
+    }
+
+    /// Set the maximum amount of time [`Pool::acquire`] will wait for a connection to be
+    /// returned to the pool (or for a slot to open up) before returning [`Error::PoolTimedOut`].
+    ///
+    /// This includes checking an idle connection before it is handed out and opening a new
+    /// connection, which is also bounded by [`connect_timeout`].
+    ///
+    /// [`connect_timeout`]: #method.connect_timeout
+    pub fn acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
     /// Set the minimum number of connections to maintain at all times.
     ///
     /// When the pool is built, this many connections will be automatically spun up.
@@ -84,6 +208,24 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Set the minimum number of idle connections to keep ready in the pool.
+    ///
+    /// Whenever acquiring a connection leaves fewer than this many connections idle, new
+    /// connections are opened in the background (up to [`max_connections`]) so that a sudden
+    /// burst of acquires does not have to wait for connections to be established. This is
+    /// independent of [`min_connections`]; idle connections beyond `min_connections` are still
+    /// not reaped by [`idle_timeout`] while doing so would drop below this amount.
+    ///
+    /// Defaults to `0`.
+    ///
+    /// [`max_connections`]: #method.max_connections
+    /// [`min_connections`]: #method.min_connections
+    /// [`idle_timeout`]: #method.idle_timeout
+    pub fn min_idle(mut self, min: u32) -> Self {
+        self.min_idle = min;
+        self
+    }
+
     /// Set the maximum lifetime of individual connections.
     ///
     /// Any connection with a lifetime greater than this will be closed.
@@ -103,6 +245,22 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Randomly vary the [`max_lifetime`] of each connection by up to this fraction.
+    ///
+    /// Connections that are opened together (e.g., the [`min_connections`] opened when the
+    /// pool is created) would otherwise all reach their maximum lifetime at the same moment
+    /// and be replaced at once. With a jitter of `0.1`, each connection is given a lifetime
+    /// somewhere between 90% and 110% of [`max_lifetime`], chosen when it is opened.
+    ///
+    /// The value is clamped to `0.0 ..= 1.0`. Defaults to `0.0` (no jitter).
+    ///
+    /// [`max_lifetime`]: #method.max_lifetime
+    /// [`min_connections`]: #method.min_connections
+    pub fn max_lifetime_jitter(mut self, jitter: f32) -> Self {
+        self.max_lifetime_jitter = jitter.max(0.0).min(1.0);
+        self
+    }
+
     /// Set a maximum idle duration for individual connections.
     ///
     /// Any connection with an idle duration longer than this will be closed.
@@ -114,16 +272,112 @@ impl<DB: Database> PoolOptions<DB> {
     }
 
     /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
-    /// before returning the connection.
+    /// (or the query set with [`health_check_query`]) before returning the connection.
     ///
     /// Defaults to `true`.
     ///
     /// [`Connection::ping`]: crate::connection::Connection::ping
+    /// [`health_check_query`]: #method.health_check_query
     pub fn test_before_acquire(mut self, test: bool) -> Self {
         self.test_before_acquire = test;
         self
     }
 
+    /// Only run the health check of [`test_before_acquire`] on connections that have been idle
+    /// for at least this long; connections returned to the pool more recently are handed out
+    /// without being tested, saving a round-trip on busy pools.
+    ///
+    /// Has no effect if `test_before_acquire` is disabled.
+    ///
+    /// Defaults to zero, which tests every connection.
+    ///
+    /// [`test_before_acquire`]: #method.test_before_acquire
+    pub fn max_idle_before_test(mut self, threshold: Duration) -> Self {
+        self.max_idle_before_test = threshold;
+        self
+    }
+
+    /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
+    /// (or the query set with [`health_check_query`]) when it is returned to the pool. A
+    /// connection that fails the check is discarded.
+    ///
+    /// This moves the cost of the check off of [`Pool::acquire`], at the expense of checking
+    /// connections that may sit idle for a while before they are used again.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`Connection::ping`]: crate::connection::Connection::ping
+    /// [`health_check_query`]: #method.health_check_query
+    pub fn test_before_release(mut self, test: bool) -> Self {
+        self.test_before_release = test;
+        self
+    }
+
+    /// Sets how a connection is checked (and cleaned) when it is reused, covering
+    /// [`test_before_acquire`] and resetting the session when the connection is released.
+    ///
+    /// [`RecyclingMethod::Clean`] calls [`Connection::reset`] on every released connection
+    /// (before [`after_release`], if set); a connection that fails to reset is closed. As the
+    /// reset also discards the settings of [`after_connect`], prefer `after_release` to
+    /// re-apply them, or use `Verified` if the session state does not need to be wiped.
+    ///
+    /// Defaults to [`RecyclingMethod::Verified`].
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .recycling_method(RecyclingMethod::Clean)
+    ///     .connect("postgres://").await?;
+    /// ```
+    ///
+    /// [`Connection::reset`]: crate::connection::Connection::reset
+    /// [`test_before_acquire`]: #method.test_before_acquire
+    /// [`after_release`]: #method.after_release
+    /// [`after_connect`]: #method.after_connect
+    pub fn recycling_method(mut self, method: RecyclingMethod) -> Self {
+        self.test_before_acquire = method != RecyclingMethod::Fast;
+        self.reset_before_release = method == RecyclingMethod::Clean;
+        self
+    }
+
+    /// Check the health of a connection by executing this query instead of calling
+    /// [`Connection::ping`], wherever [`test_before_acquire`] or [`test_before_release`] are
+    /// enabled.
+    ///
+    /// For some databases, [`Connection::ping`] only makes a round-trip at the protocol level.
+    /// A query such as `SELECT 1` additionally catches a server that accepts connections but
+    /// fails to run statements. If the query returns an error or no rows, the connection is
+    /// discarded, so a query can also reject e.g. a server that was demoted to a read-only
+    /// replica.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .health_check_query("SELECT 1 WHERE NOT pg_is_in_recovery()")
+    ///     .connect("postgres://").await?;
+    /// ```
+    ///
+    /// [`Connection::ping`]: crate::connection::Connection::ping
+    /// [`test_before_acquire`]: #method.test_before_acquire
+    /// [`test_before_release`]: #method.test_before_release
+    pub fn health_check_query(mut self, query: &str) -> Self
+    where
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let query: Arc<str> = query.into();
+
+        self.health_check = Some(Arc::new(move |conn| {
+            let query = Arc::clone(&query);
+
+            Box::pin(async move {
+                match conn.fetch_optional(&*query).await? {
+                    Some(_) => Ok(()),
+                    None => Err(err_protocol!("health check query returned no rows")),
+                }
+            })
+        }));
+
+        self
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -142,31 +396,132 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Perform an action after connecting to the database.
+    ///
+    /// The callback is invoked once for every newly opened connection, before it is placed
+    /// into the pool or returned from [`Pool::acquire`]. This is a good place to run session
+    /// setup such as `SET TIME ZONE 'UTC'`.
+    ///
+    /// If the callback returns an error, the new connection is closed and the error is returned
+    /// from [`Pool::acquire`] (or from [`connect`] when establishing the initial connections).
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .after_connect(|conn| Box::pin(async move {
+    ///         conn.execute("SET application_name = 'my_app'").await?;
+    ///
+    ///         Ok(())
+    ///     }))
+    ///     .connect("postgres://").await?;
+    /// ```
+    ///
+    /// [`connect`]: #method.connect
     pub fn after_connect<F>(mut self, callback: F) -> Self
     where
         for<'c> F:
             Fn(&'c mut DB::Connection) -> BoxFuture<'c, Result<(), Error>> + 'static + Send + Sync,
     {
-        self.after_connect = Some(Box::new(callback));
+        self.after_connect = Some(Arc::new(callback));
+        self
+    }
+
+    /// Perform an action before a connection is returned from [`Pool::acquire`].
+    ///
+    /// The callback is invoked for every idle connection that is about to be handed out,
+    /// after the ping from [`test_before_acquire`] (if enabled). It is not invoked for
+    /// connections that were just opened.
+    ///
+    /// Returning `Ok(true)` accepts the connection. Returning `Ok(false)` closes the connection
+    /// and the pool moves on to the next idle connection (or opens a new one). Returning an
+    /// error closes the connection and the error is returned from [`Pool::acquire`].
+    ///
+    /// The callback is also given the [`ConnectionMeta`] of the connection (when it was opened
+    /// and last used, and how many queries it has executed), to implement recycling policies
+    /// beyond [`max_lifetime`] and [`idle_timeout`].
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .before_acquire(|conn, meta| Box::pin(async move {
+    ///         // recycle connections after 10 minutes, regardless of `max_lifetime`
+    ///         if meta.age() > Duration::from_secs(10 * 60) {
+    ///             return Ok(false);
+    ///         }
+    ///
+    ///         let (tz,): (String,) = sqlx::query_as("SHOW TIME ZONE").fetch_one(conn).await?;
+    ///
+    ///         Ok(tz == "UTC")
+    ///     }))
+    ///     .connect("postgres://").await?;
+    /// ```
+    ///
+    /// [`test_before_acquire`]: #method.test_before_acquire
+    /// [`max_lifetime`]: #method.max_lifetime
+    /// [`idle_timeout`]: #method.idle_timeout
+    pub fn before_acquire<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut DB::Connection, ConnectionMeta) -> BoxFuture<'c, Result<bool, Error>>
+            + 'static
+            + Send
+            + Sync,
+    {
+        self.before_acquire = Some(Arc::new(callback));
         self
     }
 
-    pub fn before_acquire<F, Fut>(mut self, callback: F) -> Self
+    /// Perform an action when a connection is returned to the pool.
+    ///
+    /// The callback is invoked for every connection that is dropped or otherwise returned,
+    /// after the ping from [`test_before_release`] (if enabled). This is a good place to
+    /// clean up session state such as temporary tables.
+    ///
+    /// Returning `Ok(true)` returns the connection to the pool. Returning `Ok(false)` or an
+    /// error closes the connection instead; as this happens in the background, an error is
+    /// only logged.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .after_release(|conn| Box::pin(async move {
+    ///         conn.execute("DISCARD TEMP").await?;
+    ///
+    ///         Ok(true)
+    ///     }))
+    ///     .connect("postgres://").await?;
+    /// ```
+    ///
+    /// [`test_before_release`]: #method.test_before_release
+    pub fn after_release<F>(mut self, callback: F) -> Self
     where
         for<'c> F: Fn(&'c mut DB::Connection) -> BoxFuture<'c, Result<bool, Error>>
             + 'static
             + Send
             + Sync,
     {
-        self.before_acquire = Some(Box::new(callback));
+        self.after_release = Some(Arc::new(callback));
         self
     }
 
-    pub fn after_release<F, Fut>(mut self, callback: F) -> Self
+    /// Perform an action with the time each call to [`Pool::acquire`] spent waiting for a
+    /// connection to become available.
+    ///
+    /// The callback is invoked every time a connection is handed out. The duration covers only
+    /// the time spent queued behind other waiters, not checking idle connections or opening a
+    /// new connection; it is zero if a connection was available right away. This is useful for
+    /// recording how contended the pool is, e.g. as a histogram.
+    ///
+    /// The callback is invoked from within `acquire()` and should return quickly.
+    ///
+    /// ```rust,ignore
+    /// let pool = PgPoolOptions::new()
+    ///     .on_acquire_timing(|wait_time| {
+    ///         metrics::histogram!("pool.acquire.wait", wait_time);
+    ///     })
+    ///     .connect("postgres://").await?;
+    /// ```
+    pub fn on_acquire_timing<F>(mut self, callback: F) -> Self
     where
-        F: Fn(&mut DB::Connection) -> bool + 'static + Send + Sync,
+        F: Fn(Duration) + 'static + Send + Sync,
     {
-        self.after_release = Some(Box::new(callback));
+        self.on_acquire_timing = Some(Arc::new(callback));
         self
     }
 
@@ -182,46 +537,40 @@ impl<DB: Database> PoolOptions<DB> {
     ) -> Result<Pool<DB>, Error> {
         let shared = SharedPool::new_arc(self, options);
 
+        shared.start();
         init_min_connections(&shared).await?;
+        shared.replenish_idle_in_background();
 
         Ok(Pool(shared))
     }
 
     /// Creates a new pool from this configuration and will establish a connections as the pool
     /// starts to be used.
+    ///
+    /// No connections are opened (and no background tasks are spawned) until the pool is first
+    /// used. At that point, [`min_connections`] are opened in the background.
+    ///
+    /// [`min_connections`]: #method.min_connections
     pub fn connect_lazy(self, uri: &str) -> Result<Pool<DB>, Error> {
         Ok(self.connect_lazy_with(uri.parse()?))
     }
 
     /// Creates a new pool from this configuration and will establish a connections as the pool
     /// starts to be used.
+    ///
+    /// See [`connect_lazy`](#method.connect_lazy).
     pub fn connect_lazy_with(self, options: <DB::Connection as Connection>::Options) -> Pool<DB> {
-        let shared = SharedPool::new_arc(self, options);
-
-        let _ = spawn({
-            let shared = Arc::clone(&shared);
-            async move {
-                let _ = init_min_connections(&shared).await;
-            }
-        });
-
-        Pool(shared)
+        Pool(SharedPool::new_arc(self, options))
     }
 }
 
 async fn init_min_connections<DB: Database>(pool: &SharedPool<DB>) -> Result<(), Error> {
     for _ in 0..pool.options.min_connections.max(1) {
-        let deadline = Instant::now() + pool.options.connect_timeout;
-
         // this guard will prevent us from exceeding `max_size`
         if let Some(guard) = pool.try_increment_size() {
-            // [connect] will raise an error when past deadline
-            // [connect] returns None if its okay to retry
-            if let Some(conn) = pool.connection(deadline, guard).await? {
-                pool.idle_conns
-                    .push(conn.into_idle().into_leakable())
-                    .expect("BUG: connection queue overflow in init_min_connections");
-            }
+            // [connect] will raise an error when past `connect_timeout`
+            let conn = pool.connection(guard).await?;
+            pool.idle_conns.push(conn.into_idle().into_leakable());
         }
     }
 
@@ -233,10 +582,40 @@ impl<DB: Database> Debug for PoolOptions<DB> {
         f.debug_struct("PoolOptions")
             .field("max_connections", &self.max_connections)
             .field("min_connections", &self.min_connections)
+            .field("min_idle", &self.min_idle)
             .field("connect_timeout", &self.connect_timeout)
+            .field("min_connect_backoff", &self.min_connect_backoff)
+            .field("max_connect_backoff", &self.max_connect_backoff)
+            .field("acquire_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
+            .field("max_lifetime_jitter", &self.max_lifetime_jitter)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("max_idle_before_test", &self.max_idle_before_test)
+            .field("test_before_release", &self.test_before_release)
+            .field("reset_before_release", &self.reset_before_release)
             .finish()
     }
 }
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::PoolOptions;
+    use crate::postgres::Postgres;
+    use std::time::Duration;
+
+    #[test]
+    fn it_raises_a_zero_connect_retry_backoff() {
+        let options = PoolOptions::<Postgres>::new()
+            .connect_retry_backoff(Duration::from_secs(0), Duration::from_secs(0));
+
+        assert_eq!(options.min_connect_backoff, Duration::from_millis(1));
+        assert_eq!(options.max_connect_backoff, Duration::from_millis(1));
+
+        let options = PoolOptions::<Postgres>::new()
+            .connect_retry_backoff(Duration::from_millis(5), Duration::from_secs(1));
+
+        assert_eq!(options.min_connect_backoff, Duration::from_millis(5));
+        assert_eq!(options.max_connect_backoff, Duration::from_secs(1));
+    }
+}
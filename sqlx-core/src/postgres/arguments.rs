@@ -1,3 +1,4 @@
+use std::fmt::{self, Write};
 use std::ops::{Deref, DerefMut};
 
 use crate::arguments::Arguments;
@@ -59,6 +60,14 @@ impl<'q> Arguments<'q> for PgArguments {
     {
         self.add(value)
     }
+
+    fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "${}", self.types.len() + 1)
+    }
 }
 
 impl PgArgumentBuffer {
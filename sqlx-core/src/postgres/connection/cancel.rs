@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::error::Error;
+use crate::postgres::connection::{stream::PgStream, tls};
+use crate::postgres::message::CancelRequest;
+use crate::postgres::{PgConnectOptions, PgConnection};
+
+// https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.9
+
+impl PgConnection {
+    /// Returns a handle that can be used to cancel the query currently executing on this
+    /// connection, from another task.
+    ///
+    /// A dropped query stream does *not* cancel the query by itself; the server will run it to
+    /// completion and the results are discarded the next time the connection is used.
+    pub fn cancel_handle(&self) -> PgCancelHandle {
+        PgCancelHandle {
+            options: Arc::clone(&self.options),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+}
+
+/// A handle to request the cancellation of the query currently executing on a
+/// [`PgConnection`].
+///
+/// Created by [`PgConnection::cancel_handle`].
+#[derive(Debug, Clone)]
+pub struct PgCancelHandle {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgCancelHandle {
+    /// Request that the server cancels the query currently executing on the connection
+    /// this handle was created from.
+    ///
+    /// The request is sent on a new connection to the server. Cancellation is best-effort:
+    /// if it takes effect, the query fails with a `57014` (`query_canceled`) error. If the
+    /// connection is idle, or has since moved on to another query, the request may have no
+    /// effect or cancel that query instead.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        tls::maybe_upgrade(&mut stream, &self.options, self.options.ssl_mode).await?;
+
+        stream
+            .send(CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .await?;
+
+        // the server does not reply and closes the connection once it has processed
+        // the request
+        let _ = stream.read::<Bytes>(1).await;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use hashbrown::HashMap;
 
 use crate::common::StatementCache;
@@ -7,38 +9,65 @@ use crate::postgres::connection::{sasl, stream::PgStream, tls};
 use crate::postgres::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
-use crate::postgres::{PgConnectOptions, PgConnection};
+use crate::postgres::{PgConnectOptions, PgConnection, PgSslMode};
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        match options.ssl_mode {
+            // first try a non-SSL connection; if the server rejects it (e.g., `pg_hba.conf`
+            // only allows `hostssl`), try again with an SSL connection
+            PgSslMode::Allow => match Self::establish_with(options, PgSslMode::Allow).await {
+                Err(Error::Database(_)) => Self::establish_with(options, PgSslMode::Require).await,
+                res => res,
+            },
+
+            ssl_mode => Self::establish_with(options, ssl_mode).await,
+        }
+    }
+
+    async fn establish_with(
+        options: &PgConnectOptions,
+        ssl_mode: PgSslMode,
+    ) -> Result<Self, Error> {
         let mut stream = PgStream::connect(options).await?;
 
         // Upgrade to TLS if we were asked to and the server supports it
-        tls::maybe_upgrade(&mut stream, options).await?;
+        tls::maybe_upgrade(&mut stream, options, ssl_mode).await?;
 
         // To begin a session, a frontend opens a connection to the server
         // and sends a startup message.
 
+        let mut params = vec![
+            // Sets the display format for date and time values,
+            // as well as the rules for interpreting ambiguous date input values.
+            ("DateStyle", "ISO, MDY"),
+            // Sets the client-side encoding (character set).
+            // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
+            ("client_encoding", "UTF8"),
+            // Sets the time zone for displaying and interpreting time stamps.
+            ("TimeZone", "UTC"),
+            // Adjust postgres to return precise values for floats
+            // NOTE: This is default in postgres 12+
+            ("extra_float_digits", "3"),
+        ];
+
+        if let Some(application_name) = &options.application_name {
+            params.push(("application_name", application_name));
+        }
+
+        // Command-line arguments for the backend, e.g. `-c search_path=myschema`
+        if let Some(options) = &options.options {
+            params.push(("options", options));
+        }
+
         stream
             .send(Startup {
                 username: Some(&options.username),
                 database: options.database.as_deref(),
-                params: &[
-                    // Sets the display format for date and time values,
-                    // as well as the rules for interpreting ambiguous date input values.
-                    ("DateStyle", "ISO, MDY"),
-                    // Sets the client-side encoding (character set).
-                    // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
-                    ("client_encoding", "UTF8"),
-                    // Sets the time zone for displaying and interpreting time stamps.
-                    ("TimeZone", "UTC"),
-                    // Adjust postgres to return precise values for floats
-                    // NOTE: This is default in postgres 12+
-                    ("extra_float_digits", "3"),
-                ],
+                params: &params,
             })
             .await?;
 
@@ -129,11 +158,23 @@ impl PgConnection {
             stream,
             process_id,
             secret_key,
+            options: Arc::new(options.clone()),
             transaction_status,
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
             next_statement_id: 1,
-            cache_statement: StatementCache::new(options.statement_cache_capacity),
+            closed_statements: Default::default(),
+            prepared_columns: HashMap::new(),
+            cache_statement: StatementCache::new(if options.persistent {
+                options.statement_cache_capacity
+            } else {
+                0
+            }),
+            cache_describe: StatementCache::new(if options.describe_cache {
+                options.statement_cache_capacity
+            } else {
+                0
+            }),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
             scratch_row_columns: Default::default(),
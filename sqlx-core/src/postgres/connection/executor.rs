@@ -3,10 +3,14 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::logging::QueryLogger;
+use crate::postgres::connection::CachedStatement;
 use crate::postgres::message::{
     self, Bind, Close, CommandComplete, DataRow, Flush, MessageFormat, ParameterDescription, Parse,
     Query, RowDescription,
@@ -15,13 +19,14 @@ use crate::postgres::type_info::PgType;
 use crate::postgres::{PgArguments, PgConnection, PgDone, PgRow, PgValueFormat, Postgres};
 use crate::statement::StatementInfo;
 
-async fn prepare(
+pub(super) async fn prepare(
     conn: &mut PgConnection,
     query: &str,
     arguments: &PgArguments,
 ) -> Result<u32, Error> {
     let id = conn.next_statement_id;
-    conn.next_statement_id = conn.next_statement_id.wrapping_add(1);
+    // `0` is reserved for the unnamed statement
+    conn.next_statement_id = conn.next_statement_id.wrapping_add(1).max(1);
 
     // build a list of type OIDs to send to the database in the PARSE command
     // we have not yet started the query sequence, so we are *safe* to cleanly make
@@ -64,13 +69,17 @@ async fn prepare(
     Ok(id)
 }
 
-async fn recv_desc_params(conn: &mut PgConnection) -> Result<ParameterDescription, Error> {
+pub(super) async fn recv_desc_params(
+    conn: &mut PgConnection,
+) -> Result<ParameterDescription, Error> {
     conn.stream
         .recv_expect(MessageFormat::ParameterDescription)
         .await
 }
 
-async fn recv_desc_rows(conn: &mut PgConnection) -> Result<Option<RowDescription>, Error> {
+pub(super) async fn recv_desc_rows(
+    conn: &mut PgConnection,
+) -> Result<Option<RowDescription>, Error> {
     let rows: Option<RowDescription> = match conn.stream.recv().await? {
         // describes the rows that will be returned when the statement is eventually executed
         message if message.format == MessageFormat::RowDescription => Some(message.decode()?),
@@ -90,6 +99,40 @@ async fn recv_desc_rows(conn: &mut PgConnection) -> Result<Option<RowDescription
 }
 
 impl PgConnection {
+    /// Executes raw SQL with the simple query protocol, streaming the results of each statement
+    /// in it.
+    ///
+    /// No `Parse` or `Bind` messages are sent and nothing is prepared on the server, which makes
+    /// this usable behind connection poolers that do not support the extended protocol, such as
+    /// PgBouncer in transaction pooling mode. The query string may contain several statements
+    /// separated by semicolons but cannot have parameters; rows are returned in the text format.
+    ///
+    /// ```rust,ignore
+    /// let mut results = conn.execute_simple("SET search_path TO app; SELECT id FROM users");
+    ///
+    /// while let Some(result) = results.try_next().await? {
+    ///     // either a `PgDone` or a `PgRow`
+    /// }
+    /// ```
+    pub fn execute_simple<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxStream<'e, Result<Either<PgDone, PgRow>, Error>> {
+        let mut logger = QueryLogger::new(sql, false, &self.options.log_settings);
+
+        logger.span().instrument_stream(Box::pin(try_stream! {
+            let s = self.run(sql, None, None, 0, None).await?;
+            pin_mut!(s);
+
+            while let Some(v) = s.try_next().await? {
+                logger.record(&v);
+                r#yield!(v);
+            }
+
+            Ok(())
+        }))
+    }
+
     // wait for CloseComplete to indicate a statement was closed
     pub(super) async fn wait_for_close_complete(&mut self, mut count: usize) -> Result<(), Error> {
         // we need to wait for the [CloseComplete] to be returned from the server
@@ -118,15 +161,80 @@ impl PgConnection {
         Ok(())
     }
 
+    // closes the statements prepared by [Connection::prepare] that were dropped since
+    pub(super) async fn close_dropped_statements(&mut self) -> Result<(), Error> {
+        let mut count = 0;
+
+        while let Some(statement) = self.closed_statements.pop() {
+            self.prepared_columns.remove(&statement);
+            self.stream.write(Close::Statement(statement));
+            count += 1;
+        }
+
+        if count > 0 {
+            self.stream.write(Flush);
+            self.stream.flush().await?;
+
+            self.wait_for_close_complete(count).await?;
+        }
+
+        Ok(())
+    }
+
+    // prepares the query as a new statement, outside of the statement cache, and describes it
+    pub(super) async fn prepare_and_describe(
+        &mut self,
+        query: &str,
+    ) -> Result<(u32, StatementInfo<Postgres>), Error> {
+        self.wait_until_ready().await?;
+        self.close_dropped_statements().await?;
+
+        let id = prepare(self, query, &Default::default()).await?;
+
+        self.stream.write(message::Describe::Statement(id));
+        self.stream.write(Flush);
+
+        self.stream.flush().await?;
+
+        let params = recv_desc_params(self).await?;
+        let rows = recv_desc_rows(self).await?;
+
+        let params = self.handle_parameter_description(params).await?;
+
+        self.handle_row_description(rows, true).await?;
+
+        let columns = (&*self.scratch_row_columns).clone();
+        let nullable = self.get_nullable_for_columns(&columns).await?;
+
+        let info = StatementInfo {
+            columns,
+            nullable,
+            parameters: Some(Either::Left(params)),
+            parameter_names: None,
+        };
+
+        Ok((id, info))
+    }
+
     async fn prepare(&mut self, query: &str, arguments: &PgArguments) -> Result<u32, Error> {
         if let Some(statement) = self.cache_statement.get_mut(query) {
-            return Ok(*statement);
+            return Ok(statement.id);
         }
 
         let statement = prepare(self, query, arguments).await?;
 
-        if let Some(statement) = self.cache_statement.insert(query, statement) {
-            self.stream.write(Close::Statement(statement));
+        if !self.cache_statement.is_enabled() {
+            // caching is disabled; the statement is closed in [run] once it has been executed
+            return Ok(statement);
+        }
+
+        let cached = CachedStatement {
+            id: statement,
+            columns: None,
+        };
+
+        if let Some(evicted) = self.cache_statement.insert(query, cached) {
+            self.stream.write(Close::Statement(evicted.id));
             self.stream.write(Flush);
 
             self.stream.flush().await?;
@@ -137,34 +245,127 @@ impl PgConnection {
         Ok(statement)
     }
 
+    // makes the columns of the rows of the statement the current ones; the statement is
+    // described unless they are known from the statement cache or [Connection::prepare]
+    pub(super) async fn handle_statement_columns(
+        &mut self,
+        query: &str,
+        statement: u32,
+    ) -> Result<(), Error> {
+        let known = match self.prepared_columns.get(&statement) {
+            Some(columns) => Some(columns.clone()),
+
+            None => self
+                .cache_statement
+                .get_mut(query)
+                .filter(|cached| cached.id == statement)
+                .and_then(|cached| cached.columns.clone()),
+        };
+
+        if let Some((columns, column_names)) = known {
+            self.scratch_row_columns = columns;
+            self.scratch_row_column_names = column_names;
+
+            return Ok(());
+        }
+
+        // describe the statement and, again, ask the server to immediately respond
+        // we need to fully realize the types
+        self.stream.write(message::Describe::Statement(statement));
+        self.stream.write(message::Flush);
+        self.stream.flush().await?;
+
+        let _ = recv_desc_params(self).await?;
+        let rows = recv_desc_rows(self).await?;
+
+        self.handle_row_description(rows, true).await?;
+        self.wait_until_ready().await?;
+
+        // a cached statement keeps its columns, as the statement cannot change them
+        if let Some(cached) = self
+            .cache_statement
+            .get_mut(query)
+            .filter(|cached| cached.id == statement)
+        {
+            cached.columns = Some((
+                Arc::clone(&self.scratch_row_columns),
+                Arc::clone(&self.scratch_row_column_names),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // writes an unnamed statement without parameters or results to be executed as part of
+    // the next [Sync]
+    fn write_command(&mut self, command: &str) {
+        self.stream.write(Parse {
+            statement: 0,
+            query: command,
+            param_types: &[],
+        });
+
+        self.stream.write(Bind {
+            portal: None,
+            statement: 0,
+            formats: &[],
+            num_params: 0,
+            params: &[],
+            result_formats: &[],
+        });
+
+        self.stream.write(message::Execute {
+            portal: None,
+            limit: 0,
+        });
+    }
+
     async fn run(
         &mut self,
         query: &str,
         arguments: Option<PgArguments>,
+        prepared: Option<u32>,
         limit: u8,
+        timeout: Option<Duration>,
     ) -> Result<impl Stream<Item = Result<Either<PgDone, PgRow>, Error>> + '_, Error> {
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
+        self.close_dropped_statements().await?;
+
+        // without persistent statements, a query without arguments is sent as a simple query
+        // unless it was prepared by [Connection::prepare]
+        let arguments = arguments.filter(|arguments| {
+            prepared.is_some() || self.options.persistent || !arguments.types.is_empty()
+        });
+
+        // with a timeout, the query is surrounded by commands that set `statement_timeout` and
+        // restore it afterwards; these are local to the (possibly implicit) transaction
+        let set_timeout = timeout.map(set_statement_timeout);
 
         let format = if let Some(mut arguments) = arguments {
+            // the protocol encodes the number of parameters in 16 bits
+            if arguments.types.len() > u16::MAX as usize {
+                return Err(Error::TooManyArguments {
+                    count: arguments.types.len(),
+                    max: u16::MAX as usize,
+                });
+            }
+
             // prepare the statement if this our first time executing it
             // always return the statement ID here
-            let statement = self.prepare(query, &arguments).await?;
+            let statement = match prepared {
+                Some(statement) => statement,
+                None => self.prepare(query, &arguments).await?,
+            };
 
             // patch holes created during encoding
             arguments.buffer.patch_type_holes(self).await?;
 
-            // describe the statement and, again, ask the server to immediately respond
-            // we need to fully realize the types
-            self.stream.write(message::Describe::Statement(statement));
-            self.stream.write(message::Flush);
-            self.stream.flush().await?;
-
-            let _ = recv_desc_params(self).await?;
-            let rows = recv_desc_rows(self).await?;
+            self.handle_statement_columns(query, statement).await?;
 
-            self.handle_row_description(rows, true).await?;
-            self.wait_until_ready().await?;
+            if let Some(set_timeout) = &set_timeout {
+                self.write_command(set_timeout);
+            }
 
             // bind to attach the arguments to the statement and create a portal
             self.stream.write(Bind {
@@ -183,17 +384,34 @@ impl PgConnection {
                 limit: limit.into(),
             });
 
+            if set_timeout.is_some() {
+                self.write_command(RESTORE_STATEMENT_TIMEOUT);
+            }
+
+            if prepared.is_none() && !self.cache_statement.is_enabled() {
+                // the statement will not be used again, close it once it has been executed
+                self.stream.write(Close::Statement(statement));
+            }
+
             // finally, [Sync] asks postgres to process the messages that we sent and respond with
-            // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
-            // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
+            // a [ReadyForQuery] message when it's completely done. Sending many queries before a
+            // single [Sync] is done by [PgConnection::execute_pipeline].
             self.stream.write(message::Sync);
 
             // prepared statements are binary
             PgValueFormat::Binary
         } else {
-            self.stream.write(Query(query));
+            let query = match &set_timeout {
+                // the newline ends a trailing `--` comment
+                Some(set_timeout) => Cow::Owned(format!(
+                    "{};\n{}\n;{}",
+                    set_timeout, query, RESTORE_STATEMENT_TIMEOUT
+                )),
+
+                None => Cow::Borrowed(query),
+            };
+
+            self.stream.write(Query(&query));
 
             // and unprepared statements are text
             PgValueFormat::Text
@@ -203,14 +421,30 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
         self.stream.flush().await?;
 
+        // the first and last commands are ours when setting a timeout; each done is held back
+        // until the next message shows whether it was the last
+        let mut skip_done = set_timeout.is_some();
+        let mut held_done: Option<PgDone> = None;
+
         Ok(try_stream! {
             loop {
-                let message = self.stream.recv().await?;
+                let message = match self.stream.recv().await {
+                    Ok(message) => message,
+
+                    Err(error) => {
+                        if let Some(done) = held_done.take() {
+                            r#yield!(Either::Left(done));
+                        }
+
+                        return Err(error);
+                    }
+                };
 
                 match message.format {
                     MessageFormat::BindComplete
                     | MessageFormat::ParseComplete
                     | MessageFormat::ParameterDescription
+                    | MessageFormat::CloseComplete
                     | MessageFormat::NoData => {
                         // harmless messages to ignore
                     }
@@ -218,10 +452,18 @@ impl PgConnection {
                     MessageFormat::CommandComplete => {
                         // a SQL command completed normally
                         let cc: CommandComplete = message.decode()?;
-
-                        r#yield!(Either::Left(PgDone {
+                        let done = PgDone {
                             rows_affected: cc.rows_affected(),
-                        }));
+                        };
+
+                        if set_timeout.is_none() {
+                            r#yield!(Either::Left(done));
+                        } else if skip_done {
+                            // setting the timeout
+                            skip_done = false;
+                        } else if let Some(done) = held_done.replace(done) {
+                            r#yield!(Either::Left(done));
+                        }
                     }
 
                     MessageFormat::EmptyQueryResponse => {
@@ -250,6 +492,7 @@ impl PgConnection {
 
                     MessageFormat::ReadyForQuery => {
                         // processing of the query string is complete
+                        // a held done at this point is from restoring the timeout
                         self.handle_ready_for_query(message)?;
                         break;
                     }
@@ -281,17 +524,29 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     {
         let s = query.query();
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+        // held until the statement has been executed, so it is not closed before
+        let prepared = query.prepared().cloned();
+        let id = prepared
+            .as_ref()
+            .and_then(|id| id.id_on(&self.closed_statements));
 
-        Box::pin(try_stream! {
-            let s = self.run(s, arguments, 0).await?;
+        let cached = arguments.is_some() && (id.is_some() || self.cache_statement.contains_key(s));
+        let mut logger = QueryLogger::new(s, cached, &self.options.log_settings);
+
+        logger.span().instrument_stream(Box::pin(try_stream! {
+            let _prepared = prepared;
+
+            let s = self.run(s, arguments, id, 0, timeout).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
+                logger.record(&v);
                 r#yield!(v);
             }
 
             Ok(())
-        })
+        }))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -304,19 +559,32 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     {
         let s = query.query();
         let arguments = query.take_arguments();
+        let timeout = query.timeout();
+        // held until the statement has been executed, so it is not closed before
+        let prepared = query.prepared().cloned();
+        let id = prepared
+            .as_ref()
+            .and_then(|id| id.id_on(&self.closed_statements));
 
-        Box::pin(async move {
-            let s = self.run(s, arguments, 1).await?;
+        let cached = arguments.is_some() && (id.is_some() || self.cache_statement.contains_key(s));
+        let mut logger = QueryLogger::new(s, cached, &self.options.log_settings);
+
+        logger.span().instrument_future(Box::pin(async move {
+            let _prepared = prepared;
+
+            let s = self.run(s, arguments, id, 1, timeout).await?;
             pin_mut!(s);
 
             while let Some(s) = s.try_next().await? {
+                logger.record(&s);
+
                 if let Either::Right(r) = s {
                     return Ok(Some(r));
                 }
             }
 
             Ok(None)
-        })
+        }))
     }
 
     #[doc(hidden)]
@@ -331,28 +599,43 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let s = query.query();
 
         Box::pin(async move {
-            let id = prepare(self, s, &Default::default()).await?;
+            if let Some(info) = self.cache_describe.get_mut(s) {
+                return Ok(info.clone());
+            }
+
+            let (id, info) = self.prepare_and_describe(s).await?;
 
-            self.stream.write(message::Describe::Statement(id));
+            // the statement was only prepared to be described
+            self.stream.write(Close::Statement(id));
             self.stream.write(Flush);
 
             self.stream.flush().await?;
 
-            let params = recv_desc_params(self).await?;
-            let rows = recv_desc_rows(self).await?;
-
-            let params = self.handle_parameter_description(params).await?;
-
-            self.handle_row_description(rows, true).await?;
+            self.wait_for_close_complete(1).await?;
 
-            let columns = (&*self.scratch_row_columns).clone();
-            let nullable = self.get_nullable_for_columns(&columns).await?;
+            if self.cache_describe.is_enabled() {
+                self.cache_describe.insert(s, info.clone());
+            }
 
-            Ok(StatementInfo {
-                columns,
-                nullable,
-                parameters: Some(Either::Left(params)),
-            })
+            Ok(info)
         })
     }
 }
+
+// restores the `statement_timeout` saved by [set_statement_timeout]
+const RESTORE_STATEMENT_TIMEOUT: &str = "DO $sqlx$BEGIN \
+    PERFORM set_config('statement_timeout', current_setting('sqlx.statement_timeout'), true); \
+    END$sqlx$";
+
+fn set_statement_timeout(timeout: Duration) -> String {
+    // `statement_timeout` is in milliseconds and `0` would disable it
+    let ms = timeout.as_millis().max(1).min(i32::MAX as u128);
+
+    format!(
+        "DO $sqlx$BEGIN \
+        PERFORM set_config('sqlx.statement_timeout', current_setting('statement_timeout'), true); \
+        PERFORM set_config('statement_timeout', '{}', true); \
+        END$sqlx$",
+        ms
+    )
+}
@@ -16,11 +16,17 @@ use crate::postgres::message::{
     Close, Flush, Message, MessageFormat, ReadyForQuery, Terminate, TransactionStatus,
 };
 use crate::postgres::{PgColumn, PgConnectOptions, PgTypeInfo, Postgres};
-use crate::transaction::Transaction;
+use crate::statement::{ClosedStatements, PreparedId, Statement, StatementInfo};
+use crate::transaction::{Transaction, TransactionOptions};
 
+pub use cancel::PgCancelHandle;
+pub use pipeline::{PgPipeline, PgPipelineError, PgPipelineResult};
+
+mod cancel;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod pipeline;
 mod sasl;
 mod stream;
 mod tls;
@@ -34,20 +40,31 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options to open a new connection with when sending cancel requests
+    options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: u32,
 
     // cache statement by query string to the id and columns
-    cache_statement: StatementCache<u32>,
+    cache_statement: StatementCache<CachedStatement>,
+
+    // statements prepared by [Connection::prepare] that are to be closed
+    closed_statements: Arc<ClosedStatements>,
+
+    // the columns of the rows of the statements prepared by [Connection::prepare], by id,
+    // so executing such a statement does not describe it again
+    prepared_columns: HashMap<u32, (Arc<Vec<PgColumn>>, Arc<HashMap<UStr, usize>>)>,
+
+    // cache the results of [describe] by query string
+    cache_describe: StatementCache<StatementInfo<Postgres>>,
 
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<u32, PgTypeInfo>,
@@ -65,9 +82,35 @@ pub struct PgConnection {
     scratch_row_column_names: Arc<HashMap<UStr, usize>>,
 }
 
+// a statement in the statement cache, with the columns of its rows once it was described
+pub(crate) struct CachedStatement {
+    id: u32,
+    columns: Option<(Arc<Vec<PgColumn>>, Arc<HashMap<UStr, usize>>)>,
+}
+
 impl PgConnection {
+    /// The version of the connected server in the form of `server_version_num`,
+    /// e.g. `130004` for `13.4` or `90602` for `9.6.2`.
+    ///
+    /// This is parsed from `server_version` as reported when the connection was established
+    /// and is `None` if it could not be understood.
+    pub fn server_version_num(&self) -> Option<u32> {
+        self.stream.server_version_num
+    }
+
+    /// The capabilities of this connection, as negotiated with the server when the connection
+    /// was established.
+    pub fn capabilities(&self) -> PgCapabilities {
+        PgCapabilities {
+            // requested in the startup message; every supported server speaks it
+            protocol_version: (3, 0),
+            tls: self.stream.is_tls(),
+            persistent: self.options.persistent,
+        }
+    }
+
     // will return when the connection is ready for another query
-    async fn wait_until_ready(&mut self) -> Result<(), Error> {
+    pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.wbuf.is_empty() {
             self.stream.flush().await?;
         }
@@ -92,6 +135,37 @@ impl PgConnection {
     }
 }
 
+/// The capabilities of a PostgreSQL connection.
+///
+/// Returned from [`PgConnection::capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgCapabilities {
+    protocol_version: (u16, u16),
+    tls: bool,
+    persistent: bool,
+}
+
+impl PgCapabilities {
+    /// The `(major, minor)` version of the frontend/backend protocol in use.
+    pub fn protocol_version(&self) -> (u16, u16) {
+        self.protocol_version
+    }
+
+    /// Whether the connection is encrypted with TLS.
+    pub fn is_tls(&self) -> bool {
+        self.tls
+    }
+
+    /// Whether queries are executed as prepared statements that are kept on the server and
+    /// return their results in the binary format.
+    ///
+    /// If not, queries without arguments use the simple query protocol and return their results
+    /// in the text format; see [`PgConnectOptions::persistent`].
+    pub fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+}
+
 impl Debug for PgConnection {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("PgConnection").finish()
@@ -123,6 +197,51 @@ impl Connection for PgConnection {
         self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // `DISCARD ALL` deallocates all prepared statements of the session
+            self.cache_statement.clear();
+            self.cache_describe.clear();
+
+            self.execute("DISCARD ALL").await?;
+
+            // the statements of earlier [Connection::prepare] calls are gone as well
+            self.closed_statements = Arc::default();
+            self.prepared_columns.clear();
+
+            Ok(())
+        })
+    }
+
+    fn prepare<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Statement<'q, Postgres>, Error>>
+    where
+        Self: Sized,
+        for<'c> &'c mut Self: Executor<'c, Database = Self::Database>,
+    {
+        Box::pin(async move {
+            let (id, info) = self.prepare_and_describe(sql).await?;
+
+            self.prepared_columns.insert(
+                id,
+                (
+                    Arc::clone(&self.scratch_row_columns),
+                    Arc::clone(&self.scratch_row_column_names),
+                ),
+            );
+
+            Ok(Statement {
+                sql,
+                info,
+                id: Some(Arc::new(PreparedId::new(id, &self.closed_statements))),
+            })
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -130,6 +249,16 @@ impl Connection for PgConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.cache_statement.len()
     }
@@ -140,8 +269,10 @@ impl Connection for PgConnection {
 
             self.wait_until_ready().await?;
 
+            self.cache_describe.clear();
+
             while let Some(statement) = self.cache_statement.remove_lru() {
-                self.stream.write(Close::Statement(statement));
+                self.stream.write(Close::Statement(statement.id));
                 cleared += 1;
             }
 
@@ -156,6 +287,10 @@ impl Connection for PgConnection {
         })
     }
 
+    fn shrink_buffers(&mut self) {
+        self.stream.shrink_buffers();
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.wait_until_ready().boxed()
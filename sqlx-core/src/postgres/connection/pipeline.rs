@@ -0,0 +1,351 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::mem;
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::ext::ustr::UStr;
+use crate::logging::QueryLogger;
+use crate::postgres::connection::executor::prepare;
+use crate::postgres::connection::CachedStatement;
+use crate::postgres::message::{self, Bind, CommandComplete, DataRow, MessageFormat};
+use crate::postgres::{
+    PgArguments, PgColumn, PgConnection, PgDone, PgRow, PgValueFormat, Postgres,
+};
+
+/// A batch of queries to be sent to the server at once with
+/// [`PgConnection::execute_pipeline`].
+///
+/// ```rust,ignore
+/// let mut pipeline = PgPipeline::new();
+///
+/// pipeline
+///     .push(sqlx::query("UPDATE accounts SET balance = balance - $1 WHERE id = $2").bind(10).bind(1))
+///     .push(sqlx::query("UPDATE accounts SET balance = balance + $1 WHERE id = $2").bind(10).bind(2))
+///     .push(sqlx::query("SELECT id, balance FROM accounts"));
+///
+/// let results = conn.execute_pipeline(pipeline).await?;
+///
+/// assert_eq!(results[0].rows_affected(), 1);
+/// let accounts = results[2].rows();
+/// ```
+#[derive(Default)]
+pub struct PgPipeline<'q> {
+    queries: Vec<(&'q str, PgArguments)>,
+}
+
+impl<'q> PgPipeline<'q> {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a query, with its bound arguments, to the end of the pipeline.
+    pub fn push<E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let sql = query.query();
+        let arguments = query.take_arguments().unwrap_or_default();
+
+        self.queries.push((sql, arguments));
+        self
+    }
+
+    /// The number of queries in the pipeline.
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns `true` if no queries were added to the pipeline.
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+}
+
+/// The result of one query of a [`PgPipeline`].
+pub struct PgPipelineResult {
+    rows: Vec<PgRow>,
+    done: PgDone,
+}
+
+impl PgPipelineResult {
+    /// The rows returned by the query.
+    pub fn rows(&self) -> &[PgRow] {
+        &self.rows
+    }
+
+    /// Takes the rows returned by the query.
+    pub fn into_rows(self) -> Vec<PgRow> {
+        self.rows
+    }
+
+    /// The number of rows affected by the query, if it was an `UPDATE`, `INSERT`, or `DELETE`.
+    pub fn rows_affected(&self) -> u64 {
+        self.done.rows_affected
+    }
+}
+
+/// An error returned from [`PgConnection::execute_pipeline`], with the position of the query
+/// that failed.
+#[derive(Debug)]
+pub struct PgPipelineError {
+    index: usize,
+    error: Error,
+}
+
+impl PgPipelineError {
+    /// The position of the query that failed, starting at `0`.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The error that occurred.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Takes the error that occurred.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl Display for PgPipelineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "query {} of the pipeline failed: {}",
+            self.index, self.error
+        )
+    }
+}
+
+impl StdError for PgPipelineError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<PgPipelineError> for Error {
+    fn from(error: PgPipelineError) -> Self {
+        error.error
+    }
+}
+
+// a statement of the pipeline, ready to be executed
+struct PipelinedStatement {
+    id: u32,
+    columns: Arc<Vec<PgColumn>>,
+    column_names: Arc<HashMap<UStr, usize>>,
+}
+
+impl PgConnection {
+    /// Executes all queries of the pipeline, sending them to the server at once and
+    /// collecting their results in order.
+    ///
+    /// Statements that are not in the statement cache yet are prepared and described first,
+    /// which takes two round-trips each; a cached statement keeps its description. The queries
+    /// themselves are then sent in a single flush and followed by a single `Sync`, so the whole
+    /// pipeline takes one more round-trip instead of one per query.
+    ///
+    /// Unless the connection is in a transaction, the queries of the pipeline run in a single
+    /// implicit transaction: if one query fails, the queries after it are skipped and the effects
+    /// of the queries before it are rolled back. The returned [`PgPipelineError`] tells which
+    /// query failed.
+    ///
+    /// The [timeout](crate::query::Query::timeout) of a query is ignored.
+    pub async fn execute_pipeline(
+        &mut self,
+        pipeline: PgPipeline<'_>,
+    ) -> Result<Vec<PgPipelineResult>, PgPipelineError> {
+        if pipeline.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // statements to close once the pipeline has ended, successfully or not, as they are not
+        // cached (or were evicted from the cache to make room for another statement of the
+        // pipeline); they are closed before the next query on this connection
+        let mut to_close = Vec::new();
+
+        let result = self.run_pipeline(pipeline, &mut to_close).await;
+
+        for id in to_close {
+            self.closed_statements.push(id);
+        }
+
+        result
+    }
+
+    async fn run_pipeline(
+        &mut self,
+        mut pipeline: PgPipeline<'_>,
+        to_close: &mut Vec<u32>,
+    ) -> Result<Vec<PgPipelineResult>, PgPipelineError> {
+        self.wait_until_ready()
+            .await
+            .map_err(|error| PgPipelineError { index: 0, error })?;
+
+        let mut statements = Vec::with_capacity(pipeline.len());
+        let mut loggers = Vec::with_capacity(pipeline.len());
+
+        for (index, (sql, arguments)) in pipeline.queries.iter_mut().enumerate() {
+            let fail = move |error| PgPipelineError { index, error };
+
+            // the protocol encodes the number of parameters in 16 bits
+            if arguments.types.len() > u16::MAX as usize {
+                return Err(fail(Error::TooManyArguments {
+                    count: arguments.types.len(),
+                    max: u16::MAX as usize,
+                }));
+            }
+
+            let cached = self.cache_statement.get_mut(sql).map(|cached| cached.id);
+
+            let id = match cached {
+                Some(id) => id,
+
+                None => {
+                    let id = prepare(self, sql, arguments).await.map_err(fail)?;
+                    let cached = CachedStatement { id, columns: None };
+
+                    if !self.cache_statement.is_enabled() {
+                        to_close.push(id);
+                    } else if let Some(evicted) = self.cache_statement.insert(sql, cached) {
+                        // the evicted statement may be used by an earlier query of the pipeline
+                        to_close.push(evicted.id);
+                    }
+
+                    id
+                }
+            };
+
+            arguments
+                .buffer
+                .patch_type_holes(self)
+                .await
+                .map_err(fail)?;
+
+            // the columns of its rows, as described when the statement was first executed
+            self.handle_statement_columns(sql, id).await.map_err(fail)?;
+
+            statements.push(PipelinedStatement {
+                id,
+                columns: Arc::clone(&self.scratch_row_columns),
+                column_names: Arc::clone(&self.scratch_row_column_names),
+            });
+
+            loggers.push(QueryLogger::new(
+                sql,
+                cached.is_some(),
+                &self.options.log_settings,
+            ));
+        }
+
+        for ((_, arguments), statement) in pipeline.queries.iter().zip(&statements) {
+            self.stream.write(Bind {
+                portal: None,
+                statement: statement.id,
+                formats: &[PgValueFormat::Binary],
+                num_params: arguments.types.len() as i16,
+                params: &*arguments.buffer,
+                result_formats: &[PgValueFormat::Binary],
+            });
+
+            self.stream.write(message::Execute {
+                portal: None,
+                limit: 0,
+            });
+        }
+
+        // a single [Sync] ends the pipeline and triggers a [ReadyForQuery]
+        self.stream.write(message::Sync);
+        self.pending_ready_for_query_count += 1;
+
+        let last = statements.len() - 1;
+
+        self.stream
+            .flush()
+            .await
+            .map_err(|error| PgPipelineError { index: 0, error })?;
+
+        let mut results = Vec::with_capacity(statements.len());
+        let mut rows = Vec::new();
+
+        loop {
+            // the query whose results are being received
+            let index = results.len().min(last);
+            let fail = move |error| PgPipelineError { index, error };
+
+            let message = self.stream.recv().await.map_err(fail)?;
+
+            let rows_affected = match message.format {
+                MessageFormat::BindComplete | MessageFormat::NoData => {
+                    // harmless messages to ignore
+                    continue;
+                }
+
+                MessageFormat::DataRow => {
+                    let data: DataRow = message.decode().map_err(fail)?;
+                    let statement = &statements[index];
+
+                    let row = PgRow {
+                        data,
+                        format: PgValueFormat::Binary,
+                        columns: Arc::clone(&statement.columns),
+                        column_names: Arc::clone(&statement.column_names),
+                    };
+
+                    loggers[index].increment_rows_returned();
+                    rows.push(row);
+
+                    continue;
+                }
+
+                MessageFormat::CommandComplete => {
+                    let cc: CommandComplete = message.decode().map_err(fail)?;
+
+                    cc.rows_affected()
+                }
+
+                // an empty query string
+                MessageFormat::EmptyQueryResponse => 0,
+
+                MessageFormat::ReadyForQuery => {
+                    self.handle_ready_for_query(message).map_err(fail)?;
+                    break;
+                }
+
+                _ => {
+                    return Err(fail(err_protocol!(
+                        "execute_pipeline: unexpected message: {:?}",
+                        message.format
+                    )));
+                }
+            };
+
+            loggers[index].increase_rows_affected(rows_affected);
+
+            results.push(PgPipelineResult {
+                rows: mem::take(&mut rows),
+                done: PgDone { rows_affected },
+            });
+        }
+
+        if results.len() != statements.len() {
+            return Err(PgPipelineError {
+                index: results.len().min(last),
+                error: err_protocol!(
+                    "execute_pipeline: expected {} results but received {}",
+                    statements.len(),
+                    results.len()
+                ),
+            });
+        }
+
+        Ok(results)
+    }
+}
@@ -8,7 +8,7 @@ use log::Level;
 use crate::error::Error;
 use crate::io::{BufStream, Decode, Encode};
 use crate::net::{MaybeTlsStream, Socket};
-use crate::postgres::message::{Message, MessageFormat, Notice, Notification};
+use crate::postgres::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
 use crate::postgres::{PgConnectOptions, PgDatabaseError, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
@@ -27,6 +27,9 @@ pub struct PgStream {
     // this is set when creating a PgListener and only written to if that listener is
     // re-used for query execution in-between receiving messages
     pub(crate) notifications: Option<UnboundedSender<Notification>>,
+
+    // `server_version` as last reported by the server, in the form of `server_version_num`
+    pub(crate) server_version_num: Option<u32>,
 }
 
 impl PgStream {
@@ -41,6 +44,7 @@ impl PgStream {
         Ok(Self {
             inner,
             notifications: None,
+            server_version_num: None,
         })
     }
 
@@ -108,7 +112,13 @@ impl PgStream {
                     // informs the frontend about the current (initial)
                     // setting of backend parameters
 
-                    // we currently have no use for that data so we promptly ignore this message
+                    // we only keep the server version; everything else is ignored
+                    let status: ParameterStatus = message.decode()?;
+
+                    if status.name == "server_version" {
+                        self.server_version_num = parse_server_version(&status.value);
+                    }
+
                     continue;
                 }
 
@@ -168,3 +178,36 @@ impl DerefMut for PgStream {
         &mut self.inner
     }
 }
+
+// reproduces `server_version_num` from a `server_version` such as `13.4 (Debian 13.4-1)`,
+// `9.6.2` or `14beta1`
+fn parse_server_version(s: &str) -> Option<u32> {
+    let end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or_else(|| s.len());
+
+    let mut parts = s[..end].split('.');
+
+    let major: u32 = parts.next()?.parse().ok()?;
+    let mut next = || parts.next().map_or(Ok(0), str::parse::<u32>).ok();
+
+    if major >= 10 {
+        // since 10, the version is `major.minor`
+        Some(major * 10000 + next()?)
+    } else {
+        Some(major * 10000 + next()? * 100 + next()?)
+    }
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(parse_server_version("13.4"), Some(130004));
+    assert_eq!(
+        parse_server_version("13.4 (Debian 13.4-1.pgdg100+1)"),
+        Some(130004)
+    );
+    assert_eq!(parse_server_version("10.15"), Some(100015));
+    assert_eq!(parse_server_version("9.6.2"), Some(90602));
+    assert_eq!(parse_server_version("14beta1"), Some(140000));
+    assert_eq!(parse_server_version("devel"), None);
+}
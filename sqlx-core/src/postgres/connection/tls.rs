@@ -9,22 +9,25 @@ use crate::postgres::connection::stream::PgStream;
 use crate::postgres::message::SslRequest;
 use crate::postgres::{PgConnectOptions, PgSslMode};
 
+// `ssl_mode` is passed separately from `options` as `Allow` retries with `Require`
 pub(super) async fn maybe_upgrade(
     stream: &mut PgStream,
     options: &PgConnectOptions,
+    ssl_mode: PgSslMode,
 ) -> Result<(), Error> {
     // https://www.postgresql.org/docs/12/libpq-ssl.html#LIBPQ-SSL-SSLMODE-STATEMENTS
-    match options.ssl_mode {
-        // FIXME: Implement ALLOW
+    match ssl_mode {
+        // `Allow` only falls back to SSL when the non-SSL connection is rejected;
+        // see `PgConnection::establish`
         PgSslMode::Allow | PgSslMode::Disable => {}
 
         PgSslMode::Prefer => {
             // try upgrade, but its okay if we fail
-            upgrade(stream, options).await?;
+            upgrade(stream, options, ssl_mode).await?;
         }
 
         PgSslMode::Require | PgSslMode::VerifyFull | PgSslMode::VerifyCa => {
-            if !upgrade(stream, options).await? {
+            if !upgrade(stream, options, ssl_mode).await? {
                 // upgrade failed, die
                 return Err(Error::Tls("server does not support TLS".into()));
             }
@@ -34,7 +37,11 @@ pub(super) async fn maybe_upgrade(
     Ok(())
 }
 
-async fn upgrade(stream: &mut PgStream, options: &PgConnectOptions) -> Result<bool, Error> {
+async fn upgrade(
+    stream: &mut PgStream,
+    options: &PgConnectOptions,
+    ssl_mode: PgSslMode,
+) -> Result<bool, Error> {
     // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
 
     // To initiate an SSL-encrypted connection, the frontend initially sends an
@@ -65,15 +72,17 @@ async fn upgrade(stream: &mut PgStream, options: &PgConnectOptions) -> Result<bo
 
     // FIXME: de-duplicate with mysql/connection/tls.rs
 
-    let accept_invalid_certs = !matches!(
-        options.ssl_mode,
-        PgSslMode::VerifyCa | PgSslMode::VerifyFull
-    );
+    // like libpq, `Require` verifies the certificate chain if a root certificate is given
+    let accept_invalid_certs = match ssl_mode {
+        PgSslMode::VerifyCa | PgSslMode::VerifyFull => false,
+        PgSslMode::Require => options.ssl_root_cert.is_none(),
+        _ => true,
+    };
 
     let mut builder = TlsConnector::builder();
     builder
         .danger_accept_invalid_certs(accept_invalid_certs)
-        .danger_accept_invalid_hostnames(!matches!(options.ssl_mode, PgSslMode::VerifyFull));
+        .danger_accept_invalid_hostnames(!matches!(ssl_mode, PgSslMode::VerifyFull));
 
     if !accept_invalid_certs {
         if let Some(ca) = &options.ssl_root_cert {
@@ -90,7 +99,22 @@ async fn upgrade(stream: &mut PgStream, options: &PgConnectOptions) -> Result<bo
     #[cfg(feature = "runtime-async-std")]
     let connector = builder;
 
-    stream.upgrade(&options.host, connector.into()).await?;
+    stream
+        .upgrade(&options.host, connector.into())
+        .await
+        .map_err(|e| match e {
+            Error::Tls(e) if !accept_invalid_certs => Error::Tls(
+                format!(
+                    "failed to verify the certificate of {} for sslmode={}: {}",
+                    options.host,
+                    ssl_mode.as_str(),
+                    e
+                )
+                .into(),
+            ),
+
+            e => e,
+        })?;
 
     Ok(true)
 }
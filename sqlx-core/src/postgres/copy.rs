@@ -0,0 +1,598 @@
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::ops::DerefMut;
+use std::str::Chars;
+
+use bytes::Bytes;
+use futures_core::stream::BoxStream;
+
+use crate::error::{Error, Result};
+use crate::pool::{Pool, PoolConnection};
+use crate::postgres::message::{
+    CommandComplete, CopyData, CopyDone, CopyFail, CopyResponse, MessageFormat, Query,
+};
+use crate::postgres::{PgConnection, Postgres};
+
+impl PgConnection {
+    /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
+    /// to Postgres. This is a more efficient way to import data into Postgres as compared to
+    /// `INSERT` but requires one of a few specific data formats (text/CSV/binary).
+    ///
+    /// If `statement` is anything other than a `COPY ... FROM STDIN ...` command, an error is
+    /// returned.
+    ///
+    /// Command examples and accepted formats for `COPY` data are shown here:
+    /// https://www.postgresql.org/docs/current/sql-copy.html
+    ///
+    /// ### Note
+    /// [`PgCopyIn::finish`] or [`PgCopyIn::abort`] *must* be called when finished or the
+    /// connection will return an error the next time it is used.
+    pub async fn copy_in_raw(&mut self, statement: &str) -> Result<PgCopyIn<&mut Self>> {
+        PgCopyIn::begin(self, statement).await
+    }
+
+    /// Issue a `COPY TO STDOUT` statement and transition the connection to streaming data
+    /// from Postgres. This is a more efficient way to export data from Postgres but
+    /// arrives in chunks of one of a few data formats (text/CSV/binary).
+    ///
+    /// If `statement` is anything other than a `COPY ... TO STDOUT ...` command,
+    /// an error is returned.
+    ///
+    /// Note that once this process has begun, unless you read the stream to completion,
+    /// it can only be canceled in two ways:
+    ///
+    /// 1. by closing the connection, or:
+    /// 2. by using another connection to kill the server process that is sending the data
+    ///    as shown [in this StackOverflow answer](https://stackoverflow.com/a/35319598).
+    ///
+    /// If you don't read the stream to completion, the next time the connection is used it
+    /// will need to read and discard all the remaining queued data, which could take some time.
+    ///
+    /// Command examples and accepted formats for `COPY` data are shown here:
+    /// https://www.postgresql.org/docs/current/sql-copy.html
+    pub async fn copy_out_raw<'c>(
+        &'c mut self,
+        statement: &str,
+    ) -> Result<BoxStream<'c, Result<Bytes>>> {
+        pg_begin_copy_out(self, statement).await
+    }
+
+    /// Issue a `COPY FROM STDIN` statement and stream `rows` to Postgres in the text format,
+    /// returning the number of rows copied.
+    ///
+    /// Each row is a tuple or a `Vec` of [`PgCopyValue`]s, such as strings, numbers, and
+    /// `Option`s (`None` is sent as `NULL`). Values are escaped as needed, so they may contain
+    /// tabs, newlines, and backslashes.
+    ///
+    /// The statement must use the default text format, with the default `DELIMITER` and `NULL`
+    /// options; an error is returned before the statement is issued if it has a `FORMAT`
+    /// (other than `text`), `CSV`, `BINARY`, `DELIMITER` or `NULL` option.
+    ///
+    /// ```rust,ignore
+    /// let rows = vec![(1_i32, "Alice"), (2, "Bob\tby")];
+    ///
+    /// let copied = conn
+    ///     .copy_in_rows("COPY users (id, name) FROM STDIN", rows)
+    ///     .await?;
+    ///
+    /// assert_eq!(copied, 2);
+    /// ```
+    pub async fn copy_in_rows<I>(&mut self, statement: &str, rows: I) -> Result<u64>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        check_copy_in_rows_options(statement)?;
+
+        pg_copy_in_rows(self.copy_in_raw(statement).await?, rows).await
+    }
+}
+
+impl Pool<Postgres> {
+    /// Issue a `COPY FROM STDIN` statement and begin streaming data to Postgres on a
+    /// connection acquired from this pool.
+    ///
+    /// See [`PgConnection::copy_in_raw`] for details.
+    pub async fn copy_in_raw(&self, statement: &str) -> Result<PgCopyIn<PoolConnection<Postgres>>> {
+        PgCopyIn::begin(self.acquire().await?, statement).await
+    }
+
+    /// Issue a `COPY TO STDOUT` statement and begin streaming data from Postgres on a
+    /// connection acquired from this pool.
+    ///
+    /// See [`PgConnection::copy_out_raw`] for details.
+    pub async fn copy_out_raw(&self, statement: &str) -> Result<BoxStream<'static, Result<Bytes>>> {
+        pg_begin_copy_out(self.acquire().await?, statement).await
+    }
+
+    /// Issue a `COPY FROM STDIN` statement and stream `rows` to Postgres in the text format on
+    /// a connection acquired from this pool.
+    ///
+    /// See [`PgConnection::copy_in_rows`] for details.
+    pub async fn copy_in_rows<I>(&self, statement: &str, rows: I) -> Result<u64>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        check_copy_in_rows_options(statement)?;
+
+        pg_copy_in_rows(self.copy_in_raw(statement).await?, rows).await
+    }
+}
+
+// rows written with [PgCopyIn::send_row] are buffered up to about this size before being sent
+const ROW_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A connection in streaming `COPY FROM STDIN` mode.
+///
+/// Created by [`PgConnection::copy_in_raw`] or [`Pool::copy_in_raw`].
+///
+/// ### Note
+/// [`PgCopyIn::finish`] or [`PgCopyIn::abort`] *must* be called when finished or the
+/// connection will return an error the next time it is used.
+#[must_use = "connection will error on next use if `.finish()` or `.abort()` is not called"]
+pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
+    conn: Option<C>,
+    response: CopyResponse,
+    rows: String,
+}
+
+impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
+    async fn begin(mut conn: C, statement: &str) -> Result<Self> {
+        conn.wait_until_ready().await?;
+        conn.stream.send(Query(statement)).await?;
+
+        // [Query] will trigger a [ReadyForQuery]
+        conn.pending_ready_for_query_count += 1;
+
+        let response: CopyResponse = conn
+            .stream
+            .recv_expect(MessageFormat::CopyInResponse)
+            .await?;
+
+        Ok(PgCopyIn {
+            conn: Some(conn),
+            response,
+            rows: String::new(),
+        })
+    }
+
+    /// Returns `true` if Postgres is expecting data in text or CSV format.
+    pub fn is_textual(&self) -> bool {
+        self.response.format == 0
+    }
+
+    /// Returns the number of columns expected in the input.
+    pub fn num_columns(&self) -> usize {
+        self.response.format_codes.len()
+    }
+
+    /// Send a chunk of `COPY` data.
+    ///
+    /// The data is sent as-is; it does not need to be aligned to row boundaries.
+    pub async fn send(&mut self, data: impl AsRef<[u8]>) -> Result<&mut Self> {
+        self.flush_rows().await?;
+
+        self.conn
+            .as_deref_mut()
+            .expect("send: conn taken")
+            .stream
+            .send(CopyData(data))
+            .await?;
+
+        Ok(self)
+    }
+
+    /// Send a row of `COPY` data in the text format, escaping its values as needed.
+    ///
+    /// Rows are buffered and sent in larger chunks; any rows still buffered are sent by
+    /// [`finish`](#method.finish). The statement must use the default text format, with the
+    /// default `DELIMITER` and `NULL` options.
+    pub async fn send_row<R>(&mut self, row: &R) -> Result<&mut Self>
+    where
+        R: PgCopyRow + ?Sized,
+    {
+        row.write_copy_row(&mut self.rows);
+
+        if self.rows.len() >= ROW_BUFFER_SIZE {
+            self.flush_rows().await?;
+        }
+
+        Ok(self)
+    }
+
+    async fn flush_rows(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+
+        self.conn
+            .as_deref_mut()
+            .expect("send_row: conn taken")
+            .stream
+            .send(CopyData(self.rows.as_bytes()))
+            .await?;
+
+        self.rows.clear();
+
+        Ok(())
+    }
+
+    /// Signal that the `COPY` process is complete.
+    ///
+    /// The number of rows affected is returned.
+    pub async fn finish(mut self) -> Result<u64> {
+        self.flush_rows().await?;
+
+        let mut conn = self.conn.take().expect("finish: conn taken");
+
+        conn.stream.send(CopyDone).await?;
+
+        let cc: CommandComplete = conn
+            .stream
+            .recv_expect(MessageFormat::CommandComplete)
+            .await?;
+
+        conn.wait_until_ready().await?;
+
+        Ok(cc.rows_affected())
+    }
+
+    /// Signal that the `COPY` process should be aborted and any data received should be
+    /// discarded.
+    ///
+    /// The given message can be used for indicating the reason for the abort in the
+    /// database logs.
+    pub async fn abort(mut self, msg: impl AsRef<str>) -> Result<()> {
+        let mut conn = self.conn.take().expect("abort: conn taken");
+
+        conn.stream.send(CopyFail(msg.as_ref())).await?;
+
+        match conn.stream.recv().await {
+            // postgres responds with an error about our abort; that is what we asked for
+            // 57014 = query_canceled
+            Err(Error::Database(e)) if e.code().as_deref() == Some("57014") => {}
+
+            Err(e) => return Err(e),
+
+            Ok(message) => {
+                return Err(err_protocol!(
+                    "copy_in: unexpected message after CopyFail: {:?}",
+                    message.format
+                ))
+            }
+        }
+
+        conn.wait_until_ready().await
+    }
+}
+
+impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
+    fn drop(&mut self) {
+        if let Some(mut conn) = self.conn.take() {
+            // the message will be flushed the next time the connection is used
+            conn.stream.write(CopyFail(
+                "PgCopyIn dropped without calling finish() or abort()",
+            ));
+        }
+    }
+}
+
+// the rows are only escaped for the default text format, so the options that change it
+// are rejected rather than silently sending data that Postgres would misread
+fn check_copy_in_rows_options(statement: &str) -> Result<()> {
+    let words = copy_statement_words(statement);
+
+    // the options follow `FROM STDIN` and precede the `WHERE` clause, if any
+    let options = words
+        .iter()
+        .skip_while(|(word, quoted)| *quoted || word != "stdin")
+        .skip(1)
+        .take_while(|(word, quoted)| *quoted || word != "where");
+
+    for (word, quoted) in options {
+        let rejected = match &**word {
+            "csv" | "binary" => true,
+            "delimiter" | "null" => !*quoted,
+            _ => false,
+        };
+
+        if rejected {
+            return Err(err_protocol!(
+                "copy_in_rows: expected COPY in the text format with the default options, \
+                 but the statement has the option `{}`; use copy_in_raw instead",
+                word.to_uppercase()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// the lowercased words of the statement, besides quoted identifiers and string literals
+// (with `true`, as an option value such as `FORMAT 'csv'` may be a literal)
+fn copy_statement_words(statement: &str) -> Vec<(String, bool)> {
+    let mut words = Vec::new();
+    let mut chars = statement.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // a quoted identifier, such as a column named `csv`, is never an option
+            '"' => {
+                let _ = copy_statement_quoted(&mut chars, '"');
+            }
+
+            '\'' => {
+                let literal = copy_statement_quoted(&mut chars, '\'');
+                words.push((literal.to_lowercase(), true));
+            }
+
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = c.to_lowercase().collect::<String>();
+
+                while let Some(&c) = chars.peek() {
+                    if !c.is_alphanumeric() && c != '_' {
+                        break;
+                    }
+
+                    word.extend(c.to_lowercase());
+                    chars.next();
+                }
+
+                words.push((word, false));
+            }
+
+            _ => {}
+        }
+    }
+
+    words
+}
+
+// the rest of a quoted identifier or literal, where a doubled quote stands for itself
+fn copy_statement_quoted(chars: &mut Peekable<Chars<'_>>, quote: char) -> String {
+    let mut quoted = String::new();
+
+    while let Some(c) = chars.next() {
+        if c == quote {
+            if chars.peek() != Some(&quote) {
+                break;
+            }
+
+            chars.next();
+        }
+
+        quoted.push(c);
+    }
+
+    quoted
+}
+
+async fn pg_copy_in_rows<C, I>(mut copy: PgCopyIn<C>, rows: I) -> Result<u64>
+where
+    C: DerefMut<Target = PgConnection>,
+    I: IntoIterator,
+    I::Item: PgCopyRow,
+{
+    if !copy.is_textual() {
+        copy.abort("copy_in_rows: expected the text format").await?;
+
+        return Err(err_protocol!(
+            "copy_in_rows: expected COPY in the text format but Postgres expects binary data"
+        ));
+    }
+
+    for row in rows {
+        copy.send_row(&row).await?;
+    }
+
+    copy.finish().await
+}
+
+async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
+    mut conn: C,
+    statement: &str,
+) -> Result<BoxStream<'c, Result<Bytes>>> {
+    conn.wait_until_ready().await?;
+    conn.stream.send(Query(statement)).await?;
+
+    // [Query] will trigger a [ReadyForQuery]
+    conn.pending_ready_for_query_count += 1;
+
+    let _: CopyResponse = conn
+        .stream
+        .recv_expect(MessageFormat::CopyOutResponse)
+        .await?;
+
+    Ok(Box::pin(try_stream! {
+        loop {
+            let message = conn.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::CopyData => {
+                    r#yield!(message.contents);
+                }
+
+                MessageFormat::CopyDone => {
+                    let _: CommandComplete = conn
+                        .stream
+                        .recv_expect(MessageFormat::CommandComplete)
+                        .await?;
+
+                    conn.wait_until_ready().await?;
+
+                    break;
+                }
+
+                _ => {
+                    Err(err_protocol!(
+                        "copy_out: unexpected message: {:?}",
+                        message.format
+                    ))?;
+                }
+            }
+        }
+
+        Ok(())
+    }))
+}
+
+/// A value that can be sent as a field of `COPY` data in the text format.
+///
+/// See [`PgConnection::copy_in_rows`] and [`PgCopyIn::send_row`].
+pub trait PgCopyValue {
+    /// Writes the value in the text format, escaped as needed.
+    fn write_copy_text(&self, buf: &mut String);
+}
+
+impl PgCopyValue for str {
+    fn write_copy_text(&self, buf: &mut String) {
+        for c in self.chars() {
+            match c {
+                '\\' => buf.push_str("\\\\"),
+                '\t' => buf.push_str("\\t"),
+                '\n' => buf.push_str("\\n"),
+                '\r' => buf.push_str("\\r"),
+                c => buf.push(c),
+            }
+        }
+    }
+}
+
+impl PgCopyValue for String {
+    fn write_copy_text(&self, buf: &mut String) {
+        self.as_str().write_copy_text(buf);
+    }
+}
+
+impl PgCopyValue for bool {
+    fn write_copy_text(&self, buf: &mut String) {
+        buf.push(if *self { 't' } else { 'f' });
+    }
+}
+
+impl<T: PgCopyValue> PgCopyValue for Option<T> {
+    fn write_copy_text(&self, buf: &mut String) {
+        match self {
+            Some(value) => value.write_copy_text(buf),
+            None => buf.push_str("\\N"),
+        }
+    }
+}
+
+impl<T: PgCopyValue + ?Sized> PgCopyValue for &'_ T {
+    fn write_copy_text(&self, buf: &mut String) {
+        (**self).write_copy_text(buf);
+    }
+}
+
+macro_rules! impl_copy_value_display {
+    ($($ty:ty),*) => {
+        $(
+            // formatted values of these types never need to be escaped
+            impl PgCopyValue for $ty {
+                fn write_copy_text(&self, buf: &mut String) {
+                    let _ = write!(buf, "{}", self);
+                }
+            }
+        )*
+    };
+}
+
+impl_copy_value_display!(i8, i16, i32, i64, u32, f32, f64);
+
+/// A row of `COPY` data in the text format.
+///
+/// Implemented for tuples (of up to 16 values) and slices or `Vec`s of [`PgCopyValue`]s.
+pub trait PgCopyRow {
+    /// Writes the values of the row separated by tabs, followed by a newline.
+    fn write_copy_row(&self, buf: &mut String);
+}
+
+impl<T: PgCopyValue> PgCopyRow for [T] {
+    fn write_copy_row(&self, buf: &mut String) {
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                buf.push('\t');
+            }
+
+            value.write_copy_text(buf);
+        }
+
+        buf.push('\n');
+    }
+}
+
+impl<T: PgCopyValue> PgCopyRow for Vec<T> {
+    fn write_copy_row(&self, buf: &mut String) {
+        self.as_slice().write_copy_row(buf);
+    }
+}
+
+impl<R: PgCopyRow + ?Sized> PgCopyRow for &'_ R {
+    fn write_copy_row(&self, buf: &mut String) {
+        (**self).write_copy_row(buf);
+    }
+}
+
+macro_rules! impl_copy_row_tuple {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: PgCopyValue, $($rest: PgCopyValue),*> PgCopyRow for ($first, $($rest,)*) {
+            #[allow(non_snake_case)]
+            fn write_copy_row(&self, buf: &mut String) {
+                let ($first, $($rest,)*) = self;
+
+                $first.write_copy_text(buf);
+
+                $(
+                    buf.push('\t');
+                    $rest.write_copy_text(buf);
+                )*
+
+                buf.push('\n');
+            }
+        }
+    };
+}
+
+impl_copy_row_tuple!(T1);
+impl_copy_row_tuple!(T1, T2);
+impl_copy_row_tuple!(T1, T2, T3);
+impl_copy_row_tuple!(T1, T2, T3, T4);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+impl_copy_row_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
+
+#[test]
+fn test_check_copy_in_rows_options() {
+    assert!(check_copy_in_rows_options("COPY users (id, name) FROM STDIN").is_ok());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN WITH (FORMAT text)").is_ok());
+    assert!(check_copy_in_rows_options(r#"COPY users ("csv", "null") FROM STDIN"#).is_ok());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN WHERE name IS NULL").is_ok());
+
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN WITH (FORMAT csv)").is_err());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN (FORMAT 'CSV')").is_err());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN CSV HEADER").is_err());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN BINARY").is_err());
+    assert!(check_copy_in_rows_options("COPY users FROM STDIN DELIMITER ','").is_err());
+    assert!(check_copy_in_rows_options("copy users from stdin with null as ''").is_err());
+}
+
+#[test]
+fn test_write_copy_row_escapes_values() {
+    let mut buf = String::new();
+
+    (1_i32, "a\tb\nc\\d\re", None::<&str>, Some(true)).write_copy_row(&mut buf);
+    vec!["\\N", ""].write_copy_row(&mut buf);
+
+    assert_eq!(buf, "1\ta\\tb\\nc\\\\d\\re\t\\N\tt\n\\\\N\t\n");
+}
@@ -2,6 +2,17 @@ use crate::done::Done;
 use crate::postgres::Postgres;
 use std::iter::{Extend, IntoIterator};
 
+/// The result of executing a query with PostgreSQL.
+///
+/// Unlike MySQL and SQLite, PostgreSQL does not report the ID generated by an `INSERT`;
+/// add a `RETURNING` clause to the statement and fetch it as a row instead:
+///
+/// ```rust,ignore
+/// let id: i64 = sqlx::query_scalar("INSERT INTO users (name) VALUES ($1) RETURNING id")
+///     .bind("Alice")
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
 #[derive(Debug, Default)]
 pub struct PgDone {
     pub(super) rows_affected: u64,
@@ -170,6 +170,22 @@ impl DatabaseError for PgDatabaseError {
         Some(Cow::Borrowed(self.code()))
     }
 
+    fn constraint(&self) -> Option<&str> {
+        self.constraint()
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.table()
+    }
+
+    fn column(&self) -> Option<&str> {
+        self.column()
+    }
+
+    fn schema(&self) -> Option<&str> {
+        self.schema()
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
         self
@@ -64,6 +64,8 @@ impl PgListener {
     /// Starts listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn listen(&mut self, channel: &str) -> Result<(), Error> {
+        self.connect_if_needed().await?;
+
         self.connection()
             .execute(&*format!(r#"LISTEN "{}""#, ident(channel)))
             .await?;
@@ -78,6 +80,8 @@ impl PgListener {
         &mut self,
         channels: impl IntoIterator<Item = &str>,
     ) -> Result<(), Error> {
+        self.connect_if_needed().await?;
+
         let beg = self.channels.len();
         self.channels.extend(channels.into_iter().map(|s| s.into()));
 
@@ -93,6 +97,8 @@ impl PgListener {
     /// Stops listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn unlisten(&mut self, channel: &str) -> Result<(), Error> {
+        self.connect_if_needed().await?;
+
         self.connection()
             .execute(&*format!(r#"UNLISTEN "{}""#, ident(channel)))
             .await?;
@@ -106,6 +112,8 @@ impl PgListener {
 
     /// Stops listening for notifications on all channels.
     pub async fn unlisten_all(&mut self) -> Result<(), Error> {
+        self.connect_if_needed().await?;
+
         self.connection().execute("UNLISTEN *").await?;
 
         self.channels.clear();
@@ -135,27 +143,44 @@ impl PgListener {
     }
 
     /// Receives the next notification available from any of the subscribed channels.
+    ///
+    /// If the connection to the server is lost, a new connection is made and all channels
+    /// are listened to again. Any notifications sent while there was no connection are lost; use
+    /// [`try_recv`](PgListener::try_recv) to be told when that happens.
     pub async fn recv(&mut self) -> Result<PgNotification, Error> {
+        loop {
+            if let Some(notification) = self.try_recv().await? {
+                return Ok(notification);
+            }
+        }
+    }
+
+    /// Receives the next notification available from any of the subscribed channels.
+    ///
+    /// Returns `Ok(None)` if the connection to the server was lost, in which case any
+    /// notifications sent until the next call (which reconnects and listens to all channels
+    /// again) are lost.
+    pub async fn try_recv(&mut self) -> Result<Option<PgNotification>, Error> {
         // Flush the buffer first, if anything
         // This would only fill up if this listener is used as a connection
         if let Ok(Some(notification)) = self.buffer_rx.try_next() {
-            return Ok(PgNotification(notification));
+            return Ok(Some(PgNotification(notification)));
         }
 
-        loop {
-            // Ensure we have an active connection to work with.
-            self.connect_if_needed().await?;
+        // Ensure we have an active connection to work with.
+        self.connect_if_needed().await?;
 
+        loop {
             let message = match self.connection().stream.recv_unchecked().await {
                 Ok(message) => message,
 
-                // The connection is dead, ensure that it is dropped,
-                // update self state, and loop to try again.
+                // The connection is dead, ensure that it is dropped and update self state
+                // so that we reconnect on the next call.
                 Err(Error::Io(err)) if err.kind() == io::ErrorKind::ConnectionAborted => {
                     self.buffer_tx = self.connection().stream.notifications.take();
                     self.connection = None;
 
-                    continue;
+                    return Ok(None);
                 }
 
                 // Forward other errors
@@ -167,7 +192,7 @@ impl PgListener {
             match message.format {
                 // We've received an async notification, return it.
                 MessageFormat::NotificationResponse => {
-                    return Ok(PgNotification(message.decode()?));
+                    return Ok(Some(PgNotification(message.decode()?)));
                 }
 
                 // Mark the connection as ready for another query
@@ -0,0 +1,99 @@
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::{BufMutExt, Decode, Encode};
+
+/// The format of the data in a `COPY` operation, as announced by the server.
+#[derive(Debug)]
+pub struct CopyResponse {
+    /// `0` indicates the overall `COPY` format is textual (rows separated by newlines,
+    /// columns separated by separator characters, etc.). `1` indicates the overall copy
+    /// format is binary (similar to `DataRow` format).
+    pub format: i8,
+
+    /// The format codes to be used for each column. Each must presently be zero (text) or
+    /// one (binary). All must be zero if the overall copy format is textual.
+    pub format_codes: Vec<i16>,
+}
+
+impl Decode<'_> for CopyResponse {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let format = buf.get_i8();
+        let num_columns = buf.get_i16();
+
+        let format_codes = (0..num_columns).map(|_| buf.get_i16()).collect();
+
+        Ok(CopyResponse {
+            format,
+            format_codes,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CopyData<B>(pub B);
+
+impl<B: AsRef<[u8]>> Encode<'_> for CopyData<B> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        let data = self.0.as_ref();
+
+        buf.reserve(1 + 4 + data.len());
+        buf.push(b'd');
+        buf.extend(&((4 + data.len()) as i32).to_be_bytes());
+        buf.extend(data);
+    }
+}
+
+#[derive(Debug)]
+pub struct CopyDone;
+
+impl Encode<'_> for CopyDone {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.push(b'c');
+        buf.extend(&4_i32.to_be_bytes());
+    }
+}
+
+#[derive(Debug)]
+pub struct CopyFail<'a>(pub &'a str);
+
+impl Encode<'_> for CopyFail<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        let len = 4 + self.0.len() + 1;
+
+        buf.reserve(len + 1);
+        buf.push(b'f');
+        buf.extend(&(len as i32).to_be_bytes());
+        buf.put_str_nul(self.0);
+    }
+}
+
+#[test]
+fn test_decode_copy_response() {
+    const DATA: &[u8] = b"\x01\x00\x02\x00\x01\x00\x01";
+
+    let m = CopyResponse::decode(Bytes::from_static(DATA)).unwrap();
+
+    assert_eq!(m.format, 1);
+    assert_eq!(m.format_codes, [1, 1]);
+}
+
+#[test]
+fn test_encode_copy_data() {
+    const EXPECTED: &[u8] = b"d\0\0\0\x071,2";
+
+    let mut buf = Vec::new();
+    CopyData("1,2").encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
+
+#[test]
+fn test_encode_copy_fail() {
+    const EXPECTED: &[u8] = b"f\0\0\0\x09oops\0";
+
+    let mut buf = Vec::new();
+    CopyFail("oops").encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
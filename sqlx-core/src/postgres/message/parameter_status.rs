@@ -0,0 +1,29 @@
+use bytes::Bytes;
+
+use crate::error::Error;
+use crate::io::{BufExt, Decode};
+
+#[derive(Debug)]
+pub struct ParameterStatus {
+    pub name: String,
+    pub value: String,
+}
+
+impl Decode<'_> for ParameterStatus {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let name = buf.get_str_nul()?;
+        let value = buf.get_str_nul()?;
+
+        Ok(Self { name, value })
+    }
+}
+
+#[test]
+fn test_decode_parameter_status() {
+    const DATA: &[u8] = b"server_version\x0013.4\x00";
+
+    let m = ParameterStatus::decode(DATA.into()).unwrap();
+
+    assert_eq!(&m.name, "server_version");
+    assert_eq!(&m.value, "13.4");
+}
@@ -5,7 +5,7 @@ use crate::postgres::io::PgBufMutExt;
 
 #[derive(Debug)]
 pub struct Parse<'a> {
-    /// The ID of the destination prepared statement.
+    /// The ID of the destination prepared statement (`0` selects the unnamed statement).
     pub statement: u32,
 
     /// The query string to be parsed.
@@ -3,6 +3,7 @@
 mod arguments;
 mod column;
 mod connection;
+mod copy;
 mod database;
 mod done;
 mod error;
@@ -21,7 +22,10 @@ mod migrate;
 
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
-pub use connection::PgConnection;
+pub use connection::{
+    PgCancelHandle, PgCapabilities, PgConnection, PgPipeline, PgPipelineError, PgPipelineResult,
+};
+pub use copy::{PgCopyIn, PgCopyRow, PgCopyValue};
 pub use database::Postgres;
 pub use done::PgDone;
 pub use error::{PgDatabaseError, PgErrorPosition};
@@ -1,5 +1,8 @@
 use std::env::var;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::logging::LogSettings;
 
 mod connect;
 mod parse;
@@ -25,6 +28,10 @@ pub use ssl_mode::PgSslMode;
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSqlSslMode`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `persistent` | `true` | Whether queries are executed as prepared statements that are kept on the server. See [`persistent`](#method.persistent). |
+/// | `application_name` | `None` | The name of the application, as shown in `pg_stat_activity`. |
+/// | `options` | `None` | Command-line options to send to the server at connection start, e.g. `-c search_path=myschema`. |
+/// | `search_path` | `None` | The schema search path of every session, e.g. `myschema,public`. Shorthand for the equivalent `options`. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 ///
 /// The URI scheme designator can be either `postgresql://` or `postgres://`.
@@ -77,6 +84,11 @@ pub struct PgConnectOptions {
     pub(crate) ssl_mode: PgSslMode,
     pub(crate) ssl_root_cert: Option<PathBuf>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) describe_cache: bool,
+    pub(crate) persistent: bool,
+    pub(crate) application_name: Option<String>,
+    pub(crate) options: Option<String>,
+    pub(crate) log_settings: LogSettings,
 }
 
 impl Default for PgConnectOptions {
@@ -98,6 +110,8 @@ impl PgConnectOptions {
     ///  * `PGDATABASE`
     ///  * `PGSSLROOTCERT`
     ///  * `PGSSLMODE`
+    ///  * `PGAPPNAME`
+    ///  * `PGOPTIONS`
     ///
     /// # Example
     ///
@@ -126,6 +140,11 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            describe_cache: false,
+            persistent: true,
+            application_name: var("PGAPPNAME").ok(),
+            options: var("PGOPTIONS").ok(),
+            log_settings: Default::default(),
         }
     }
 
@@ -264,12 +283,152 @@ impl PgConnectOptions {
     /// amount of queries hits the defined limit, the oldest statement will get
     /// dropped.
     ///
-    /// The default cache capacity is 100 statements.
+    /// The default cache capacity is 100 statements. A capacity of `0` disables the cache;
+    /// every statement is then prepared when it is executed and closed right after.
     pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
         self.statement_cache_capacity = capacity;
         self
     }
 
+    /// Sets whether the results of [`Executor::describe`] are cached by query string, so
+    /// describing the same query again does not need a round-trip to the server.
+    ///
+    /// The cache is disabled by default, as a cached description goes stale if the schema of
+    /// the query changes. Once enabled, it holds as many queries as the statement cache (see
+    /// [`statement_cache_capacity`]) and is cleared along with the statement cache by
+    /// [`Connection::clear_cached_statements`].
+    ///
+    /// [`Executor::describe`]: crate::executor::Executor::describe
+    /// [`Connection::clear_cached_statements`]: crate::connection::Connection::clear_cached_statements
+    /// [`statement_cache_capacity`]: #method.statement_cache_capacity
+    pub fn describe_cache(mut self, enabled: bool) -> Self {
+        self.describe_cache = enabled;
+        self
+    }
+
+    /// Sets whether queries are executed as prepared statements that are kept on the server.
+    ///
+    /// This is enabled by default. When disabled, no statement outlives the query that it is
+    /// executed for, which suits connection poolers that do not support prepared statements
+    /// (e.g., PgBouncer in transaction mode):
+    ///
+    ///  * A query without bound arguments is sent with the simple query protocol, avoiding
+    ///    `Parse`, `Describe` and `Bind` altogether. Its results are returned in the text format,
+    ///    which not every type can be decoded from.
+    ///  * A query with bound arguments still needs the extended protocol; it is prepared as a
+    ///    one-off statement that is closed right after it is executed, as with a
+    ///    [`statement_cache_capacity`] of `0`.
+    ///
+    /// This only affects how queries are executed at runtime. The query macros still prepare
+    /// and describe the queries against the development database at compile time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .persistent(false);
+    /// ```
+    ///
+    /// [`statement_cache_capacity`]: #method.statement_cache_capacity
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.persistent = persistent;
+        self
+    }
+
+    /// Sets the name of the application, as reported by the server (e.g., in
+    /// `pg_stat_activity` and the server log).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .application_name("my-app");
+    /// ```
+    pub fn application_name(mut self, application_name: &str) -> Self {
+        self.application_name = Some(application_name.to_owned());
+        self
+    }
+
+    /// Adds command-line options to send to the server at connection start, as with the
+    /// `options` parameter of libpq.
+    ///
+    /// Options are separated by whitespace; a space within a value must be escaped with a
+    /// backslash. Calling this again adds to the options that were already set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .options("-c statement_timeout=5000 -c geqo=off");
+    /// ```
+    pub fn options(mut self, options: &str) -> Self {
+        match &mut self.options {
+            Some(existing) => {
+                existing.push(' ');
+                existing.push_str(options);
+            }
+
+            None => self.options = Some(options.to_owned()),
+        }
+
+        self
+    }
+
+    /// Sets the schema search path of the session when connecting, saving a `SET search_path`
+    /// after every connect.
+    ///
+    /// This is sent as a `-c search_path=..` option; see [`options`]. As the search path is
+    /// part of the startup message, every connection opened by a pool built from these options
+    /// starts in the configured schema, including connections opened to replace ones that were
+    /// closed. This is the recommended way to give a pool a default (e.g., tenant-specific)
+    /// schema; a [`PoolOptions::after_connect`] callback running `SET search_path` works as
+    /// well, but costs an extra round-trip per connection.
+    ///
+    /// Prepared statements are cached per connection and resolve table names when they are
+    /// first prepared. If the search path of a connection is changed after statements have
+    /// been cached (e.g., with `SET search_path` after acquiring it from the pool), those
+    /// statements may keep referring to the tables of the previous schema or fail with
+    /// `cached plan must not change result type`; prefer a separate pool per schema, or
+    /// disable the cache with [`statement_cache_capacity`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .search_path("myschema, public");
+    /// ```
+    ///
+    /// [`options`]: #method.options
+    /// [`statement_cache_capacity`]: #method.statement_cache_capacity
+    /// [`PoolOptions::after_connect`]: crate::pool::PoolOptions::after_connect
+    pub fn search_path(self, search_path: &str) -> Self {
+        let mut option = String::from("-c search_path=");
+
+        for c in search_path.chars() {
+            if c == '\\' || c.is_whitespace() {
+                option.push('\\');
+            }
+
+            option.push(c);
+        }
+
+        self.options(&option)
+    }
+
+    /// Sets the execution time at or above which a statement is reported as slow.
+    ///
+    /// When the `tracing` feature is enabled, every statement executed on the connection
+    /// is recorded in a `sqlx::query` span and statements that run at least this long
+    /// additionally emit an event at the `WARN` level. The default threshold is 1 second.
+    pub fn slow_statement_threshold(mut self, threshold: Duration) -> Self {
+        self.log_settings.slow_statement_threshold = threshold;
+        self
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
@@ -48,6 +48,22 @@ impl FromStr for PgConnectOptions {
                         options.statement_cache_capacity(value.parse().map_err(Error::config)?);
                 }
 
+                "persistent" => {
+                    options = options.persistent(value.parse().map_err(Error::config)?);
+                }
+
+                "application_name" => {
+                    options = options.application_name(&*value);
+                }
+
+                "options" => {
+                    options = options.options(&*value);
+                }
+
+                "search_path" => {
+                    options = options.search_path(&*value);
+                }
+
                 "host" => {
                     if value.starts_with("/") {
                         options = options.socket(&*value);
@@ -80,3 +96,59 @@ fn it_parses_host_correctly_from_parameter() {
     assert_eq!(None, opts.socket);
     assert_eq!("google.database.com", &opts.host);
 }
+
+#[test]
+fn it_parses_ssl_mode_correctly() {
+    for mode in &[
+        "disable",
+        "allow",
+        "prefer",
+        "require",
+        "verify-ca",
+        "verify-full",
+    ] {
+        let uri = format!("postgres:///?sslmode={}&sslrootcert=ca.crt", mode);
+        let opts = PgConnectOptions::from_str(&uri).unwrap();
+
+        assert_eq!(*mode, opts.ssl_mode.as_str());
+        assert_eq!(Some("ca.crt".into()), opts.ssl_root_cert);
+    }
+
+    assert!(PgConnectOptions::from_str("postgres:///?sslmode=verify").is_err());
+}
+
+#[test]
+fn it_parses_startup_parameters_correctly() {
+    let uri = "postgres:///?options=-csearch_path%3Dmyschema&application_name=myapp";
+    let opts = PgConnectOptions::from_str(uri).unwrap();
+
+    assert_eq!(Some("-csearch_path=myschema"), opts.options.as_deref());
+    assert_eq!(Some("myapp"), opts.application_name.as_deref());
+
+    let opts = PgConnectOptions::from_str("postgres:///?search_path=tenant_1,public").unwrap();
+
+    assert_eq!(
+        Some("-c search_path=tenant_1,public"),
+        opts.options.as_deref()
+    );
+
+    let opts = PgConnectOptions::from_str(uri)
+        .unwrap()
+        .search_path("my schema, public");
+
+    assert_eq!(
+        Some(r"-csearch_path=myschema -c search_path=my\ schema,\ public"),
+        opts.options.as_deref()
+    );
+}
+
+#[test]
+fn it_parses_persistent_correctly() {
+    let opts = PgConnectOptions::from_str("postgres:///").unwrap();
+    assert!(opts.persistent);
+
+    let opts = PgConnectOptions::from_str("postgres:///?persistent=false").unwrap();
+    assert!(!opts.persistent);
+
+    assert!(PgConnectOptions::from_str("postgres:///?persistent=no").is_err());
+}
@@ -34,6 +34,20 @@ impl Default for PgSslMode {
     }
 }
 
+impl PgSslMode {
+    /// The value of `sslmode` for this mode, as understood by libpq.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            PgSslMode::Disable => "disable",
+            PgSslMode::Allow => "allow",
+            PgSslMode::Prefer => "prefer",
+            PgSslMode::Require => "require",
+            PgSslMode::VerifyCa => "verify-ca",
+            PgSslMode::VerifyFull => "verify-full",
+        }
+    }
+}
+
 impl FromStr for PgSslMode {
     type Err = Error;
 
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
@@ -6,7 +8,7 @@ use crate::postgres::message::Query;
 use crate::postgres::{PgConnection, Postgres};
 use crate::transaction::{
     begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    TransactionManager, TransactionOptions,
 };
 
 /// Implementation of [`TransactionManager`] for PostgreSQL.
@@ -15,10 +17,20 @@ pub struct PgTransactionManager;
 impl TransactionManager for PgTransactionManager {
     type Database = Postgres;
 
-    fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
+    fn begin_with(
+        conn: &mut PgConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            conn.execute(&*begin_ansi_transaction_sql(conn.transaction_depth))
-                .await?;
+            options.validate(conn.transaction_depth)?;
+
+            let query = if options.is_default() {
+                begin_ansi_transaction_sql(conn.transaction_depth)
+            } else {
+                Cow::Owned(begin_transaction_sql(&options))
+            };
+
+            conn.execute(&*query).await?;
 
             conn.transaction_depth += 1;
 
@@ -63,3 +75,45 @@ impl TransactionManager for PgTransactionManager {
         }
     }
 }
+
+// BEGIN [ ISOLATION LEVEL { ... } ] [ READ WRITE | READ ONLY ] [ [ NOT ] DEFERRABLE ]
+fn begin_transaction_sql(options: &TransactionOptions) -> String {
+    let mut query = String::from("BEGIN");
+
+    if let Some(level) = options.isolation_level {
+        query.push_str(" ISOLATION LEVEL ");
+        query.push_str(level.as_sql());
+    }
+
+    if options.read_only {
+        query.push_str(" READ ONLY");
+    }
+
+    if options.deferrable {
+        query.push_str(" DEFERRABLE");
+    }
+
+    query
+}
+
+#[test]
+fn test_begin_transaction_sql() {
+    use crate::transaction::IsolationLevel;
+
+    let options = TransactionOptions::new()
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(true)
+        .deferrable(true);
+
+    assert_eq!(
+        begin_transaction_sql(&options),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+    );
+
+    let options = TransactionOptions::new().isolation_level(IsolationLevel::ReadCommitted);
+
+    assert_eq!(
+        begin_transaction_sql(&options),
+        "BEGIN ISOLATION LEVEL READ COMMITTED"
+    );
+}
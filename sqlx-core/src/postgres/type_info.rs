@@ -198,6 +198,17 @@ impl PgTypeInfo {
         .contains(self)
         {
             Some("ipnetwork")
+        } else if [PgTypeInfo::MACADDR, PgTypeInfo::MACADDR_ARRAY].contains(self) {
+            Some("mac_address")
+        } else if [
+            PgTypeInfo::BIT,
+            PgTypeInfo::VARBIT,
+            PgTypeInfo::BIT_ARRAY,
+            PgTypeInfo::VARBIT_ARRAY,
+        ]
+        .contains(self)
+        {
+            Some("bit-vec")
         } else if [PgTypeInfo::NUMERIC, PgTypeInfo::NUMERIC_ARRAY].contains(self) {
             Some("bigdecimal")
         } else {
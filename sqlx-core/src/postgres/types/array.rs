@@ -3,10 +3,37 @@ use bytes::Buf;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::type_info::PgType;
+use crate::postgres::type_info::{PgType, PgTypeKind};
 use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use crate::types::Type;
 
+/// A user-defined type that has an array type in Postgres, allowing `Vec<T>` and `&[T]` to be
+/// used for arrays of it.
+///
+/// This is implemented by `#[derive(sqlx::Type)]` for composite types, whose array type is
+/// named after them with a leading underscore (e.g. `_address` for `address`).
+pub trait PgHasArrayType {
+    fn array_type_info() -> PgTypeInfo;
+}
+
+impl<T> Type<Postgres> for [T]
+where
+    T: PgHasArrayType + Type<Postgres>,
+{
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+}
+
+impl<T> Type<Postgres> for Vec<T>
+where
+    T: PgHasArrayType + Type<Postgres>,
+{
+    fn type_info() -> PgTypeInfo {
+        <[T] as Type<Postgres>>::type_info()
+    }
+}
+
 impl<T> Type<Postgres> for [Option<T>]
 where
     [T]: Type<Postgres>,
@@ -80,7 +107,7 @@ where
     Self: Type<Postgres>,
 {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        let element_type_info = T::type_info();
+        let element_type_info = element_type_info::<T>(&value.type_info);
         let format = value.format();
 
         match format {
@@ -137,6 +164,15 @@ where
 
                 // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
 
+                if s.starts_with('[') {
+                    // arrays not starting from "1" are prefixed with their bounds, `[0:1]={..}`
+                    return Err(format!("encountered an array with explicit bounds ({:?}); only arrays starting at one are supported", s).into());
+                }
+
+                if s.len() < 2 || !s.starts_with('{') || !s.ends_with('}') {
+                    return Err(format!("invalid array literal: {:?}", s).into());
+                }
+
                 // trim the wrapping braces
                 let s = &s[1..(s.len() - 1)];
 
@@ -145,6 +181,10 @@ where
                     return Ok(Vec::new());
                 }
 
+                if s.starts_with('{') {
+                    return Err("encountered a multi-dimensional array; only one-dimensional arrays are supported".into());
+                }
+
                 // NOTE: Nearly *all* types use ',' as the sequence delimiter. Yes, there is one
                 //       that does not. The BOX (not PostGIS) type uses ';' as a delimiter.
 
@@ -155,6 +195,7 @@ where
                 let mut done = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
+                let mut quoted = false;
                 let mut value = String::with_capacity(10);
                 let mut chars = s.chars();
                 let mut elements = Vec::with_capacity(4);
@@ -170,6 +211,7 @@ where
 
                                 '"' => {
                                     in_quotes = !in_quotes;
+                                    quoted = true;
                                 }
 
                                 '\\' => {
@@ -192,7 +234,8 @@ where
                         }
                     }
 
-                    let value_opt = if value == "NULL" {
+                    // a quoted "NULL" is the string 'NULL' and not a NULL element
+                    let value_opt = if value == "NULL" && !quoted {
                         None
                     } else {
                         Some(value.as_bytes())
@@ -206,6 +249,7 @@ where
                     })?);
 
                     value.clear();
+                    quoted = false;
                 }
 
                 Ok(elements)
@@ -213,3 +257,65 @@ where
         }
     }
 }
+
+// the type of the elements of an array; for an array of a custom type, this is the type resolved
+// by the connection, which composite types need to decode their fields
+fn element_type_info<T: Type<Postgres>>(array: &PgTypeInfo) -> PgTypeInfo {
+    match &array.0 {
+        PgType::DeclareWithOid(_) | PgType::DeclareWithName(_) => T::type_info(),
+
+        ty => match ty.kind() {
+            PgTypeKind::Array(element) => element.clone(),
+            _ => T::type_info(),
+        },
+    }
+}
+
+#[cfg(test)]
+fn text_value(s: &str, type_info: PgTypeInfo) -> PgValueRef<'_> {
+    PgValueRef {
+        value: Some(s.as_bytes()),
+        row: None,
+        type_info,
+        format: PgValueFormat::Text,
+    }
+}
+
+#[test]
+fn test_decode_text_array_with_nulls() {
+    let v = Vec::<Option<i32>>::decode(text_value("{1,NULL,3}", PgTypeInfo::INT4_ARRAY)).unwrap();
+    assert_eq!(v, [Some(1), None, Some(3)]);
+
+    let v =
+        Vec::<Option<String>>::decode(text_value(r#"{NULL,"NULL","a,b"}"#, PgTypeInfo::TEXT_ARRAY))
+            .unwrap();
+    assert_eq!(v, [None, Some("NULL".to_owned()), Some("a,b".to_owned())]);
+
+    let v = Vec::<Option<i32>>::decode(text_value("{}", PgTypeInfo::INT4_ARRAY)).unwrap();
+    assert!(v.is_empty());
+
+    assert!(Vec::<i32>::decode(text_value("{1,NULL}", PgTypeInfo::INT4_ARRAY)).is_err());
+}
+
+#[test]
+fn test_decode_text_array_errors_on_unsupported_shapes() {
+    for s in &["{{1,2},{3,4}}", "[0:1]={1,2}", ""] {
+        assert!(Vec::<Option<i32>>::decode(text_value(s, PgTypeInfo::INT4_ARRAY)).is_err());
+    }
+}
+
+#[test]
+fn test_decode_binary_array_with_nulls() {
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode(&[Some(1_i32), None][..], &mut buf);
+
+    let v = Vec::<Option<i32>>::decode(PgValueRef {
+        value: Some(&buf),
+        row: None,
+        type_info: PgTypeInfo::INT4_ARRAY,
+        format: PgValueFormat::Binary,
+    })
+    .unwrap();
+
+    assert_eq!(v, [Some(1), None]);
+}
@@ -0,0 +1,117 @@
+use std::convert::TryFrom;
+
+use bit_vec::BitVec;
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::Type;
+
+impl Type<Postgres> for BitVec {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::VARBIT
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::BIT || *ty == PgTypeInfo::VARBIT
+    }
+}
+
+impl Type<Postgres> for [BitVec] {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::VARBIT_ARRAY
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::BIT_ARRAY || *ty == PgTypeInfo::VARBIT_ARRAY
+    }
+}
+
+impl Type<Postgres> for Vec<BitVec> {
+    fn type_info() -> PgTypeInfo {
+        <[BitVec] as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <[BitVec] as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for BitVec {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // the number of bits, followed by the bits packed into bytes (most significant first);
+        // any unused bits of the final byte are zero
+        buf.extend(&(self.len() as i32).to_be_bytes());
+        buf.extend(self.to_bytes());
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + (self.len() + 7) / 8
+    }
+}
+
+impl Decode<'_, Postgres> for BitVec {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+
+                if bytes.len() < 4 {
+                    return Err("invalid VARBIT: missing the number of bits".into());
+                }
+
+                let len = usize::try_from(BigEndian::read_i32(bytes))?;
+                let bytes = &bytes[4..];
+
+                if bytes.len() != (len + 7) / 8 {
+                    return Err(format!(
+                        "invalid VARBIT: expected {} bytes for {} bits but received {}",
+                        (len + 7) / 8,
+                        len,
+                        bytes.len()
+                    )
+                    .into());
+                }
+
+                // drop the padding of the final byte to preserve the exact number of bits
+                let mut bits = BitVec::from_bytes(bytes);
+                bits.truncate(len);
+
+                Ok(bits)
+            }
+
+            PgValueFormat::Text => {
+                let s = value.as_str()?;
+                let mut bits = BitVec::with_capacity(s.len());
+
+                for c in s.chars() {
+                    match c {
+                        '0' => bits.push(false),
+                        '1' => bits.push(true),
+                        _ => {
+                            return Err(format!("invalid VARBIT: unexpected {:?}", c).into());
+                        }
+                    }
+                }
+
+                Ok(bits)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_encode_bit_vec() {
+    let mut bits = BitVec::from_bytes(&[0b1010_0000]);
+    bits.truncate(3);
+
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode_by_ref(&bits, &mut buf);
+
+    assert_eq!(&buf[..], &[0, 0, 0, 3, 0b1010_0000][..]);
+    assert_eq!(buf.len(), Encode::<Postgres>::size_hint(&bits));
+}
@@ -1,5 +1,5 @@
 use num_bigint::{BigInt, Sign};
-use num_traits::ToPrimitive;
+use num_traits::{pow, ToPrimitive};
 use rust_decimal::{prelude::Zero, Decimal};
 use std::convert::{TryFrom, TryInto};
 
@@ -10,6 +10,12 @@ use crate::postgres::types::numeric::{PgNumeric, PgNumericSign};
 use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use crate::types::Type;
 
+// the largest number of digits after the decimal point a `Decimal` can hold
+const MAX_SCALE: u32 = 28;
+
+// the largest mantissa a `Decimal` can hold (96 bits)
+const MAX_MANTISSA: i128 = (1 << 96) - 1;
+
 impl Type<Postgres> for Decimal {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::NUMERIC
@@ -32,22 +38,26 @@ impl TryFrom<PgNumeric> for Decimal {
     type Error = BoxDynError;
 
     fn try_from(numeric: PgNumeric) -> Result<Self, BoxDynError> {
-        let (digits, sign, weight) = match numeric {
+        let (digits, sign, weight, dscale) = match numeric {
             PgNumeric::Number {
                 digits,
                 sign,
                 weight,
-                ..
-            } => (digits, sign, weight),
+                scale,
+            } => (digits, sign, weight, scale),
 
             PgNumeric::NotANumber => {
                 return Err("Decimal does not support NaN values".into());
             }
         };
 
+        // the display scale is the number of digits after the decimal point, e.g. 2 for `12.30`;
+        // digits past the largest scale of `Decimal` are rounded off, as when parsing one
+        let dscale = i64::from(dscale.max(0)).min(i64::from(MAX_SCALE));
+
         if digits.is_empty() {
             // Postgres returns an empty digit array for 0 but BigInt expects at least one zero
-            return Ok(0u64.into());
+            return Ok(Decimal::from_i128_with_scale(0, dscale as u32));
         }
 
         let sign = match sign {
@@ -68,17 +78,34 @@ impl TryFrom<PgNumeric> for Decimal {
         let bigint = BigInt::from_radix_be(sign, &cents, 100)
             .ok_or("PgNumeric contained an out-of-range digit")?;
 
-        match (bigint.to_i128(), scale) {
-            // A negative scale, meaning we have nothing on the right and must
-            // add zeroes to the left.
-            (Some(num), scale) if scale < 0 => Ok(Decimal::from_i128_with_scale(
-                num * 10i128.pow(scale.abs() as u32),
-                0,
-            )),
-            // A positive scale, so we have decimals on the right.
-            (Some(num), _) => Ok(Decimal::from_i128_with_scale(num, scale as u32)),
-            (None, _) => Err("Decimal's integer part out of range.".into()),
-        }
+        // the digit groups are padded to a multiple of 4 decimal digits (or, for a negative
+        // scale, leave out the trailing zeroes of the integer part); shift the digits so that
+        // the scale of the decoded value is the display scale, which preserves e.g. `12.30`
+        let bigint = if scale > dscale {
+            let ten = BigInt::from(10);
+            let truncated = bigint / pow(ten.clone(), (scale - dscale - 1) as usize);
+
+            // round half away from zero on the last digit dropped
+            let last = (&truncated % &ten).to_i8().unwrap_or(0);
+            let mut rounded = truncated / ten;
+
+            if last >= 5 {
+                rounded += 1;
+            } else if last <= -5 {
+                rounded -= 1;
+            }
+
+            rounded
+        } else {
+            bigint * pow(BigInt::from(10), (dscale - scale) as usize)
+        };
+
+        let num = bigint
+            .to_i128()
+            .filter(|num| *num <= MAX_MANTISSA && *num >= -MAX_MANTISSA)
+            .ok_or("Decimal's integer part out of range.")?;
+
+        Ok(Decimal::from_i128_with_scale(num, dscale as u32))
     }
 }
 
@@ -89,7 +116,7 @@ impl TryFrom<&'_ Decimal> for PgNumeric {
         if decimal.is_zero() {
             return Ok(PgNumeric::Number {
                 sign: PgNumericSign::Positive,
-                scale: 0,
+                scale: decimal.scale() as i16,
                 weight: 0,
                 digits: vec![],
             });
@@ -408,3 +435,122 @@ mod decimal_to_pgnumeric {
         );
     }
 }
+
+#[cfg(test)]
+mod pgnumeric_to_decimal {
+    use super::{Decimal, PgNumeric, PgNumericSign};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn preserves_scale() {
+        // 12.30
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 2,
+            weight: 0,
+            digits: vec![12, 3000],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "12.30");
+        assert_eq!(decimal.scale(), 2);
+
+        // -10000.0
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Negative,
+            scale: 1,
+            weight: 1,
+            digits: vec![1],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "-10000.0");
+
+        // 0.000
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 3,
+            weight: 0,
+            digits: vec![],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "0.000");
+    }
+
+    #[test]
+    fn round_trips() {
+        for s in &[
+            "0.00",
+            "1.50",
+            "-12345.67890",
+            "0.00001234",
+            "79228162514264337593543950335",
+        ] {
+            let decimal: Decimal = s.parse().unwrap();
+            let numeric = PgNumeric::try_from(&decimal).unwrap();
+
+            assert_eq!(Decimal::try_from(numeric).unwrap().to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn rejects_nan_and_out_of_range() {
+        assert!(Decimal::try_from(PgNumeric::NotANumber).is_err());
+
+        // 1e32
+        assert!(Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 0,
+            weight: 8,
+            digits: vec![1],
+        })
+        .is_err());
+
+        // 1e32 with a scale too large for `Decimal`
+        assert!(Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 30,
+            weight: 8,
+            digits: vec![1],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn rounds_scale_too_large() {
+        // 1.500000000000000000000000000000, e.g. `1.5::NUMERIC(40, 30)`
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 30,
+            weight: 0,
+            digits: vec![1, 5000],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "1.5000000000000000000000000000");
+        assert_eq!(decimal.scale(), 28);
+
+        // -0.000000000000000000000000000150
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Negative,
+            scale: 30,
+            weight: -7,
+            digits: vec![1, 5000],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "-0.0000000000000000000000000002");
+
+        // 0.000000000000000000000000000049
+        let decimal = Decimal::try_from(PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            scale: 30,
+            weight: -8,
+            digits: vec![4900],
+        })
+        .unwrap();
+
+        assert_eq!(decimal.to_string(), "0.0000000000000000000000000000");
+    }
+}
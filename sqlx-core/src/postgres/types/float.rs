@@ -3,6 +3,8 @@ use byteorder::{BigEndian, ByteOrder};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+#[cfg(feature = "numeric-lossy")]
+use crate::postgres::types::numeric::PgNumeric;
 use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use crate::types::Type;
 
@@ -45,6 +47,10 @@ impl Type<Postgres> for f64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::FLOAT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::FLOAT8 || (cfg!(feature = "numeric-lossy") && *ty == PgTypeInfo::NUMERIC)
+    }
 }
 
 impl Type<Postgres> for [f64] {
@@ -69,6 +75,14 @@ impl Encode<'_, Postgres> for f64 {
 
 impl Decode<'_, Postgres> for f64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        #[cfg(feature = "numeric-lossy")]
+        if value.type_info == PgTypeInfo::NUMERIC {
+            return Ok(match value.format() {
+                PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.to_f64(),
+                PgValueFormat::Text => value.as_str()?.parse()?,
+            });
+        }
+
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_f64(value.as_bytes()?),
             PgValueFormat::Text => value.as_str()?.parse()?,
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::Type;
+
+// `hstore` is provided by an extension and so does not have a fixed OID; it is looked up by name
+// when first used on a connection. A `NULL` value in the map is distinct from an empty string.
+
+// https://github.com/postgres/postgres/blob/master/contrib/hstore/hstore_io.c
+
+impl Type<Postgres> for HashMap<String, Option<String>> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("hstore")
+    }
+}
+
+impl Encode<'_, Postgres> for HashMap<String, Option<String>> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // number of pairs
+        buf.extend(&(self.len() as i32).to_be_bytes());
+
+        for (key, value) in self {
+            buf.extend(&(key.len() as i32).to_be_bytes());
+            buf.extend(key.as_bytes());
+
+            match value {
+                Some(value) => {
+                    buf.extend(&(value.len() as i32).to_be_bytes());
+                    buf.extend(value.as_bytes());
+                }
+
+                // a length of -1 marks a NULL value
+                None => {
+                    buf.extend(&(-1_i32).to_be_bytes());
+                }
+            }
+        }
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        self.iter().fold(4, |size, (key, value)| {
+            size + 8 + key.len() + value.as_ref().map_or(0, String::len)
+        })
+    }
+}
+
+impl Decode<'_, Postgres> for HashMap<String, Option<String>> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => decode_binary(value.as_bytes()?),
+            PgValueFormat::Text => decode_text(value.as_str()?),
+        }
+    }
+}
+
+fn decode_binary(mut buf: &[u8]) -> Result<HashMap<String, Option<String>>, BoxDynError> {
+    let count = read_len(&mut buf)?.ok_or("invalid hstore: negative number of pairs")?;
+    let mut map = HashMap::with_capacity(count);
+
+    for _ in 0..count {
+        let key = read_str(&mut buf)?.ok_or("invalid hstore: NULL key")?;
+        let value = read_str(&mut buf)?;
+
+        map.insert(key, value);
+    }
+
+    if !buf.is_empty() {
+        return Err("invalid hstore: unexpected trailing data".into());
+    }
+
+    Ok(map)
+}
+
+// reads an `int4` length; a negative length is returned as `None`
+fn read_len(buf: &mut &[u8]) -> Result<Option<usize>, BoxDynError> {
+    if buf.len() < 4 {
+        return Err("invalid hstore: unexpected end of data".into());
+    }
+
+    let len = BigEndian::read_i32(buf);
+    *buf = &buf[4..];
+
+    Ok(usize::try_from(len).ok())
+}
+
+fn read_str(buf: &mut &[u8]) -> Result<Option<String>, BoxDynError> {
+    let len = match read_len(buf)? {
+        Some(len) => len,
+        None => return Ok(None),
+    };
+
+    if buf.len() < len {
+        return Err("invalid hstore: unexpected end of data".into());
+    }
+
+    let s = std::str::from_utf8(&buf[..len])?.to_owned();
+    *buf = &buf[len..];
+
+    Ok(Some(s))
+}
+
+// parses the text format, e.g. `"a"=>"1", "b"=>NULL`
+fn decode_text(s: &str) -> Result<HashMap<String, Option<String>>, BoxDynError> {
+    let mut map = HashMap::new();
+    let mut chars = s.chars().peekable();
+
+    loop {
+        skip_whitespace(&mut chars);
+
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let (key, _) = read_token(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+
+        if chars.next() != Some('=') || chars.next() != Some('>') {
+            return Err(format!("invalid hstore: expected `=>` in {:?}", s).into());
+        }
+
+        skip_whitespace(&mut chars);
+
+        let value = match read_token(&mut chars)? {
+            // an unquoted NULL is a NULL value; `"NULL"` is the string
+            (value, false) if value.eq_ignore_ascii_case("NULL") => None,
+            (value, _) => Some(value),
+        };
+
+        map.insert(key, value);
+
+        skip_whitespace(&mut chars);
+
+        match chars.next() {
+            Some(',') => {}
+            None => break,
+            Some(c) => {
+                return Err(format!("invalid hstore: unexpected {:?} in {:?}", c, s).into());
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+// reads a key or value, returning whether it was quoted
+fn read_token(chars: &mut Peekable<Chars<'_>>) -> Result<(String, bool), BoxDynError> {
+    let mut token = String::new();
+
+    if chars.peek() == Some(&'"') {
+        chars.next();
+
+        loop {
+            match chars.next() {
+                Some('"') => return Ok((token, true)),
+                Some('\\') => token.push(chars.next().ok_or("invalid hstore: unexpected end")?),
+                Some(c) => token.push(c),
+                None => return Err("invalid hstore: unterminated string".into()),
+            }
+        }
+    }
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == ',' || c == '=' {
+            break;
+        }
+
+        if c == '\\' {
+            chars.next();
+            token.push(chars.next().ok_or("invalid hstore: unexpected end")?);
+        } else {
+            token.push(c);
+            chars.next();
+        }
+    }
+
+    if token.is_empty() {
+        return Err("invalid hstore: expected a key or value".into());
+    }
+
+    Ok((token, false))
+}
+
+#[cfg(test)]
+fn hstore(pairs: &[(&str, Option<&str>)]) -> HashMap<String, Option<String>> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.map(str::to_owned)))
+        .collect()
+}
+
+#[test]
+fn test_decode_text_hstore() {
+    assert_eq!(decode_text("").unwrap(), hstore(&[]));
+
+    assert_eq!(
+        decode_text(r#""a"=>"1", "b"=>NULL, "c"=>"", "d"=>"NULL""#).unwrap(),
+        hstore(&[
+            ("a", Some("1")),
+            ("b", None),
+            ("c", Some("")),
+            ("d", Some("NULL"))
+        ])
+    );
+
+    assert_eq!(
+        decode_text(r#""quote\"d"=>"back\\slash", key => value"#).unwrap(),
+        hstore(&[("quote\"d", Some("back\\slash")), ("key", Some("value"))])
+    );
+
+    assert!(decode_text(r#""a"=>"#).is_err());
+    assert!(decode_text(r#""a" "1""#).is_err());
+}
+
+#[test]
+fn test_encode_decode_binary_hstore() {
+    let map = hstore(&[("a", Some("1")), ("b", None), ("c", Some(""))]);
+
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode_by_ref(&map, &mut buf);
+
+    assert_eq!(buf.len(), Encode::<Postgres>::size_hint(&map));
+    assert_eq!(decode_binary(&buf).unwrap(), map);
+
+    // a NULL value is encoded with a length of -1, an empty string with a length of 0
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode_by_ref(&hstore(&[("b", None)]), &mut buf);
+
+    assert_eq!(
+        &buf[..],
+        &[0, 0, 0, 1, 0, 0, 0, 1, b'b', 255, 255, 255, 255][..]
+    );
+
+    assert!(decode_binary(&[0, 0, 0, 1, 0, 0, 0, 5, b'a']).is_err());
+}
@@ -3,6 +3,8 @@ use byteorder::{BigEndian, ByteOrder};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+#[cfg(feature = "numeric-lossy")]
+use crate::postgres::types::numeric::{self, PgNumeric};
 use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use crate::types::Type;
 
@@ -148,6 +150,10 @@ impl Type<Postgres> for i64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::INT8 || (cfg!(feature = "numeric-lossy") && *ty == PgTypeInfo::NUMERIC)
+    }
 }
 
 impl Type<Postgres> for [i64] {
@@ -172,6 +178,14 @@ impl Encode<'_, Postgres> for i64 {
 
 impl Decode<'_, Postgres> for i64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        #[cfg(feature = "numeric-lossy")]
+        if value.type_info == PgTypeInfo::NUMERIC {
+            return match value.format() {
+                PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.to_i64(),
+                PgValueFormat::Text => numeric::parse_i64(value.as_str()?),
+            };
+        }
+
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_i64(value.as_bytes()?),
             PgValueFormat::Text => value.as_str()?.parse()?,
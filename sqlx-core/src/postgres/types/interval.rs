@@ -95,6 +95,44 @@ impl Encode<'_, Postgres> for std::time::Duration {
     }
 }
 
+impl<'de> Decode<'de, Postgres> for std::time::Duration {
+    fn decode(value: PgValueRef<'de>) -> Result<Self, BoxDynError> {
+        PgInterval::decode(value)?.try_into()
+    }
+}
+
+impl TryFrom<PgInterval> for std::time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `std::time::Duration`.
+    ///
+    /// Days are taken to be exactly 24 hours. This returns an error if the interval has a
+    /// month (or year) component, as a month is not a fixed length of time, or if the
+    /// interval is negative.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        if value.months != 0 {
+            return Err(
+                "PostgreSQL `INTERVAL` with months or years cannot be converted to `std::time::Duration`"
+                    .into(),
+            );
+        }
+
+        let microseconds = i64::from(value.days)
+            .checked_mul(86_400_000_000)
+            .and_then(|days| days.checked_add(value.microseconds))
+            .ok_or("PostgreSQL `INTERVAL` overflows `std::time::Duration`")?;
+
+        if microseconds < 0 {
+            return Err(
+                "negative PostgreSQL `INTERVAL` cannot be converted to `std::time::Duration`"
+                    .into(),
+            );
+        }
+
+        Ok(std::time::Duration::from_micros(microseconds as u64))
+    }
+}
+
 impl TryFrom<std::time::Duration> for PgInterval {
     type Error = BoxDynError;
 
@@ -294,6 +332,41 @@ fn test_pginterval_std() {
     );
 }
 
+#[test]
+fn test_std_duration_from_pginterval() {
+    use std::time::Duration;
+
+    let interval = PgInterval {
+        months: 0,
+        days: 2,
+        microseconds: 27_000,
+    };
+    assert_eq!(
+        Duration::try_from(interval).unwrap(),
+        Duration::from_secs(2 * 86_400) + Duration::from_micros(27_000)
+    );
+
+    let interval = PgInterval {
+        months: 1,
+        days: 0,
+        microseconds: 0,
+    };
+    assert!(Duration::try_from(interval).is_err());
+
+    let interval = PgInterval {
+        months: 0,
+        days: -1,
+        microseconds: 0,
+    };
+    assert!(Duration::try_from(interval).is_err());
+
+    let duration = Duration::from_micros(3_600_000_001);
+    assert_eq!(
+        Duration::try_from(PgInterval::try_from(duration).unwrap()).unwrap(),
+        duration
+    );
+}
+
 #[test]
 #[cfg(feature = "chrono")]
 fn test_pginterval_chrono() {
@@ -0,0 +1,132 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use crate::types::Type;
+
+// A plain IP address is an INET (or CIDR) with a host prefix (`/32` or `/128`); decoding a value
+// that refers to a network is an error. Use `IpNetwork` to keep the prefix.
+
+impl Type<Postgres> for IpAddr {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INET
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <IpNetwork as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Type<Postgres> for [IpAddr] {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INET_ARRAY
+    }
+}
+
+impl Type<Postgres> for Vec<IpAddr> {
+    fn type_info() -> PgTypeInfo {
+        <[IpAddr] as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <[IpAddr] as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for IpAddr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        IpNetwork::from(*self).encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        IpNetwork::from(*self).size_hint()
+    }
+}
+
+impl Decode<'_, Postgres> for IpAddr {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let network = IpNetwork::decode(value)?;
+
+        let is_host = match network {
+            IpNetwork::V4(net) => net.prefix() == 32,
+            IpNetwork::V6(net) => net.prefix() == 128,
+        };
+
+        if !is_host {
+            return Err(format!(
+                "expected a host address but received the network {}; \
+                 decode as `IpNetwork` instead",
+                network
+            )
+            .into());
+        }
+
+        Ok(network.ip())
+    }
+}
+
+impl Type<Postgres> for Ipv4Addr {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INET
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <IpNetwork as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for Ipv4Addr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        IpNetwork::V4(Ipv4Network::from(*self)).encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<'_, Postgres> for Ipv4Addr {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match IpAddr::decode(value)? {
+            IpAddr::V4(addr) => Ok(addr),
+            IpAddr::V6(addr) => {
+                Err(format!("expected an IPv4 address but received {}", addr).into())
+            }
+        }
+    }
+}
+
+impl Type<Postgres> for Ipv6Addr {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INET
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <IpNetwork as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for Ipv6Addr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        IpNetwork::V6(Ipv6Network::from(*self)).encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        20
+    }
+}
+
+impl Decode<'_, Postgres> for Ipv6Addr {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match IpAddr::decode(value)? {
+            IpAddr::V6(addr) => Ok(addr),
+            IpAddr::V4(addr) => {
+                Err(format!("expected an IPv6 address but received {}", addr).into())
+            }
+        }
+    }
+}
@@ -62,11 +62,13 @@ where
         let mut buf = value.as_bytes()?;
 
         if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
-            assert_eq!(
-                buf[0], 1,
-                "unsupported JSONB format version {}; please open an issue",
-                buf[0]
-            );
+            if buf.first() != Some(&1) {
+                return Err(format!(
+                    "unsupported JSONB format version {:?}; please open an issue",
+                    buf.first()
+                )
+                .into());
+            }
 
             buf = &buf[1..];
         }
@@ -0,0 +1,61 @@
+use mac_address::MacAddress;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::Type;
+
+impl Type<Postgres> for MacAddress {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::MACADDR
+    }
+}
+
+impl Type<Postgres> for [MacAddress] {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::MACADDR_ARRAY
+    }
+}
+
+impl Type<Postgres> for Vec<MacAddress> {
+    fn type_info() -> PgTypeInfo {
+        <[MacAddress] as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for MacAddress {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend_from_slice(&self.bytes());
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        6
+    }
+}
+
+impl Decode<'_, Postgres> for MacAddress {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = match value.format() {
+            PgValueFormat::Binary => value.as_bytes()?,
+            PgValueFormat::Text => {
+                return Ok(value.as_str()?.parse()?);
+            }
+        };
+
+        // a MACADDR8 is 8 bytes and can't be represented
+        if bytes.len() != 6 {
+            return Err(format!(
+                "invalid MACADDR: expected 6 bytes but received {}",
+                bytes.len()
+            )
+            .into());
+        }
+
+        Ok(MacAddress::new([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5],
+        ]))
+    }
+}
@@ -13,6 +13,7 @@
 //! | `&str`, `String`                      | VARCHAR, CHAR(N), TEXT, NAME                         |
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | [`PgInterval`]                        | INTERVAL                                             |
+//! | `std::time::Duration`                 | INTERVAL                                             |
 //! | [`PgRange<T>`]                        | INT8RANGE, INT4RANGE, TSRANGE, TSTZTRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
 //!
@@ -34,6 +35,19 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `rust_decimal::Decimal`               | NUMERIC                                              |
 //!
+//! ### Lossy `NUMERIC`
+//!
+//! Requires the `numeric-lossy` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `f64`                                 | NUMERIC                                              |
+//! | `i64`                                 | NUMERIC                                              |
+//!
+//! For reports and aggregates where the exact value is not needed; only decoding is supported.
+//! Decoding into `f64` rounds away digits beyond its precision. Decoding into `i64` fails if the
+//! value has a fractional part or does not fit.
+//!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
 //! Requires the `chrono` Cargo feature flag.
@@ -72,6 +86,42 @@
 //! | Rust type                             | Postgres type(s)                                     |
 //! |---------------------------------------|------------------------------------------------------|
 //! | `ipnetwork::IpNetwork`                | INET, CIDR                                           |
+//! | `std::net::IpAddr`                    | INET, CIDR                                           |
+//! | `std::net::Ipv4Addr`                  | INET, CIDR                                           |
+//! | `std::net::Ipv6Addr`                  | INET, CIDR                                           |
+//!
+//! The `std::net` types are host addresses; decoding a value with a network prefix
+//! (e.g. `'10.0.0.0/8'::cidr`) into one of them is an error.
+//!
+//! ### [`mac_address`](https://crates.io/crates/mac_address)
+//!
+//! Requires the `mac_address` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `mac_address::MacAddress`             | MACADDR                                              |
+//!
+//! `MacAddress` holds 6 bytes, so decoding a `MACADDR8` into it is an error; cast the value
+//! with `::macaddr` first if it was converted from a 6-byte address.
+//!
+//! ### [`bit-vec`](https://crates.io/crates/bit-vec)
+//!
+//! Requires the `bit-vec` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
+//!
+//! ### [`hstore`](https://www.postgresql.org/docs/current/hstore.html)
+//!
+//! Requires the `hstore` Cargo feature flag and the `hstore` extension to be installed in the
+//! database.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `HashMap<String, Option<String>>`     | HSTORE                                               |
+//!
+//! A `NULL` value in the map is decoded as `None`, distinct from an empty string.
 //!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
@@ -104,7 +154,7 @@
 //!
 //! ```rust,ignore
 //! #[derive(sqlx::Type)]
-//! #[sqlx(rename = "inventory_item")]
+//! #[sqlx(type_name = "inventory_item")]
 //! struct InventoryItem {
 //!     name: String,
 //!     supplier_id: i32,
@@ -112,6 +162,9 @@
 //! }
 //! ```
 //!
+//! Fields that may be `NULL` should be declared as `Option<T>`, and a field may itself be another
+//! composite type. `#[sqlx(rename = "..")]` is accepted as well as `type_name`.
+//!
 //! Anonymous composite types are represented as tuples. Note that anonymous composites may only
 //! be returned and not sent to Postgres (this is a limitation of postgres).
 //!
@@ -119,6 +172,9 @@
 //!
 //! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
 //!
+//! This includes arrays of user-defined composite types (e.g. `address[]` as `Vec<Address>`),
+//! which may contain `NULL` elements when decoded as `Vec<Option<Address>>`.
+//!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
 //! User-defined enumerations are supported through a derive for `Type`.
@@ -129,10 +185,14 @@
 //!
 //! ```rust,ignore
 //! #[derive(sqlx::Type)]
-//! #[sqlx(rename = "mood", rename_all = "lowercase")]
+//! #[sqlx(type_name = "mood", rename_all = "lowercase")]
 //! enum Mood { Sad, Ok, Happy }
 //! ```
 //!
+//! The labels are matched against the variant names (after `rename_all`), or against
+//! `#[sqlx(rename = "..")]` given on a variant. Such an enum can also be decoded from a `TEXT` or
+//! `VARCHAR` column, for databases that store enumerations as strings.
+//!
 //! Rust enumerations may also be defined to be represented as an integer using `repr`.
 //! The following type expects a SQL type of `INTEGER` or `INT4` and will convert to/from the
 //! Rust enumeration.
@@ -169,7 +229,7 @@ mod void;
 #[cfg(feature = "bigdecimal")]
 mod bigdecimal;
 
-#[cfg(any(feature = "bigdecimal", feature = "decimal"))]
+#[cfg(any(feature = "bigdecimal", feature = "decimal", feature = "numeric-lossy"))]
 mod numeric;
 
 #[cfg(feature = "decimal")]
@@ -190,6 +250,19 @@ mod json;
 #[cfg(feature = "ipnetwork")]
 mod ipnetwork;
 
+#[cfg(feature = "ipnetwork")]
+mod ipaddr;
+
+#[cfg(feature = "mac_address")]
+mod mac_address;
+
+#[cfg(feature = "hstore")]
+mod hstore;
+
+#[cfg(feature = "bit-vec")]
+mod bit_vec;
+
+pub use array::PgHasArrayType;
 pub use interval::PgInterval;
 pub use money::PgMoney;
 pub use range::PgRange;
@@ -18,8 +18,15 @@ use std::{
 /// Data is read and written as 64-bit signed integers, and conversion into a
 /// decimal should be done using the right precision.
 ///
-/// Reading `MONEY` value in text format is not supported and will cause an error.
+/// The value is always in minor units (e.g. cents), which is only meaningful together with the
+/// `lc_monetary` of the database it was read from; changing `lc_monetary` changes what the
+/// stored integers mean.
 ///
+/// In the text format, used by queries without arguments when [`persistent`] is disabled, the
+/// value is formatted for `lc_monetary` (e.g. `$1,234.56`). It is decoded by reading its digits,
+/// which works as long as the currency symbol itself contains none.
+///
+/// [`persistent`]: crate::postgres::PgConnectOptions::persistent
 /// [`MONEY`]: https://www.postgresql.org/docs/current/datatype-money.html
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct PgMoney(pub i64);
@@ -106,16 +113,42 @@ impl Decode<'_, Postgres> for PgMoney {
 
                 Ok(PgMoney(cents))
             }
-            PgValueFormat::Text => {
-                let error = io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "Reading a `MONEY` value in text format is not supported.",
-                );
+            PgValueFormat::Text => Ok(PgMoney(decode_text(value.as_str()?)?)),
+        }
+    }
+}
 
-                Err(Box::new(error))
-            }
+// the text format depends on `lc_monetary`, e.g. `$1,234.56`, `-$1,234.56` or `1.234,56 €`,
+// but always prints every fractional digit so the digits alone are the amount in minor units
+fn decode_text(s: &str) -> Result<i64, BoxDynError> {
+    let negative = s.contains('-') || s.contains('(');
+    let mut cents: Option<i64> = None;
+
+    for digit in s.bytes().filter(u8::is_ascii_digit) {
+        let digit = i64::from(digit - b'0');
+        let shifted = cents.unwrap_or(0).checked_mul(10);
+
+        cents = if negative {
+            shifted.and_then(|cents| cents.checked_sub(digit))
+        } else {
+            shifted.and_then(|cents| cents.checked_add(digit))
+        };
+
+        if cents.is_none() {
+            return Err(Box::new(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`MONEY` value {:?} is out of range", s),
+            )));
         }
     }
+
+    cents.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("`MONEY` value {:?} has no digits", s),
+        )
+        .into()
+    })
 }
 
 impl Add<PgMoney> for PgMoney {
@@ -176,7 +209,23 @@ impl SubAssign<PgMoney> for PgMoney {
 
 #[cfg(test)]
 mod tests {
-    use super::PgMoney;
+    use super::{decode_text, PgMoney};
+
+    #[test]
+    fn decoding_text_works() {
+        assert_eq!(decode_text("$1,234.56").unwrap(), 123456);
+        assert_eq!(decode_text("-$1,234.56").unwrap(), -123456);
+        assert_eq!(decode_text("($0.05)").unwrap(), -5);
+        assert_eq!(decode_text("1.234,56 €").unwrap(), 123456);
+        assert_eq!(decode_text("¥1,234").unwrap(), 1234);
+        assert_eq!(
+            decode_text("-$92,233,720,368,547,758.08").unwrap(),
+            i64::MIN
+        );
+
+        assert!(decode_text("$92,233,720,368,547,758.08").is_err());
+        assert!(decode_text("$").is_err());
+    }
 
     #[test]
     fn adding_works() {
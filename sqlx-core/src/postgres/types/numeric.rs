@@ -3,6 +3,7 @@ use std::convert::TryInto;
 use bytes::Buf;
 
 use crate::error::BoxDynError;
+#[cfg(any(feature = "bigdecimal", feature = "decimal"))]
 use crate::postgres::PgArgumentBuffer;
 
 /// Represents a `NUMERIC` value in the **Postgres** wire protocol.
@@ -102,6 +103,7 @@ impl PgNumeric {
     ///
     /// * If `digits.len()` overflows `i16`
     /// * If any element in `digits` is greater than or equal to 10000
+    #[cfg(any(feature = "bigdecimal", feature = "decimal"))]
     pub(crate) fn encode(&self, buf: &mut PgArgumentBuffer) {
         match *self {
             PgNumeric::Number {
@@ -136,3 +138,178 @@ impl PgNumeric {
         }
     }
 }
+
+// lossy conversions to primitives, for decoding a `NUMERIC` into `f64` or `i64`
+#[cfg(feature = "numeric-lossy")]
+impl PgNumeric {
+    /// Converts to the nearest `f64`.
+    ///
+    /// Digits beyond the precision of an `f64` are rounded away, and a value too large for an
+    /// `f64` becomes an infinity.
+    pub(crate) fn to_f64(&self) -> f64 {
+        match self {
+            PgNumeric::NotANumber => f64::NAN,
+
+            PgNumeric::Number {
+                sign,
+                digits,
+                weight,
+                ..
+            } => {
+                if digits.is_empty() {
+                    return 0.0;
+                }
+
+                // write out the base-10000 digits in scientific notation and let the standard
+                // library parse it, which rounds correctly
+                let mut s = String::with_capacity(digits.len() * 4 + 8);
+
+                if *sign == PgNumericSign::Negative {
+                    s.push('-');
+                }
+
+                for digit in digits {
+                    s.push_str(&format!("{:04}", digit));
+                }
+
+                let exponent = (i32::from(*weight) - (digits.len() as i32 - 1)) * 4;
+                s.push_str(&format!("e{}", exponent));
+
+                s.parse().unwrap_or(f64::NAN)
+            }
+        }
+    }
+
+    /// Converts to an `i64`, failing if the value has a fractional part or does not fit.
+    pub(crate) fn to_i64(&self) -> Result<i64, BoxDynError> {
+        let (sign, digits, weight) = match self {
+            PgNumeric::NotANumber => {
+                return Err("cannot decode NUMERIC 'NaN' as an integer".into());
+            }
+
+            PgNumeric::Number {
+                sign,
+                digits,
+                weight,
+                ..
+            } => (*sign, digits, i32::from(*weight)),
+        };
+
+        // the digits at positions `0..=weight` are before the decimal point; missing digits are
+        // trailing zeroes
+        let integral = (weight + 1).max(0) as usize;
+
+        if digits.iter().skip(integral).any(|&digit| digit != 0) {
+            return Err("cannot decode NUMERIC with a fractional part as an integer".into());
+        }
+
+        let mut value: i128 = 0;
+
+        for i in 0..integral {
+            let digit = digits.get(i).copied().unwrap_or(0);
+
+            value = value * 10_000 + i128::from(digit);
+
+            if value > i128::from(u64::MAX) {
+                return Err("NUMERIC value out of range for i64".into());
+            }
+        }
+
+        if sign == PgNumericSign::Negative {
+            value = -value;
+        }
+
+        value
+            .try_into()
+            .map_err(|_| "NUMERIC value out of range for i64".into())
+    }
+}
+
+/// Parses the text form of a `NUMERIC` value as an `i64`, with the same rules as
+/// [`PgNumeric::to_i64`].
+#[cfg(feature = "numeric-lossy")]
+pub(crate) fn parse_i64(s: &str) -> Result<i64, BoxDynError> {
+    if s == "NaN" {
+        return Err("cannot decode NUMERIC 'NaN' as an integer".into());
+    }
+
+    let (integral, fractional) = match s.find('.') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    };
+
+    if fractional.bytes().any(|b| b != b'0') {
+        return Err("cannot decode NUMERIC with a fractional part as an integer".into());
+    }
+
+    integral
+        .parse()
+        .map_err(|_| format!("NUMERIC value out of range for i64: {}", s).into())
+}
+
+#[cfg(all(test, feature = "numeric-lossy"))]
+mod lossy {
+    use super::{parse_i64, PgNumeric, PgNumericSign};
+
+    fn number(sign: PgNumericSign, digits: &[i16], weight: i16) -> PgNumeric {
+        PgNumeric::Number {
+            sign,
+            digits: digits.to_vec(),
+            weight,
+            scale: 0,
+        }
+    }
+
+    #[test]
+    fn it_converts_to_f64() {
+        // 12345.678
+        let n = number(PgNumericSign::Positive, &[1, 2345, 6780], 1);
+        assert_eq!(n.to_f64(), 12345.678);
+
+        // -0.0001
+        let n = number(PgNumericSign::Negative, &[1], -1);
+        assert_eq!(n.to_f64(), -0.0001);
+
+        // 10000 ^ 3
+        let n = number(PgNumericSign::Positive, &[1], 3);
+        assert_eq!(n.to_f64(), 1e12);
+
+        assert_eq!(number(PgNumericSign::Positive, &[], 0).to_f64(), 0.0);
+        assert!(PgNumeric::NotANumber.to_f64().is_nan());
+    }
+
+    #[test]
+    fn it_converts_to_i64() {
+        // 12345
+        let n = number(PgNumericSign::Positive, &[1, 2345], 1);
+        assert_eq!(n.to_i64().unwrap(), 12345);
+
+        // -200000000, with the trailing zero digits left out
+        let n = number(PgNumericSign::Negative, &[2], 2);
+        assert_eq!(n.to_i64().unwrap(), -200_000_000);
+
+        // i64::MIN = -9223372036854775808
+        let n = number(PgNumericSign::Negative, &[922, 3372, 368, 5477, 5808], 4);
+        assert_eq!(n.to_i64().unwrap(), i64::MIN);
+
+        // i64::MAX + 1
+        let n = number(PgNumericSign::Positive, &[922, 3372, 368, 5477, 5808], 4);
+        assert!(n.to_i64().is_err());
+
+        // 1.5
+        let n = number(PgNumericSign::Positive, &[1, 5000], 0);
+        assert!(n.to_i64().is_err());
+
+        assert_eq!(number(PgNumericSign::Positive, &[], 0).to_i64().unwrap(), 0);
+        assert!(PgNumeric::NotANumber.to_i64().is_err());
+    }
+
+    #[test]
+    fn it_parses_i64() {
+        assert_eq!(parse_i64("-42").unwrap(), -42);
+        assert_eq!(parse_i64("42.000").unwrap(), 42);
+        assert!(parse_i64("42.5").is_err());
+        assert!(parse_i64("9223372036854775808").is_err());
+        assert!(parse_i64("NaN").is_err());
+    }
+}
@@ -25,12 +25,66 @@ bitflags! {
   }
 }
 
+/// A PostgreSQL [range] such as `INT4RANGE` or `TSTZRANGE`, as a pair of bounds.
+///
+/// A range can be created with [`new`], [`inclusive`], [`exclusive`] or [`unbounded`], or
+/// converted from a Rust range (e.g. `PgRange::from(1..10)` is `[1,10)`).
+///
+/// The element type `T` may be any type that has a corresponding range type in Postgres:
+/// `i32`, `i64`, and, with the respective features, `BigDecimal`, `Decimal` and the
+/// date and time types of `chrono` and `time`.
+///
+/// Note that Postgres normalizes discrete ranges, so `[1,2]` of `INT4RANGE` is returned as
+/// `[1,3)`. An empty range is decoded with both bounds unbounded.
+///
+/// [range]: https://www.postgresql.org/docs/current/rangetypes.html
+/// [`new`]: #method.new
+/// [`inclusive`]: #method.inclusive
+/// [`exclusive`]: #method.exclusive
+/// [`unbounded`]: #method.unbounded
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PgRange<T> {
     pub start: Bound<T>,
     pub end: Bound<T>,
 }
 
+impl<T> PgRange<T> {
+    /// Creates a range from its bounds.
+    pub fn new(start: Bound<T>, end: Bound<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the range `[start,end]`, including both ends.
+    pub fn inclusive(start: T, end: T) -> Self {
+        Self::new(Bound::Included(start), Bound::Included(end))
+    }
+
+    /// Creates the range `(start,end)`, excluding both ends.
+    pub fn exclusive(start: T, end: T) -> Self {
+        Self::new(Bound::Excluded(start), Bound::Excluded(end))
+    }
+
+    /// Creates the range `(,)`, without a bound at either end.
+    pub fn unbounded() -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// Returns the lower bound of the range.
+    pub fn start(&self) -> Bound<&T> {
+        self.start_bound()
+    }
+
+    /// Returns the upper bound of the range.
+    pub fn end(&self) -> Bound<&T> {
+        self.end_bound()
+    }
+
+    /// Consumes the range, returning its lower and upper bounds.
+    pub fn into_bounds(self) -> (Bound<T>, Bound<T>) {
+        (self.start, self.end)
+    }
+}
+
 impl<T> From<[Bound<T>; 2]> for PgRange<T> {
     fn from(v: [Bound<T>; 2]) -> Self {
         let [start, end] = v;
@@ -142,6 +196,17 @@ impl Type<Postgres> for PgRange<bigdecimal::BigDecimal> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl Type<Postgres> for PgRange<rust_decimal::Decimal> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        range_compatible::<rust_decimal::Decimal>(ty)
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl Type<Postgres> for PgRange<chrono::NaiveDate> {
     fn type_info() -> PgTypeInfo {
@@ -227,6 +292,13 @@ impl Type<Postgres> for [PgRange<bigdecimal::BigDecimal>] {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl Type<Postgres> for [PgRange<rust_decimal::Decimal>] {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_RANGE_ARRAY
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl Type<Postgres> for [PgRange<chrono::NaiveDate>] {
     fn type_info() -> PgTypeInfo {
@@ -288,6 +360,13 @@ impl Type<Postgres> for Vec<PgRange<bigdecimal::BigDecimal>> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl Type<Postgres> for Vec<PgRange<rust_decimal::Decimal>> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_RANGE_ARRAY
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl Type<Postgres> for Vec<PgRange<chrono::NaiveDate>> {
     fn type_info() -> PgTypeInfo {
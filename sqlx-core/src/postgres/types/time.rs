@@ -1,4 +1,4 @@
-use time::{date, offset, Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+use time::{date, offset, Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
@@ -104,22 +104,7 @@ impl<'r> Decode<'r, Postgres> for Time {
                 Time::midnight() + Duration::microseconds(us)
             }
 
-            PgValueFormat::Text => {
-                // If there are less than 9 digits after the decimal point
-                // We need to zero-pad
-
-                // FIXME: Ask [time] to add a parse % for less-than-fixed-9 nanos
-
-                let s = value.as_str()?;
-
-                let s = if s.len() < 20 {
-                    Cow::Owned(format!("{:0<19}", s))
-                } else {
-                    Cow::Borrowed(s)
-                };
-
-                Time::parse(&*s, "%H:%M:%S.%N")?
-            }
+            PgValueFormat::Text => Time::parse(&*pad_nanos(value.as_str()?), "%H:%M:%S.%N")?,
         })
     }
 }
@@ -172,35 +157,7 @@ impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
             }
 
             PgValueFormat::Text => {
-                // If there are less than 9 digits after the decimal point
-                // We need to zero-pad
-
-                // TODO: De-duplicate with MySQL
-                // TODO: Ask [time] to add a parse % for less-than-fixed-9 nanos
-
-                let s = value.as_str()?;
-
-                let s = if let Some(plus) = s.rfind('+') {
-                    let mut big = String::from(&s[..plus]);
-
-                    while big.len() < 31 {
-                        big.push('0');
-                    }
-
-                    big.push_str(&s[plus..]);
-
-                    Cow::Owned(big)
-                } else if s.len() < 31 {
-                    if s.contains('.') {
-                        Cow::Owned(format!("{:0<30}", s))
-                    } else {
-                        Cow::Owned(format!("{}.000000000", s))
-                    }
-                } else {
-                    Cow::Borrowed(s)
-                };
-
-                PrimitiveDateTime::parse(&*s, "%Y-%m-%d %H:%M:%S.%N")?
+                PrimitiveDateTime::parse(&*pad_nanos(value.as_str()?), "%Y-%m-%d %H:%M:%S.%N")?
             }
         })
     }
@@ -221,6 +178,98 @@ impl Encode<'_, Postgres> for OffsetDateTime {
 
 impl<'r> Decode<'r, Postgres> for OffsetDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(<PrimitiveDateTime as Decode<Postgres>>::decode(value)?.assume_utc())
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                <PrimitiveDateTime as Decode<Postgres>>::decode(value)?.assume_utc()
+            }
+
+            PgValueFormat::Text => {
+                // TIMESTAMPTZ is printed in the time zone of the session, followed by its offset
+                // from UTC (e.g. `2019-01-02 05:10:20.1151+05:30`)
+                let s = value.as_str()?;
+                let (s, offset) = split_offset(s)?;
+
+                PrimitiveDateTime::parse(&*pad_nanos(s), "%Y-%m-%d %H:%M:%S.%N")?
+                    .assume_offset(offset)
+                    .to_offset(offset!(UTC))
+            }
+        })
     }
 }
+
+// Postgres prints up to 6 digits after the decimal point, and leaves out the fraction entirely
+// when it is zero, but `time` expects exactly 9 digits
+// TODO: Ask [time] to add a parse % for less-than-fixed-9 nanos
+fn pad_nanos(s: &str) -> Cow<'_, str> {
+    match s.find('.') {
+        Some(dot) if s.len() - dot - 1 < 9 => Cow::Owned(format!("{:0<1$}", s, dot + 10)),
+        Some(_) => Cow::Borrowed(s),
+        None => Cow::Owned(format!("{}.000000000", s)),
+    }
+}
+
+// splits a trailing UTC offset of the form `+HH`, `+HH:MM` or `+HH:MM:SS` from a timestamp
+fn split_offset(s: &str) -> Result<(&str, UtcOffset), BoxDynError> {
+    // the sign comes after the time; a `-` before that is part of the date
+    let pos = match s.rfind(|c| c == '+' || c == '-') {
+        Some(pos) if pos > s.find(' ').unwrap_or(s.len()) => pos,
+        _ => return Ok((s, offset!(UTC))),
+    };
+
+    let (timestamp, offset) = s.split_at(pos);
+    let mut seconds = 0;
+
+    for (part, unit) in offset[1..].split(':').zip(&[3600, 60, 1]) {
+        seconds += part.parse::<i32>()? * unit;
+    }
+
+    if offset.starts_with('-') {
+        seconds = -seconds;
+    }
+
+    Ok((timestamp, UtcOffset::seconds(seconds)))
+}
+
+#[cfg(test)]
+fn text_value(s: &str, type_info: PgTypeInfo) -> PgValueRef<'_> {
+    PgValueRef {
+        value: Some(s.as_bytes()),
+        row: None,
+        type_info,
+        format: PgValueFormat::Text,
+    }
+}
+
+#[test]
+fn test_decode_text_time() {
+    use time::time;
+
+    let decode = |s| <Time as Decode<Postgres>>::decode(text_value(s, PgTypeInfo::TIME)).unwrap();
+
+    assert_eq!(decode("05:10:20"), time!(5:10:20));
+    assert_eq!(decode("05:10:20.1151"), time!(5:10:20.115100));
+}
+
+#[test]
+fn test_decode_text_timestamptz() {
+    use time::time;
+
+    let decode = |s| {
+        <OffsetDateTime as Decode<Postgres>>::decode(text_value(s, PgTypeInfo::TIMESTAMPTZ))
+            .unwrap()
+    };
+
+    let expected = date!(2019 - 1 - 2).with_time(time!(5:10:20)).assume_utc();
+
+    assert_eq!(decode("2019-01-02 05:10:20+00"), expected);
+    assert_eq!(decode("2019-01-02 10:40:20+05:30"), expected);
+    assert_eq!(decode("2019-01-02 00:10:20-05"), expected);
+    assert_eq!(decode("2019-01-02 00:10:20-05").offset(), offset!(UTC));
+
+    assert_eq!(
+        decode("2019-01-02 05:10:20.1151+00"),
+        date!(2019 - 1 - 2)
+            .with_time(time!(5:10:20.115100))
+            .assume_utc()
+    );
+}
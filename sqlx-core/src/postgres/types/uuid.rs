@@ -41,3 +41,38 @@ impl Decode<'_, Postgres> for Uuid {
         .map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+const UUID: &str = "b731678f-636f-4135-bc6f-19440c13bd19";
+
+#[cfg(test)]
+fn value(bytes: &[u8], format: PgValueFormat) -> PgValueRef<'_> {
+    PgValueRef {
+        value: Some(bytes),
+        row: None,
+        type_info: PgTypeInfo::UUID,
+        format,
+    }
+}
+
+#[test]
+fn test_decode_uuid_binary() {
+    let expected = Uuid::parse_str(UUID).unwrap();
+
+    let v = Uuid::decode(value(expected.as_bytes(), PgValueFormat::Binary)).unwrap();
+    assert_eq!(v, expected);
+
+    assert!(Uuid::decode(value(&expected.as_bytes()[..15], PgValueFormat::Binary)).is_err());
+}
+
+#[test]
+fn test_decode_uuid_text() {
+    let expected = Uuid::parse_str(UUID).unwrap();
+
+    let v = Uuid::decode(value(UUID.as_bytes(), PgValueFormat::Text)).unwrap();
+    assert_eq!(v, expected);
+
+    // the text form is never mistaken for the 16-byte binary form, and vice versa
+    assert!(Uuid::decode(value(UUID.as_bytes(), PgValueFormat::Binary)).is_err());
+    assert!(Uuid::decode(value(expected.as_bytes(), PgValueFormat::Text)).is_err());
+}
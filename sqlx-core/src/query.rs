@@ -1,4 +1,6 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -9,6 +11,7 @@ use crate::database::{Database, HasArguments};
 use crate::encode::Encode;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::statement::PreparedId;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters. Returned by [`query`][crate::query::query].
@@ -17,6 +20,8 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) query: &'q str,
     pub(crate) arguments: Option<A>,
     pub(crate) database: PhantomData<DB>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) prepared: Option<Arc<PreparedId>>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -47,6 +52,16 @@ where
     fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
         self.arguments.take().map(IntoArguments::into_arguments)
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    #[inline]
+    fn prepared(&self) -> Option<&Arc<PreparedId>> {
+        self.prepared.as_ref()
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
@@ -58,6 +73,23 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
     ///
     /// There is no validation that the value is of the type expected by the query. Most SQL
     /// flavors will perform type coercion (Postgres will return a database error).
+    ///
+    /// Borrowed values such as `&str` and `&[u8]` (or an `Option` of either) can be bound
+    /// without first copying them into a `String` or `Vec<u8>`. The borrow must live for `'q`,
+    /// that is, for as long as the query itself, so the data has to outlive the execution of
+    /// the query:
+    ///
+    /// ```rust,ignore
+    /// let payload: Vec<u8> = read_payload();
+    ///
+    /// sqlx::query("INSERT INTO blobs (data) VALUES (?)")
+    ///     .bind(&payload[..])
+    ///     .execute(&mut conn)
+    ///     .await?;
+    /// ```
+    ///
+    /// SQLite passes the borrowed data to the statement as it is. The other drivers encode it
+    /// directly into the buffer that is sent to the database, without an intermediate copy.
     pub fn bind<T: 'q + Send + Encode<'q, DB> + Type<DB>>(mut self, value: T) -> Self {
         if let Some(arguments) = &mut self.arguments {
             arguments.add(value);
@@ -72,6 +104,27 @@ where
     DB: Database,
     A: 'q + IntoArguments<'q, DB>,
 {
+    /// Limit the amount of time this query may run for.
+    ///
+    /// On PostgreSQL, `statement_timeout` is set for this query only (and restored afterwards),
+    /// so the server itself cancels the query once the timeout elapses and a database error
+    /// with the code `57014` (`query_canceled`) is returned. To do this, the query is run within
+    /// a transaction, so commands that cannot run inside a transaction block (such as `VACUUM`)
+    /// will fail.
+    ///
+    /// On SQLite, the running statement is interrupted once the timeout elapses and
+    /// [`Error::QueryTimedOut`] is returned.
+    ///
+    /// On MySQL, the query is cancelled with `KILL QUERY` from a second connection once the
+    /// timeout elapses, and on MSSQL by sending an attention signal. [`Error::QueryTimedOut`] is
+    /// returned once the server has stopped the query and the connection is ready for the next.
+    ///
+    /// The timeout covers the whole execution, including receiving every row of the result.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Map each row in the result to another type.
     ///
     /// See [`try_map`](Query::try_map) for a fallible version of this method.
@@ -115,7 +168,7 @@ where
 
     /// Execute multiple queries and return the rows affected from each query, in a stream.
     #[inline]
-    pub async fn execute_many<'e, 'c: 'e, E>(
+    pub fn execute_many<'e, 'c: 'e, E>(
         self,
         executor: E,
     ) -> BoxStream<'e, Result<DB::Done, Error>>
@@ -138,6 +191,24 @@ where
         executor.fetch(self)
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of up to
+    /// `chunk_size` rows.
+    ///
+    /// See [`Executor::fetch_chunked`] for details.
+    #[inline]
+    pub fn fetch_chunked<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<DB::Row>, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.fetch_chunked(self, chunk_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[inline]
@@ -201,6 +272,16 @@ where
     fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
         self.inner.take_arguments()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+
+    #[inline]
+    fn prepared(&self) -> Option<&Arc<PreparedId>> {
+        self.inner.prepared()
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -210,6 +291,14 @@ where
     O: Send + Unpin,
     A: 'q + Send + IntoArguments<'q, DB>,
 {
+    /// Limit the amount of time this query may run for.
+    ///
+    /// See [`Query::timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner.timeout = Some(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -350,6 +439,8 @@ where
         database: PhantomData,
         arguments: Some(Default::default()),
         query: sql,
+        timeout: None,
+        prepared: None,
     }
 }
 
@@ -364,6 +455,8 @@ where
         database: PhantomData,
         arguments: Some(arguments),
         query: sql,
+        timeout: None,
+        prepared: None,
     }
 }
 
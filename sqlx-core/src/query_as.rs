@@ -1,4 +1,6 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -11,6 +13,7 @@ use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
 use crate::query::{query, query_with, Query};
+use crate::statement::PreparedId;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`].
@@ -35,6 +38,16 @@ where
     fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
         self.inner.take_arguments()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+
+    #[inline]
+    fn prepared(&self) -> Option<&Arc<PreparedId>> {
+        self.inner.prepared()
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -55,6 +68,14 @@ where
     A: 'q + IntoArguments<'q, DB>,
     O: Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Limit the amount of time this query may run for.
+    ///
+    /// See [`Query::timeout`](crate::query::Query::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner.timeout = Some(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -157,6 +178,10 @@ where
 
 /// Make a SQL query, with the given arguments, that is mapped to a concrete type
 /// using [`FromRow`](crate::row::FromRow).
+///
+/// This is the counterpart of [`query_with`] for when the arguments are built up
+/// dynamically, e.g. with [`Arguments::add`](crate::arguments::Arguments::add), rather
+/// than bound one by one.
 #[inline]
 pub fn query_as_with<'q, DB, O, A>(sql: &'q str, arguments: A) -> QueryAs<'q, DB, O, A>
 where
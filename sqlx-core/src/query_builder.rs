@@ -0,0 +1,372 @@
+//! Runtime query-builder API.
+
+use std::fmt::{self, Display, Write};
+use std::marker::PhantomData;
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasArguments};
+use crate::encode::Encode;
+use crate::query::Query;
+use crate::types::Type;
+
+/// A builder type for constructing queries at runtime.
+///
+/// Bind parameters are pushed with [`push_bind`](QueryBuilder::push_bind) which writes the
+/// correct placeholder for the database (e.g., `?` for MySQL and SQLite, `$N` for Postgres)
+/// and keeps track of the argument buffer, so the SQL and the arguments always line up.
+///
+/// ```rust,ignore
+/// let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE id IN (");
+///
+/// let mut separated = builder.separated(", ");
+/// for id in &ids {
+///     separated.push_bind(*id);
+/// }
+///
+/// builder.push(")");
+///
+/// let users = builder.build().fetch_all(&pool).await?;
+/// ```
+///
+/// Note that the [`Any`](crate::any::Any) driver does not know which database it will be
+/// connected to when the query is built; start the builder with
+/// [`QueryBuilder::with_kind`] instead of [`QueryBuilder::new`], which always writes `?`.
+pub struct QueryBuilder<'args, DB>
+where
+    DB: Database,
+{
+    query: String,
+    init_len: usize,
+    arguments: Option<<DB as HasArguments<'args>>::Arguments>,
+
+    // writes the placeholder of the argument at the given index in place of the arguments
+    // themselves, for drivers whose arguments do not know the placeholders of the database
+    format_placeholder: Option<fn(&mut String, usize) -> fmt::Result>,
+}
+
+impl<'args, DB> QueryBuilder<'args, DB>
+where
+    DB: Database,
+{
+    /// Start building a query with an initial SQL fragment, which may be an empty string.
+    pub fn new(init: impl Into<String>) -> Self {
+        let init = init.into();
+
+        QueryBuilder {
+            init_len: init.len(),
+            query: init,
+            arguments: Some(Default::default()),
+            format_placeholder: None,
+        }
+    }
+
+    #[inline]
+    fn sanity_check(&self) {
+        assert!(
+            self.arguments.is_some(),
+            "QueryBuilder must be reset before reuse after `.build()`"
+        );
+    }
+
+    /// Append a SQL fragment to the query.
+    ///
+    /// The fragment is pushed as-is; it is **not** escaped and must never contain
+    /// untrusted input. Use [`push_bind`](QueryBuilder::push_bind) for values.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.sanity_check();
+
+        write!(self.query, "{}", sql).expect("error formatting `sql`");
+
+        self
+    }
+
+    /// Push a bind parameter placeholder and bind `value` to it.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        self.sanity_check();
+
+        let arguments = self
+            .arguments
+            .as_mut()
+            .expect("BUG: Arguments taken already");
+
+        match self.format_placeholder {
+            Some(format_placeholder) => format_placeholder(&mut self.query, arguments.len()),
+            None => arguments.format_placeholder(&mut self.query),
+        }
+        .expect("error in format_placeholder");
+
+        arguments.add(value);
+
+        self
+    }
+
+    /// Start a list of SQL fragments and bind parameters separated by `separator`.
+    ///
+    /// The separator is written before every pushed item except the first.
+    pub fn separated<'qb, Sep>(&'qb mut self, separator: Sep) -> Separated<'qb, 'args, DB, Sep>
+    where
+        Sep: Display,
+    {
+        self.sanity_check();
+
+        Separated {
+            query_builder: self,
+            separator,
+            push_separator: false,
+        }
+    }
+
+    /// Push a `VALUES` clause with one parenthesized, comma-separated tuple per item
+    /// in `tuples`, for use in bulk inserts.
+    ///
+    /// `push_tuple` is called once for each item and should push the fields of that tuple
+    /// through the given [`Separated`].
+    ///
+    /// ```rust,ignore
+    /// let mut builder = QueryBuilder::<MySql>::new("INSERT INTO users(id, name) ");
+    ///
+    /// builder.push_values(users, |mut b, user| {
+    ///     b.push_bind(user.id).push_bind(user.name);
+    /// });
+    ///
+    /// builder.build().execute(&pool).await?;
+    /// ```
+    ///
+    /// Note that databases limit the number of bind parameters in a single statement
+    /// (e.g., 65535 for Postgres and MySQL, 999 for SQLite before 3.32.0 and 32766 after);
+    /// executing a query with more returns an error. To insert more rows than fit in one
+    /// statement, split them into chunks of at most `limit / binds_per_row` rows and
+    /// [`reset`](QueryBuilder::reset) the builder for each chunk:
+    ///
+    /// ```rust,ignore
+    /// // two binds per row
+    /// const ROWS_PER_INSERT: usize = 65535 / 2;
+    ///
+    /// let mut builder = QueryBuilder::<Postgres>::new("INSERT INTO users(id, name) ");
+    ///
+    /// for chunk in users.chunks(ROWS_PER_INSERT) {
+    ///     builder.reset();
+    ///
+    ///     builder.push_values(chunk, |mut b, user| {
+    ///         b.push_bind(user.id).push_bind(&user.name);
+    ///     });
+    ///
+    ///     builder.build().execute(&pool).await?;
+    /// }
+    /// ```
+    pub fn push_values<I, F>(&mut self, tuples: I, mut push_tuple: F) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+    {
+        self.sanity_check();
+
+        self.push("VALUES ");
+
+        let mut separated = self.separated(", ");
+
+        for tuple in tuples {
+            separated.push("(");
+
+            // use a `Separated` with a separate (hah) internal state
+            push_tuple(separated.query_builder.separated(", "), tuple);
+
+            separated.push_unseparated(")");
+        }
+
+        separated.query_builder
+    }
+
+    /// Produce an executable query from this builder.
+    ///
+    /// The arguments are moved into the returned [`Query`]; the builder must then be
+    /// [`reset`](QueryBuilder::reset) before it can be used again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the builder was already built and not reset since.
+    pub fn build(&mut self) -> Query<'_, DB, <DB as HasArguments<'args>>::Arguments> {
+        self.sanity_check();
+
+        Query {
+            query: &self.query,
+            arguments: self.arguments.take(),
+            database: PhantomData,
+            timeout: None,
+            prepared: None,
+        }
+    }
+
+    /// Reset this builder to its initial SQL fragment with no arguments, so it may be reused.
+    pub fn reset(&mut self) -> &mut Self {
+        self.query.truncate(self.init_len);
+        self.arguments = Some(Default::default());
+
+        self
+    }
+
+    /// Get the current SQL of this builder.
+    pub fn sql(&self) -> &str {
+        &self.query
+    }
+
+    /// Take the SQL of this builder, discarding its arguments.
+    pub fn into_sql(self) -> String {
+        self.query
+    }
+}
+
+#[cfg(feature = "any")]
+impl<'args> QueryBuilder<'args, crate::any::Any> {
+    /// Start building a query for the given kind of database, with an initial SQL fragment.
+    ///
+    /// [`push_bind`](QueryBuilder::push_bind) then writes the placeholders of that database;
+    /// pass the kind of the connection or pool the query will be executed on, e.g.
+    /// [`AnyConnection::kind`](crate::any::AnyConnection::kind).
+    pub fn with_kind(kind: crate::any::AnyKind, init: impl Into<String>) -> Self {
+        use crate::any::AnyKind;
+
+        let format_placeholder: fn(&mut String, usize) -> fmt::Result = match kind {
+            #[cfg(feature = "postgres")]
+            AnyKind::Postgres => |query, index| write!(query, "${}", index + 1),
+
+            #[cfg(feature = "mysql")]
+            AnyKind::MySql => |query, _| query.write_str("?"),
+
+            #[cfg(feature = "sqlite")]
+            AnyKind::Sqlite => |query, _| query.write_str("?"),
+
+            #[cfg(feature = "mssql")]
+            AnyKind::Mssql => |query, index| write!(query, "@p{}", index + 1),
+        };
+
+        QueryBuilder {
+            format_placeholder: Some(format_placeholder),
+            ..Self::new(init)
+        }
+    }
+}
+
+/// A list of SQL fragments and bind parameters separated by a given separator.
+///
+/// Returned by [`QueryBuilder::separated`].
+pub struct Separated<'qb, 'args, DB, Sep>
+where
+    DB: Database,
+{
+    query_builder: &'qb mut QueryBuilder<'args, DB>,
+    separator: Sep,
+    push_separator: bool,
+}
+
+impl<'qb, 'args: 'qb, DB, Sep> Separated<'qb, 'args, DB, Sep>
+where
+    DB: Database,
+    Sep: Display,
+{
+    /// Push the separator if this isn't the first item, then push the SQL fragment.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        if self.push_separator {
+            self.query_builder.push(&self.separator);
+        }
+
+        self.query_builder.push(sql);
+        self.push_separator = true;
+
+        self
+    }
+
+    /// Push a SQL fragment without a separator.
+    ///
+    /// The next item pushed with [`push`](Separated::push) or
+    /// [`push_bind`](Separated::push_bind) is still preceded by the separator.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.query_builder.push(sql);
+
+        self
+    }
+
+    /// Push the separator if this isn't the first item, then push a bind parameter.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        if self.push_separator {
+            self.query_builder.push(&self.separator);
+        }
+
+        self.query_builder.push_bind(value);
+        self.push_separator = true;
+
+        self
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::QueryBuilder;
+    use crate::postgres::Postgres;
+
+    #[test]
+    fn it_numbers_postgres_placeholders() {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE id IN (");
+
+        let mut separated = builder.separated(", ");
+        for id in 1..=3 {
+            separated.push_bind(id);
+        }
+
+        builder.push(") AND name = ").push_bind("alice");
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM users WHERE id IN ($1, $2, $3) AND name = $4"
+        );
+    }
+
+    #[test]
+    fn it_pushes_values() {
+        let mut builder = QueryBuilder::<Postgres>::new("INSERT INTO users(id, name) ");
+
+        builder.push_values(vec![(1, "alice"), (2, "bob")], |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        });
+
+        assert_eq!(
+            builder.sql(),
+            "INSERT INTO users(id, name) VALUES ($1, $2), ($3, $4)"
+        );
+    }
+
+    #[test]
+    fn it_resets() {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT ");
+
+        builder.push_bind(1_i32);
+        let _ = builder.build();
+
+        builder.reset().push_bind(2_i32);
+
+        assert_eq!(builder.sql(), "SELECT $1");
+    }
+
+    #[cfg(feature = "any")]
+    #[test]
+    fn it_numbers_placeholders_for_the_kind_of_any() {
+        use crate::any::{Any, AnyKind};
+
+        let mut builder = QueryBuilder::<Any>::with_kind(AnyKind::Postgres, "SELECT ");
+
+        builder.push_bind(1_i32).push(", ").push_bind(2_i32);
+        assert_eq!(builder.sql(), "SELECT $1, $2");
+
+        let _ = builder.build();
+
+        builder.reset().push_bind(3_i32);
+
+        assert_eq!(builder.sql(), "SELECT $1");
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
@@ -9,13 +12,14 @@ use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
 use crate::query_as::{query_as, query_as_with, QueryAs};
+use crate::statement::PreparedId;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`] on `(O,)`.
 /// Returned from [`query_scalar`].
 #[must_use = "query must be executed to affect database"]
 pub struct QueryScalar<'q, DB: Database, O, A> {
-    inner: QueryAs<'q, DB, (O,), A>,
+    pub(crate) inner: QueryAs<'q, DB, (O,), A>,
 }
 
 impl<'q, DB: Database, O: Send, A: Send> Execute<'q, DB> for QueryScalar<'q, DB, O, A>
@@ -31,6 +35,16 @@ where
     fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
         self.inner.take_arguments()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.inner.timeout
+    }
+
+    #[inline]
+    fn prepared(&self) -> Option<&Arc<PreparedId>> {
+        self.inner.prepared()
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -52,6 +66,15 @@ where
     A: 'q + IntoArguments<'q, DB>,
     (O,): Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Limit the amount of time this query may run for.
+    ///
+    /// See [`Query::timeout`](crate::query::Query::timeout).
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     #[inline]
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use crate::database::{Database, HasValueRef};
-use crate::decode::Decode;
+use crate::decode::{Decode, DecodeLossy};
 use crate::error::{mismatched_types, Error};
 use crate::type_info::TypeInfo;
 use crate::types::Type;
@@ -216,13 +216,53 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
         })
     }
 
-    /// Index into the database row and decode a single value.
+    /// Index into the database row and decode a single value, widening it if the SQL type of
+    /// the value is a narrower numeric type than the one requested.
+    ///
+    /// Unlike [`try_get`], this can read an `INT4` as an `i64`, or an `INTEGER` as an `f64`
+    /// (which is useful with the dynamic typing of SQLite). Only conversions that cannot lose
+    /// information are performed, as listed on [`DecodeLossy`]; other type mismatches are still
+    /// an error.
+    ///
+    /// [`try_get`]: #method.try_get
+    /// [`DecodeLossy`]: crate::decode::DecodeLossy
+    ///
+    /// # Errors
+    ///
+    ///  * [`ColumnNotFound`] if the column by the given name was not found.
+    ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
+    ///  * [`ColumnDecode`] if the value could not be decoded or widened into the requested type.
+    ///
+    /// [`ColumnDecode`]: crate::Error::ColumnDecode
+    /// [`ColumnNotFound`]: crate::Error::ColumnNotFound
+    /// [`ColumnIndexOutOfBounds`]: crate::Error::ColumnIndexOutOfBounds
+    ///
+    fn try_get_lossy<'r, T, I>(&'r self, index: I) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: DecodeLossy<'r, Self::Database>,
+    {
+        let value = self.try_get_raw(&index)?;
+
+        T::decode_lossy(value).map_err(|source| Error::ColumnDecode {
+            index: format!("{:?}", index),
+            source,
+        })
+    }
+
+    /// Index into the database row and return the raw, undecoded value.
+    ///
+    /// The returned [`ValueRef`] exposes the [`type_info`] and [`is_null`] of the value, which
+    /// allows decoding rows dynamically without knowing the Rust types at compile time.
     ///
     /// # Errors
     ///
     ///  * [`ColumnNotFound`] if the column by the given name was not found.
     ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
     ///
+    /// [`ValueRef`]: crate::value::ValueRef
+    /// [`type_info`]: crate::value::ValueRef::type_info
+    /// [`is_null`]: crate::value::ValueRef::is_null
     /// [`ColumnNotFound`]: crate::Error::ColumnNotFound
     /// [`ColumnIndexOutOfBounds`]: crate::Error::ColumnIndexOutOfBounds
     ///
@@ -21,12 +21,19 @@ pub(super) fn describe<'c: 'e, 'q: 'e, 'e>(
         let mut columns = Vec::new();
         let mut nullable = Vec::new();
         let mut num_params = 0;
+        let mut parameter_names = Vec::new();
 
         let mut statement = statement?;
 
         // we start by finding the first statement that *can* return results
         while let Some((statement, ..)) = statement.execute()? {
-            num_params += statement.bind_parameter_count();
+            let statement_params = statement.bind_parameter_count();
+            num_params += statement_params;
+
+            // NOTE: The first host parameter has an index of 1, not 0.
+            for param in 1..=statement_params {
+                parameter_names.push(statement.bind_parameter_name(param).map(str::to_owned));
+            }
 
             let mut stepped = false;
 
@@ -99,6 +106,7 @@ pub(super) fn describe<'c: 'e, 'q: 'e, 'e>(
             columns,
             parameters: Some(Either::Right(num_params)),
             nullable,
+            parameter_names: Some(parameter_names),
         })
     })
 }
@@ -4,7 +4,7 @@ use std::ptr::{null, null_mut};
 use libsqlite3_sys::{
     sqlite3_busy_timeout, sqlite3_extended_result_codes, sqlite3_open_v2, SQLITE_OK,
     SQLITE_OPEN_CREATE, SQLITE_OPEN_MEMORY, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_PRIVATECACHE,
-    SQLITE_OPEN_READONLY, SQLITE_OPEN_READWRITE,
+    SQLITE_OPEN_READONLY, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE, SQLITE_OPEN_URI,
 };
 use sqlx_rt::blocking;
 
@@ -34,7 +34,13 @@ pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteCo
     // [SQLITE_OPEN_NOMUTEX] will instruct [sqlite3_open_v2] to return an error if it
     // cannot satisfy our wish for a thread-safe, lock-free connection object
 
-    let mut flags = SQLITE_OPEN_NOMUTEX | SQLITE_OPEN_PRIVATECACHE;
+    let mut flags = SQLITE_OPEN_NOMUTEX;
+
+    flags |= if options.shared_cache {
+        SQLITE_OPEN_SHAREDCACHE
+    } else {
+        SQLITE_OPEN_PRIVATECACHE
+    };
 
     flags |= if options.read_only {
         SQLITE_OPEN_READONLY
@@ -45,9 +51,13 @@ pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteCo
     };
 
     if options.in_memory {
-        flags |= SQLITE_OPEN_MEMORY;
+        // the database is named by a `file:` URI so it can be shared between connections
+        flags |= SQLITE_OPEN_MEMORY | SQLITE_OPEN_URI;
     }
 
+    // the busy timeout is in milliseconds
+    let busy_timeout = options.busy_timeout.as_millis().min(i32::MAX as u128) as i32;
+
     let handle = blocking!({
         let mut handle = null_mut();
 
@@ -85,8 +95,7 @@ pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteCo
         // This causes SQLite to automatically sleep in increasing intervals until the time
         // when there is something locked during [sqlite3_step]. This is sync. but we only
         // run [sqlite3_step] in [blocking!] so its okay.
-        // TODO: Allow this timeout to be configured in SqliteOptions
-        status = unsafe { sqlite3_busy_timeout(handle.0.as_ptr(), 5000) };
+        status = unsafe { sqlite3_busy_timeout(handle.0.as_ptr(), busy_timeout) };
         if status != SQLITE_OK {
             return Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))));
         }
@@ -101,5 +110,6 @@ pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteCo
         statement: None,
         transaction_depth: 0,
         scratch_row_column_names: Default::default(),
+        log_settings: options.log_settings.clone(),
     })
 }
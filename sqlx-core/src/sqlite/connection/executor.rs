@@ -1,9 +1,10 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
-use futures_util::{FutureExt, TryStreamExt};
+use futures_util::{pin_mut, FutureExt, TryStreamExt};
 use hashbrown::HashMap;
 use libsqlite3_sys::sqlite3_last_insert_rowid;
 
@@ -11,9 +12,10 @@ use crate::common::StatementCache;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
+use crate::logging::QueryLogger;
 use crate::sqlite::connection::describe::describe;
 use crate::sqlite::connection::ConnectionHandle;
-use crate::sqlite::statement::{SqliteStatement, StatementHandle};
+use crate::sqlite::statement::{SqliteStatement, StatementHandle, StatementWorker};
 use crate::sqlite::{
     Sqlite, SqliteArguments, SqliteColumn, SqliteConnection, SqliteDone, SqliteRow,
 };
@@ -89,6 +91,44 @@ fn emplace_row_metadata(
     Ok(())
 }
 
+// steps the statement on the worker; once the deadline passes, the statement is interrupted
+// and [Error::QueryTimedOut] is returned after the worker has stopped
+async fn step_until(
+    worker: &StatementWorker,
+    handle: &StatementHandle,
+    deadline: Option<Instant>,
+) -> Result<Either<u64, ()>, Error> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return worker.step(handle).await,
+    };
+
+    let step = worker.step(handle);
+    pin_mut!(step);
+
+    let remaining = deadline
+        .checked_duration_since(Instant::now())
+        .unwrap_or_else(|| Duration::from_secs(0));
+
+    match sqlx_rt::timeout(remaining, &mut step).await {
+        Ok(res) => res,
+
+        Err(_) => {
+            // the step may still be running on the worker; it must not be abandoned or the
+            // connection would be used while the worker still holds the statement
+            handle.interrupt();
+
+            // SQLITE_INTERRUPT is expected here, or a result if the step finished meanwhile
+            let _ = step.await;
+
+            // stop the statement so a missed interrupt cannot affect the next one
+            handle.reset();
+
+            Err(Error::QueryTimedOut)
+        }
+    }
+}
+
 fn update_column_type_metadata(statement: &StatementHandle, columns: &mut Vec<SqliteColumn>) {
     for col in columns.iter_mut() {
         col.type_info = statement.column_type_info(col.ordinal);
@@ -108,8 +148,12 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
     {
         let s = query.query();
         let arguments = query.take_arguments();
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
+
+        let cached = arguments.is_some() && self.statements.contains_key(s);
+        let mut logger = QueryLogger::new(s, cached, &self.log_settings);
 
-        Box::pin(try_stream! {
+        logger.span().instrument_stream(Box::pin(try_stream! {
             let SqliteConnection {
                 handle: ref mut conn,
                 ref mut statements,
@@ -140,7 +184,7 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                     // and send them to the still-live row object
                     SqliteRow::inflate_if_needed(handle, &*columns, last_row_values.take());
 
-                    let s = worker.step(handle).await?;
+                    let s = step_until(worker, handle, deadline).await?;
 
                     if !have_metadata {
                         have_metadata = true;
@@ -165,7 +209,10 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                                 last_insert_rowid: last_insert_rowid,
                             };
 
-                            r#yield!(Either::Left(done));
+                            let v = Either::Left(done);
+                            logger.record(&v);
+
+                            r#yield!(v);
 
                             break;
                         }
@@ -180,6 +227,7 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                             let v = Either::Right(row);
                             *last_row_values = Some(weak_values_ref);
 
+                            logger.record(&v);
                             r#yield!(v);
                         }
                     }
@@ -187,7 +235,7 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
             }
 
             Ok(())
-        })
+        }))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
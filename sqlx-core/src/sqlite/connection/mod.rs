@@ -5,12 +5,13 @@ use std::sync::Arc;
 use futures_core::future::BoxFuture;
 use futures_util::future;
 use hashbrown::HashMap;
-use libsqlite3_sys::sqlite3;
+use libsqlite3_sys::{sqlite3, sqlite3_changes, sqlite3_last_insert_rowid, sqlite3_total_changes};
 
 use crate::common::StatementCache;
 use crate::connection::Connection;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::logging::LogSettings;
 use crate::sqlite::statement::{SqliteStatement, StatementWorker};
 use crate::sqlite::{Sqlite, SqliteConnectOptions};
 
@@ -21,7 +22,7 @@ mod executor;
 mod explain;
 mod handle;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 pub(crate) use handle::ConnectionHandle;
 
 /// A connection to a [Sqlite] database.
@@ -40,6 +41,9 @@ pub struct SqliteConnection {
 
     // working memory for the active row's column information
     scratch_row_column_names: Arc<HashMap<UStr, usize>>,
+
+    // settings for recording executed statements
+    log_settings: LogSettings,
 }
 
 impl SqliteConnection {
@@ -48,6 +52,30 @@ impl SqliteConnection {
         self.handle.as_ptr()
     }
 
+    /// The rowid of the most recent successful `INSERT` on this connection, or `0` if there
+    /// has been none.
+    ///
+    /// See [`sqlite3_last_insert_rowid`](https://www.sqlite.org/c3ref/last_insert_rowid.html).
+    pub fn last_insert_rowid(&self) -> i64 {
+        unsafe { sqlite3_last_insert_rowid(self.handle.as_ptr()) }
+    }
+
+    /// The number of rows changed by the most recently completed `INSERT`, `UPDATE`, or
+    /// `DELETE` on this connection.
+    ///
+    /// See [`sqlite3_changes`](https://www.sqlite.org/c3ref/changes.html).
+    pub fn changes(&self) -> u64 {
+        unsafe { sqlite3_changes(self.handle.as_ptr()) as u64 }
+    }
+
+    /// The number of rows changed by all `INSERT`, `UPDATE`, or `DELETE` statements since the
+    /// connection was opened.
+    ///
+    /// See [`sqlite3_total_changes`](https://www.sqlite.org/c3ref/total_changes.html).
+    pub fn total_changes(&self) -> u64 {
+        unsafe { sqlite3_total_changes(self.handle.as_ptr()) as u64 }
+    }
+
     pub fn create_collation(
         &mut self,
         name: &str,
@@ -85,6 +113,16 @@ impl Connection for SqliteConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.statements.len()
     }
@@ -9,9 +9,22 @@ pub struct SqliteDone {
 }
 
 impl SqliteDone {
+    /// Returns the [rowid](https://www.sqlite.org/lang_createtable.html#rowid) of the most
+    /// recent successful `INSERT` on the connection.
+    ///
+    /// For a table with an `INTEGER PRIMARY KEY` column, this is the value of that column for
+    /// the inserted row.
     pub fn last_insert_rowid(&self) -> i64 {
         self.last_insert_rowid
     }
+
+    /// Returns the ID generated by the most recent successful `INSERT`.
+    ///
+    /// This is the same as [`last_insert_rowid`](#method.last_insert_rowid), under the name
+    /// used by [`MySqlDone`](crate::mysql::MySqlDone) and [`AnyDone`](crate::any::AnyDone).
+    pub fn last_insert_id(&self) -> i64 {
+        self.last_insert_rowid
+    }
 }
 
 impl Done for SqliteDone {
@@ -35,6 +35,11 @@ impl SqliteError {
             message: message.to_owned(),
         }
     }
+
+    // the primary result code, without the extended information in the upper bits
+    pub(crate) fn primary_code(&self) -> c_int {
+        self.code & 0xff
+    }
 }
 
 impl Display for SqliteError {
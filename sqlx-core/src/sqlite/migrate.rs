@@ -1,6 +1,7 @@
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::migrate::quote_table_name;
 use crate::migrate::MigrateError;
 use crate::migrate::Migration;
 use crate::migrate::{Migrate, MigrateDatabase};
@@ -53,12 +54,17 @@ impl MigrateDatabase for Sqlite {
 }
 
 impl Migrate for SqliteConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
             // language=SQLite
-            self.execute(
+            self.execute(&*format!(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+CREATE TABLE IF NOT EXISTS {} (
     version BIGINT PRIMARY KEY,
     description TEXT NOT NULL,
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -67,19 +73,26 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     execution_time BIGINT NOT NULL
 );
                 "#,
-            )
+                table_name
+            ))
             .await?;
 
             Ok(())
         })
     }
 
-    fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+    fn version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
             // language=SQLite
-            let row = query_as(
-                "SELECT version, NOT success FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
-            )
+            let row = query_as(&*format!(
+                "SELECT version, NOT success FROM {} ORDER BY version DESC LIMIT 1",
+                table_name
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -95,17 +108,41 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         Box::pin(async move { Ok(()) })
     }
 
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<i64>, MigrateError>> {
+        Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
+            // language=SQLite
+            let versions = query_scalar(&*format!(
+                "SELECT version FROM {} WHERE success ORDER BY version",
+                table_name
+            ))
+            .fetch_all(self)
+            .await?;
+
+            Ok(versions)
+        })
+    }
+
     fn validate<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
             // language=SQL
-            let checksum: Option<Vec<u8>> =
-                query_scalar("SELECT checksum FROM _sqlx_migrations WHERE version = ?1")
-                    .bind(migration.version)
-                    .fetch_optional(self)
-                    .await?;
+            let checksum: Option<Vec<u8>> = query_scalar(&*format!(
+                "SELECT checksum FROM {} WHERE version = ?1",
+                table_name
+            ))
+            .bind(migration.version)
+            .fetch_optional(self)
+            .await?;
 
             if let Some(checksum) = checksum {
                 return if checksum == &*migration.checksum {
@@ -121,9 +158,12 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
             let mut tx = self.begin().await?;
             let start = Instant::now();
 
@@ -134,12 +174,13 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(
+            let _ = query(&*format!(
                 r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
     VALUES ( ?1, ?2, TRUE, ?3, ?4 )
                 "#,
-            )
+                table_name
+            ))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(&*migration.checksum)
@@ -150,4 +191,38 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             Ok(elapsed)
         })
     }
+
+    fn revert<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let table_name = quote_table_name(table_name, '"');
+
+            let down_sql = migration
+                .down_sql
+                .as_deref()
+                .ok_or(MigrateError::Irreversible(migration.version))?;
+
+            // statements left active by `fetch_optional` (as in `version` and `validate`)
+            // would keep their tables locked and fail any `DROP TABLE` of the down script
+            self.clear_cached_statements().await?;
+
+            let mut tx = self.begin().await?;
+            let start = Instant::now();
+
+            let _ = tx.execute(down_sql).await?;
+
+            // language=SQLite
+            let _ = query(&*format!("DELETE FROM {} WHERE version = ?1", table_name))
+                .bind(migration.version)
+                .execute(&mut tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(start.elapsed())
+        })
+    }
 }
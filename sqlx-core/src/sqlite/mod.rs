@@ -28,7 +28,7 @@ pub use connection::SqliteConnection;
 pub use database::Sqlite;
 pub use done::SqliteDone;
 pub use error::SqliteError;
-pub use options::{SqliteConnectOptions, SqliteJournalMode};
+pub use options::{SqliteConnectOptions, SqliteJournalMode, SqliteSynchronous};
 pub use row::SqliteRow;
 pub use transaction::SqliteTransactionManager;
 pub use type_info::SqliteTypeInfo;
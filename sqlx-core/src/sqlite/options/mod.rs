@@ -3,9 +3,14 @@ use std::path::Path;
 mod connect;
 mod journal_mode;
 mod parse;
+mod synchronous;
 
 pub use journal_mode::SqliteJournalMode;
 use std::borrow::Cow;
+use std::time::Duration;
+pub use synchronous::SqliteSynchronous;
+
+use crate::logging::LogSettings;
 
 /// Options and flags which can be used to configure a SQLite connection.
 ///
@@ -14,11 +19,34 @@ use std::borrow::Cow;
 ///
 /// | URI | Description |
 /// | -- | -- |
-/// `sqlite::memory:` | Open an in-memory database. |
+/// `sqlite::memory:` | Open an in-memory database, shared by all connections opened with these options. |
 /// `sqlite:data.db` | Open the file `data.db` in the current directory. |
 /// `sqlite://data.db` | Open the file `data.db` in the current directory. |
 /// `sqlite:///data.db` | Open the file `data.db` from the root (`/`) directory. |
 /// `sqlite://data.db?mode=ro` | Open the file `data.db` for read-only access. |
+/// `sqlite://data.db?journal_mode=wal&busy_timeout=5000` | Open the file `data.db` in WAL mode, waiting up to 5 seconds for locks. |
+///
+/// Besides `mode`, the [`journal_mode`], [`synchronous`], [`foreign_keys`] and
+/// [`busy_timeout`] (in milliseconds) options may be set as query parameters.
+///
+/// [`journal_mode`]: #method.journal_mode
+/// [`synchronous`]: #method.synchronous
+/// [`foreign_keys`]: #method.foreign_keys
+/// [`busy_timeout`]: #method.busy_timeout
+///
+/// # In-memory Databases
+///
+/// Every connection to a plain `:memory:` database would see a separate, empty database. To
+/// make `sqlite::memory:` usable with a pool, the options parsed from it name a unique in-memory
+/// database which is opened in [shared-cache mode](https://www.sqlite.org/sharedcache.html), so
+/// all connections opened with these (or cloned) options see the same data.
+///
+/// The database is deleted once its last connection is closed. Configure the pool with
+/// `min_connections(1)` (and no `idle_timeout` or `max_lifetime`) to keep it alive.
+///
+/// Shared-cache mode uses table-level locks, and a lock conflict is returned as an error
+/// immediately instead of waiting for the [`busy_timeout`]. If concurrent writes are expected,
+/// a pool with `max_connections(1)` avoids these conflicts altogether.
 ///
 /// # Example
 ///
@@ -44,11 +72,15 @@ use std::borrow::Cow;
 pub struct SqliteConnectOptions {
     pub(crate) filename: Cow<'static, Path>,
     pub(crate) in_memory: bool,
+    pub(crate) shared_cache: bool,
     pub(crate) read_only: bool,
     pub(crate) create_if_missing: bool,
     pub(crate) journal_mode: SqliteJournalMode,
     pub(crate) foreign_keys: bool,
+    pub(crate) synchronous: SqliteSynchronous,
+    pub(crate) busy_timeout: Duration,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) log_settings: LogSettings,
 }
 
 impl Default for SqliteConnectOptions {
@@ -62,11 +94,15 @@ impl SqliteConnectOptions {
         Self {
             filename: Cow::Borrowed(Path::new(":memory:")),
             in_memory: false,
+            shared_cache: false,
             read_only: false,
             create_if_missing: false,
             foreign_keys: true,
             statement_cache_capacity: 100,
             journal_mode: SqliteJournalMode::Wal,
+            synchronous: SqliteSynchronous::Full,
+            busy_timeout: Duration::from_secs(5),
+            log_settings: Default::default(),
         }
     }
 
@@ -93,6 +129,27 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets the [synchronous](https://www.sqlite.org/pragma.html#pragma_synchronous) setting
+    /// for the database connection.
+    ///
+    /// The default setting is FULL. In WAL mode, NORMAL is also safe from corruption and is
+    /// usually faster, but a recently committed transaction may be rolled back following a
+    /// power loss.
+    pub fn synchronous(mut self, synchronous: SqliteSynchronous) -> Self {
+        self.synchronous = synchronous;
+        self
+    }
+
+    /// Sets a [busy timeout](https://www.sqlite.org/c3ref/busy_timeout.html) for the database
+    /// connection.
+    ///
+    /// When the database is locked by another connection, SQLite retries until this much time
+    /// has passed before returning an error. The default timeout is 5 seconds.
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = timeout;
+        self
+    }
+
     /// Sets the [access mode](https://www.sqlite.org/c3ref/open.html) to open the database
     /// for read-only access.
     pub fn read_only(mut self, read_only: bool) -> Self {
@@ -109,14 +166,34 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets whether the database is opened in [shared-cache mode](https://www.sqlite.org/sharedcache.html).
+    ///
+    /// This is enabled for `sqlite::memory:` so that pooled connections share the database;
+    /// otherwise, it is disabled by default.
+    pub fn shared_cache(mut self, on: bool) -> Self {
+        self.shared_cache = on;
+        self
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
     /// dropped.
     ///
-    /// The default cache capacity is 100 statements.
+    /// The default cache capacity is 100 statements. A capacity of `0` disables the cache;
+    /// every statement is then prepared when it is executed and closed right after.
     pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
         self.statement_cache_capacity = capacity;
         self
     }
+
+    /// Sets the execution time at or above which a statement is reported as slow.
+    ///
+    /// When the `tracing` feature is enabled, every statement executed on the connection
+    /// is recorded in a `sqlx::query` span and statements that run at least this long
+    /// additionally emit an event at the `WARN` level. The default threshold is 1 second.
+    pub fn slow_statement_threshold(mut self, threshold: Duration) -> Self {
+        self.log_settings.slow_statement_threshold = threshold;
+        self
+    }
 }
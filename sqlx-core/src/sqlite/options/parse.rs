@@ -2,11 +2,16 @@ use crate::error::Error;
 use crate::sqlite::SqliteConnectOptions;
 use percent_encoding::percent_decode_str;
 use std::borrow::Cow;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 // https://www.sqlite.org/uri.html
 
+// sequence for naming in-memory databases so each set of options refers to a distinct database
+static IN_MEMORY_DB_SEQ: AtomicUsize = AtomicUsize::new(0);
+
 impl FromStr for SqliteConnectOptions {
     type Err = Error;
 
@@ -68,6 +73,38 @@ impl FromStr for SqliteConnectOptions {
                         }
                     }
 
+                    "journal_mode" => {
+                        options.journal_mode = value.parse()?;
+                    }
+
+                    "synchronous" => {
+                        options.synchronous = value.parse()?;
+                    }
+
+                    "foreign_keys" => {
+                        options.foreign_keys = match &*value {
+                            "true" | "on" | "1" => true,
+                            "false" | "off" | "0" => false,
+
+                            _ => {
+                                return Err(Error::Configuration(
+                                    format!("unknown value {:?} for `foreign_keys`", value).into(),
+                                ));
+                            }
+                        };
+                    }
+
+                    // in milliseconds, as with `PRAGMA busy_timeout`
+                    "busy_timeout" => {
+                        let ms = value.parse().map_err(|_| {
+                            Error::Configuration(
+                                format!("invalid value {:?} for `busy_timeout`", value).into(),
+                            )
+                        })?;
+
+                        options.busy_timeout = Duration::from_millis(ms);
+                    }
+
                     _ => {
                         return Err(Error::Configuration(
                             format!(
@@ -81,6 +118,19 @@ impl FromStr for SqliteConnectOptions {
             }
         }
 
+        if options.in_memory {
+            // name the database so it can be shared between connections; this requires
+            // the filename to be a URI
+            let seqno = IN_MEMORY_DB_SEQ.fetch_add(1, Ordering::Relaxed);
+
+            options.filename = Cow::Owned(PathBuf::from(format!(
+                "file:sqlx-in-memory-{}?mode=memory",
+                seqno
+            )));
+
+            options.shared_cache = true;
+        }
+
         Ok(options)
     }
 }
@@ -95,6 +145,11 @@ fn test_parse_in_memory() -> Result<(), Error> {
 
     let options: SqliteConnectOptions = "sqlite://:memory:".parse()?;
     assert!(options.in_memory);
+    assert!(options.shared_cache);
+
+    // each set of options refers to a distinct database
+    let other: SqliteConnectOptions = "sqlite://:memory:".parse()?;
+    assert_ne!(options.filename, other.filename);
 
     Ok(())
 }
@@ -107,3 +162,23 @@ fn test_parse_read_only() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_pragmas() -> Result<(), Error> {
+    use crate::sqlite::{SqliteJournalMode, SqliteSynchronous};
+
+    let options: SqliteConnectOptions =
+        "sqlite://a.db?journal_mode=truncate&synchronous=normal&foreign_keys=off&busy_timeout=250"
+            .parse()?;
+
+    assert!(matches!(options.journal_mode, SqliteJournalMode::Truncate));
+    assert!(matches!(options.synchronous, SqliteSynchronous::Normal));
+    assert!(!options.foreign_keys);
+    assert_eq!(options.busy_timeout, Duration::from_millis(250));
+
+    assert!("sqlite://a.db?busy_timeout=soon"
+        .parse::<SqliteConnectOptions>()
+        .is_err());
+
+    Ok(())
+}
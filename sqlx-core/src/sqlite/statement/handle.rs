@@ -13,8 +13,9 @@ use libsqlite3_sys::{
     sqlite3_column_count, sqlite3_column_database_name, sqlite3_column_decltype,
     sqlite3_column_double, sqlite3_column_int, sqlite3_column_int64, sqlite3_column_name,
     sqlite3_column_origin_name, sqlite3_column_table_name, sqlite3_column_type,
-    sqlite3_column_value, sqlite3_db_handle, sqlite3_sql, sqlite3_stmt, sqlite3_stmt_readonly,
-    sqlite3_table_column_metadata, sqlite3_value, SQLITE_OK, SQLITE_TRANSIENT, SQLITE_UTF8,
+    sqlite3_column_value, sqlite3_db_handle, sqlite3_interrupt, sqlite3_reset, sqlite3_sql,
+    sqlite3_stmt, sqlite3_stmt_readonly, sqlite3_table_column_metadata, sqlite3_value, SQLITE_OK,
+    SQLITE_TRANSIENT, SQLITE_UTF8,
 };
 
 use crate::error::{BoxDynError, Error};
@@ -53,6 +54,19 @@ impl StatementHandle {
         }
     }
 
+    pub(crate) fn interrupt(&self) {
+        // stops whatever is running on the connection of this statement
+        // https://www.sqlite.org/c3ref/interrupt.html
+        unsafe { sqlite3_interrupt(self.db_handle()) }
+    }
+
+    pub(crate) fn reset(&self) {
+        // https://www.sqlite.org/c3ref/reset.html
+        unsafe {
+            sqlite3_reset(self.0.as_ptr());
+        }
+    }
+
     #[inline]
     pub(crate) fn last_error(&self) -> SqliteError {
         SqliteError::new(unsafe { self.db_handle() })
@@ -8,7 +8,7 @@ use crate::executor::Executor;
 use crate::sqlite::{Sqlite, SqliteConnection, SqliteError};
 use crate::transaction::{
     begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    TransactionManager, TransactionOptions,
 };
 
 /// Implementation of [`TransactionManager`] for SQLite.
@@ -17,10 +17,23 @@ pub struct SqliteTransactionManager;
 impl TransactionManager for SqliteTransactionManager {
     type Database = Sqlite;
 
-    fn begin(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
+    fn begin_with(
+        conn: &mut SqliteConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
+            options.validate(depth)?;
+
+            // SQLite transactions are always serializable and so any isolation level
+            // is satisfied; there is no way to declare a transaction as read-only
+            if options.read_only {
+                return Err(Error::Configuration(
+                    "read-only transactions are not supported by SQLite".into(),
+                ));
+            }
+
             conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
             conn.transaction_depth = depth + 1;
 
@@ -23,6 +23,18 @@
 //! | `chrono::DateTime<Utc>`               | DATETIME                                             |
 //! | `chrono::DateTime<Local>`             | DATETIME                                             |
 //!
+//! ### [`json`](https://crates.io/crates/serde_json)
+//!
+//! Requires the `json` Cargo feature flag.
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | [`Json<T>`]                           | TEXT                                                 |
+//! | `serde_json::Value`                   | TEXT                                                 |
+//! | `&serde_json::value::RawValue`        | TEXT                                                 |
+//!
+//! [`Json<T>`]: crate::types::Json
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -35,4 +47,6 @@ mod bytes;
 mod chrono;
 mod float;
 mod int;
+#[cfg(feature = "json")]
+mod json;
 mod str;
@@ -1,6 +1,14 @@
-use crate::database::Database;
+use crate::column::Column;
+use crate::database::{Database, HasArguments};
+use crate::from_row::FromRow;
+use crate::query::{query, Query};
+use crate::query_as::{query_as, QueryAs};
+use crate::query_scalar::{query_scalar, QueryScalar};
+use crossbeam_queue::SegQueue;
 use either::Either;
 use std::convert::identity;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
 
 /// Provides information on a prepared statement.
 ///
@@ -8,6 +16,9 @@ use std::convert::identity;
 ///
 /// The query macros (e.g., `query!`, `query_as!`, etc.) use the information here to validate
 /// output and parameter types; and, generate an anonymous record.
+///
+/// This type does not borrow from the connection that produced it and may be cloned and stored
+/// for as long as needed (e.g., in a cache keyed by query string).
 #[derive(Debug)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -22,6 +33,23 @@ pub struct StatementInfo<DB: Database> {
     pub(crate) columns: Vec<DB::Column>,
     pub(crate) parameters: Option<Either<Vec<DB::TypeInfo>, usize>>,
     pub(crate) nullable: Vec<Option<bool>>,
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) parameter_names: Option<Vec<Option<String>>>,
+}
+
+// NOTE: a derive would add a `DB: Clone` bound
+impl<DB: Database> Clone for StatementInfo<DB>
+where
+    DB::Column: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            columns: self.columns.clone(),
+            parameters: self.parameters.clone(),
+            nullable: self.nullable.clone(),
+            parameter_names: self.parameter_names.clone(),
+        }
+    }
 }
 
 impl<DB: Database> StatementInfo<DB> {
@@ -42,6 +70,17 @@ impl<DB: Database> StatementInfo<DB> {
         &self.columns
     }
 
+    /// Gets the names of all columns in this statement, in order.
+    pub fn column_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.columns.iter().map(Column::name)
+    }
+
+    /// Gets the index of the first column with the given `name`, or `None` if there is no
+    /// such column.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name() == name)
+    }
+
     /// Gets the available information for parameters in this statement.
     ///
     /// Some drivers may return more or less than others. As an example, **PostgreSQL** will
@@ -54,8 +93,190 @@ impl<DB: Database> StatementInfo<DB> {
         })
     }
 
+    /// Gets the name of the parameter at `index`, if it was given one and the driver is able
+    /// to report it.
+    ///
+    /// Currently only **SQLite** reports parameter names. The name is returned as written in
+    /// the query, including its prefix (e.g., `:id`, `@name` or `$value`).
+    pub fn parameter_name(&self, index: usize) -> Option<&str> {
+        self.parameter_names.as_ref()?.get(index)?.as_deref()
+    }
+
     /// Gets whether a column may be `NULL`, if this information is available.
     pub fn nullable(&self, column: usize) -> Option<bool> {
         self.nullable.get(column).copied().and_then(identity)
     }
+
+    /// Overrides whether a column may be `NULL`.
+    ///
+    /// This can be used to correct the nullability inferred by the driver before the
+    /// information is used for code generation. Passing `None` marks it as unknown.
+    ///
+    /// Panics if `column` is out of bounds.
+    pub fn set_nullable(&mut self, column: usize, nullable: Option<bool>) {
+        assert!(
+            column < self.columns.len(),
+            "column index out of bounds: the len is {}, but the index is {}",
+            self.columns.len(),
+            column
+        );
+
+        if self.nullable.len() <= column {
+            self.nullable.resize(self.columns.len(), None);
+        }
+
+        self.nullable[column] = nullable;
+    }
+}
+
+/// A SQL statement that has been prepared on a connection.
+///
+/// Returned from [`Connection::prepare`](crate::connection::Connection::prepare). The
+/// information about its parameters and columns is known before it is executed.
+///
+/// The queries returned by [`query`](Self::query), [`query_as`](Self::query_as) and
+/// [`query_scalar`](Self::query_scalar) execute the statement with the arguments bound to them.
+/// On **PostgreSQL** and **MySQL**, they bind to the statement that was prepared on the
+/// connection, which does not take up room in the statement cache and stays prepared until
+/// this `Statement` (and every clone of it and query made from it) is dropped. It is then
+/// closed the next time the connection is used.
+///
+/// Other databases, and a connection other than the one it was prepared on, look up the
+/// statement in the statement cache of the connection as any other query would.
+pub struct Statement<'q, DB: Database> {
+    pub(crate) sql: &'q str,
+    pub(crate) info: StatementInfo<DB>,
+    pub(crate) id: Option<Arc<PreparedId>>,
+}
+
+/// The id of a statement prepared on a connection by [`Connection::prepare`], which is queued
+/// to be closed on that connection once dropped.
+///
+/// [`Connection::prepare`]: crate::connection::Connection::prepare
+#[doc(hidden)]
+pub struct PreparedId {
+    id: u32,
+    closed: Arc<ClosedStatements>,
+}
+
+impl PreparedId {
+    pub(crate) fn new(id: u32, closed: &Arc<ClosedStatements>) -> Self {
+        Self {
+            id,
+            closed: Arc::clone(closed),
+        }
+    }
+
+    // the id of the statement if it was prepared on the connection owning `closed`
+    pub(crate) fn id_on(&self, closed: &Arc<ClosedStatements>) -> Option<u32> {
+        if Arc::ptr_eq(&self.closed, closed) {
+            Some(self.id)
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for PreparedId {
+    fn drop(&mut self) {
+        self.closed.push(self.id);
+    }
+}
+
+impl Debug for PreparedId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PreparedId").field(&self.id).finish()
+    }
+}
+
+// the ids of the statements prepared by [Connection::prepare] that were dropped and are
+// to be closed by the connection before it runs the next query
+#[derive(Default)]
+pub(crate) struct ClosedStatements(SegQueue<u32>);
+
+impl ClosedStatements {
+    pub(crate) fn push(&self, id: u32) {
+        self.0.push(id);
+    }
+
+    pub(crate) fn pop(&self) -> Option<u32> {
+        self.0.pop().ok()
+    }
+}
+
+impl<'q, DB: Database> Statement<'q, DB> {
+    /// Gets the SQL of this statement.
+    pub fn sql(&self) -> &'q str {
+        self.sql
+    }
+
+    /// Gets the information about the parameters and columns of this statement.
+    pub fn info(&self) -> &StatementInfo<DB> {
+        &self.info
+    }
+
+    /// Gets all columns of this statement.
+    pub fn columns(&self) -> &[DB::Column] {
+        self.info.columns()
+    }
+
+    /// Gets the available information for parameters of this statement.
+    ///
+    /// See [`StatementInfo::parameters`].
+    pub fn parameters(&self) -> Option<Either<&[DB::TypeInfo], usize>> {
+        self.info.parameters()
+    }
+
+    /// Make a query executing this statement; bind the arguments with
+    /// [`Query::bind`](crate::query::Query::bind).
+    pub fn query(&self) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
+        let mut query = query(self.sql);
+        query.prepared = self.id.clone();
+        query
+    }
+
+    /// Make a query executing this statement that maps its rows to a concrete type
+    /// using [`FromRow`].
+    pub fn query_as<O>(&self) -> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments>
+    where
+        O: for<'r> FromRow<'r, DB::Row>,
+    {
+        let mut query = query_as(self.sql);
+        query.inner.prepared = self.id.clone();
+        query
+    }
+
+    /// Make a query executing this statement that maps the first column of its rows to a
+    /// concrete type.
+    pub fn query_scalar<O>(&self) -> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments>
+    where
+        (O,): for<'r> FromRow<'r, DB::Row>,
+    {
+        let mut query = query_scalar(self.sql);
+        query.inner.inner.prepared = self.id.clone();
+        query
+    }
+}
+
+// NOTE: a derive would add a `DB: Clone` bound
+impl<'q, DB: Database> Clone for Statement<'q, DB>
+where
+    DB::Column: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            sql: self.sql,
+            info: self.info.clone(),
+            id: self.id.clone(),
+        }
+    }
+}
+
+impl<'q, DB: Database> Debug for Statement<'q, DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Statement")
+            .field("sql", &self.sql)
+            .field("info", &self.info)
+            .finish()
+    }
 }
@@ -18,6 +18,17 @@ pub trait TransactionManager {
     /// Begin a new transaction or establish a savepoint within the active transaction.
     fn begin(
         conn: &mut <Self::Database as Database>::Connection,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    /// Begin a new transaction with the given options or establish a savepoint within the
+    /// active transaction.
+    ///
+    /// Non-default options are an error when establishing a savepoint.
+    fn begin_with(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: TransactionOptions,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
     /// Commit the active transaction or release the most recent savepoint.
@@ -34,6 +45,102 @@ pub trait TransactionManager {
     fn start_rollback(conn: &mut <Self::Database as Database>::Connection);
 }
 
+/// The isolation level of a transaction.
+///
+/// See the documentation of your database for the exact guarantees of each level. Not every
+/// database supports every level; SQLite transactions are always serializable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub(crate) fn as_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options for beginning a new transaction with [`Connection::begin_with`] or
+/// [`Pool::begin_with`].
+///
+/// The default options begin a transaction with the defaults of the database server,
+/// exactly as `begin` does.
+///
+/// [`Connection::begin_with`]: crate::connection::Connection::begin_with
+/// [`Pool::begin_with`]: crate::pool::Pool::begin_with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionOptions {
+    pub(crate) isolation_level: Option<IsolationLevel>,
+    pub(crate) read_only: bool,
+    pub(crate) deferrable: bool,
+}
+
+impl TransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// On MSSQL, the isolation level remains in effect for the rest of the session.
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Sets whether the transaction is read-only.
+    ///
+    /// Not supported by SQLite or MSSQL.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets whether the transaction is deferrable (**PostgreSQL** only).
+    ///
+    /// A deferrable transaction may block when it starts but then runs without the overhead
+    /// of ensuring serializability. This is only valid for a `SERIALIZABLE` and read-only
+    /// transaction.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // checks the options that are invalid regardless of the database
+    // and returns an error if these options cannot be used at the given transaction depth
+    pub(crate) fn validate(&self, depth: usize) -> Result<(), Error> {
+        if depth > 0 && !self.is_default() {
+            return Err(Error::Configuration(
+                "transaction options cannot be set when establishing a savepoint".into(),
+            ));
+        }
+
+        if self.deferrable
+            && (self.isolation_level != Some(IsolationLevel::Serializable) || !self.read_only)
+        {
+            return Err(Error::Configuration(
+                "a deferrable transaction must be serializable and read-only".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+const CONNECTION_ERR: &str = "(bug) connection already returned from transaction";
+
 /// An in-progress database transaction or savepoint.
 ///
 /// A transaction starts with a call to [`Pool::begin`] or [`Connection::begin`].
@@ -46,6 +153,10 @@ pub trait TransactionManager {
 /// executed after it was established to be rolled back, restoring the transaction state to
 /// what it was at the time of the savepoint.
 ///
+/// Calling [`Connection::begin`] on a `Transaction` (which dereferences to the connection)
+/// establishes a savepoint. Committing or rolling back the returned `Transaction` releases or
+/// restores that savepoint; savepoints may be nested to any depth.
+///
 /// [`Connection::begin`]: struct.Connection.html#method.begin
 /// [`Pool::begin`]: struct.Pool.html#method.begin
 /// [`commit`]: #method.commit
@@ -54,7 +165,8 @@ pub struct Transaction<'c, DB>
 where
     DB: Database,
 {
-    connection: MaybePoolConnection<'c, DB>,
+    // only `None` once `commit_and_return` has moved the connection out
+    connection: Option<MaybePoolConnection<'c, DB>>,
     open: bool,
 }
 
@@ -64,30 +176,71 @@ where
 {
     pub(crate) fn begin(
         conn: impl Into<MaybePoolConnection<'c, DB>>,
+    ) -> BoxFuture<'c, Result<Self, Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    pub(crate) fn begin_with(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: TransactionOptions,
     ) -> BoxFuture<'c, Result<Self, Error>> {
         let mut conn = conn.into();
 
         Box::pin(async move {
-            DB::TransactionManager::begin(&mut conn).await?;
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
 
             Ok(Self {
-                connection: conn,
+                connection: Some(conn),
                 open: true,
             })
         })
     }
 
+    // count a query executed through this transaction, if it is on a pooled connection
+    pub(crate) fn count_query(&mut self) {
+        self.connection
+            .as_mut()
+            .expect(CONNECTION_ERR)
+            .count_query();
+    }
+
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
-        DB::TransactionManager::commit(&mut self.connection).await?;
+        DB::TransactionManager::commit(&mut self).await?;
         self.open = false;
 
         Ok(())
     }
 
+    /// Commits this transaction or savepoint and returns the connection it was started on.
+    ///
+    /// The connection can then be used for further queries outside of the transaction. A
+    /// [`PoolConnection`] is handed back instead of being returned to the pool, saving another
+    /// call to [`Pool::acquire`].
+    ///
+    /// ```rust,ignore
+    /// let mut tx = pool.begin().await?;
+    /// sqlx::query("UPDATE accounts SET balance = balance - 100 WHERE id = 1")
+    ///     .execute(&mut tx)
+    ///     .await?;
+    ///
+    /// if let MaybePoolConnection::PoolConnection(mut conn) = tx.commit_and_return().await? {
+    ///     sqlx::query("NOTIFY accounts").execute(&mut conn).await?;
+    /// }
+    /// ```
+    ///
+    /// [`PoolConnection`]: crate::pool::PoolConnection
+    /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    pub async fn commit_and_return(mut self) -> Result<MaybePoolConnection<'c, DB>, Error> {
+        DB::TransactionManager::commit(&mut self).await?;
+        self.open = false;
+
+        Ok(self.connection.take().expect(CONNECTION_ERR))
+    }
+
     /// Aborts this transaction or savepoint.
     pub async fn rollback(mut self) -> Result<(), Error> {
-        DB::TransactionManager::rollback(&mut self.connection).await?;
+        DB::TransactionManager::rollback(&mut self).await?;
         self.open = false;
 
         Ok(())
@@ -117,6 +270,7 @@ macro_rules! impl_executor_for_transaction {
                 't: 'e,
                 E: crate::executor::Execute<'q, Self::Database>,
             {
+                self.count_query();
                 (&mut **self).fetch_many(query)
             }
 
@@ -128,6 +282,7 @@ macro_rules! impl_executor_for_transaction {
                 't: 'e,
                 E: crate::executor::Execute<'q, Self::Database>,
             {
+                self.count_query();
                 (&mut **self).fetch_optional(query)
             }
 
@@ -167,7 +322,7 @@ where
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.connection
+        self.connection.as_ref().expect(CONNECTION_ERR)
     }
 }
 
@@ -177,7 +332,7 @@ where
 {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.connection
+        self.connection.as_mut().expect(CONNECTION_ERR)
     }
 }
 
@@ -193,7 +348,7 @@ where
             // operation that will happen on the next asynchronous invocation of the underlying
             // connection (including if the connection is returned to a pool)
 
-            DB::TransactionManager::start_rollback(&mut self.connection);
+            DB::TransactionManager::start_rollback(self);
         }
     }
 }
@@ -10,6 +10,19 @@
 //! Any external types that have had [`Type`] implemented for, are re-exported in this module
 //! for convenience as downstream users need to use a compatible version of the external crate
 //! to take advantage of the implementation.
+//!
+//! # Newtypes
+//!
+//! A tuple struct with a single field may derive [`Type`], `Encode` and `Decode` as a transparent
+//! wrapper over that field. The derived implementations delegate entirely to the inner type,
+//! including [`Type::type_info`] and [`Type::compatible`], for every database the inner type
+//! supports.
+//!
+//! ```rust,ignore
+//! #[derive(sqlx::Type)]
+//! #[sqlx(transparent)]
+//! struct UserId(i64);
+//! ```
 
 use crate::database::Database;
 
@@ -49,6 +62,16 @@ pub mod ipnetwork {
     pub use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 }
 
+#[cfg(feature = "mac_address")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac_address")))]
+pub mod mac_address {
+    pub use mac_address::MacAddress;
+}
+
+#[cfg(feature = "bit-vec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bit-vec")))]
+pub use bit_vec::BitVec;
+
 #[cfg(feature = "json")]
 pub use json::Json;
 
@@ -43,6 +43,8 @@ impl_database_ext! {
 
         sqlx::postgres::types::PgInterval,
 
+        sqlx::postgres::types::PgMoney,
+
         #[cfg(feature = "bigdecimal")]
         sqlx::types::BigDecimal,
 
@@ -52,6 +54,12 @@ impl_database_ext! {
         #[cfg(feature = "ipnetwork")]
         sqlx::types::ipnetwork::IpNetwork,
 
+        #[cfg(feature = "mac_address")]
+        sqlx::types::mac_address::MacAddress,
+
+        #[cfg(feature = "bit-vec")]
+        sqlx::types::BitVec,
+
         #[cfg(feature = "json")]
         serde_json::Value,
 
@@ -95,12 +103,20 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         Vec<sqlx::types::time::OffsetDateTime> | &[sqlx::types::time::OffsetDateTime],
 
+        Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
+
         #[cfg(feature = "bigdecimal")]
         Vec<sqlx::types::BigDecimal> | &[sqlx::types::BigDecimal],
 
         #[cfg(feature = "ipnetwork")]
         Vec<sqlx::types::ipnetwork::IpNetwork> | &[sqlx::types::ipnetwork::IpNetwork],
 
+        #[cfg(feature = "mac_address")]
+        Vec<sqlx::types::mac_address::MacAddress> | &[sqlx::types::mac_address::MacAddress],
+
+        #[cfg(feature = "bit-vec")]
+        Vec<sqlx::types::BitVec> | &[sqlx::types::BitVec],
+
         #[cfg(feature = "json")]
         Vec<serde_json::Value> | &[serde_json::Value],
 
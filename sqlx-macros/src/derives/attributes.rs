@@ -43,6 +43,8 @@ pub struct SqlxContainerAttributes {
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
     pub default: bool,
+    pub flatten: bool,
+    pub prefix: Option<String>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -86,6 +88,16 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 ..
                             }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
 
+                            // `type_name` is an alias of `rename` that reads better on a
+                            // struct mapped to a composite type
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("type_name") => {
+                                try_set!(rename, val.value(), value)
+                            }
+
                             u => fail!(u, "unexpected attribute"),
                         },
                         u => fail!(u, "unexpected attribute"),
@@ -118,6 +130,8 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
     let mut default = false;
+    let mut flatten = false;
+    let mut prefix = None;
 
     for attr in input {
         let meta = attr
@@ -134,7 +148,13 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                                 lit: Lit::Str(val),
                                 ..
                             }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("prefix") => try_set!(prefix, val.value(), value),
                             Meta::Path(path) if path.is_ident("default") => default = true,
+                            Meta::Path(path) if path.is_ident("flatten") => flatten = true,
                             u => fail!(u, "unexpected attribute"),
                         },
                         u => fail!(u, "unexpected attribute"),
@@ -145,7 +165,12 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename, default })
+    Ok(SqlxChildAttributes {
+        rename,
+        default,
+        flatten,
+        prefix,
+    })
 }
 
 pub fn check_transparent_attributes(
@@ -173,6 +198,32 @@ pub fn check_transparent_attributes(
     Ok(attributes)
 }
 
+pub fn check_row_field_attributes(field: &Field) -> syn::Result<SqlxChildAttributes> {
+    let attributes = parse_child_attributes(&field.attrs)?;
+
+    if attributes.flatten {
+        assert_attribute!(
+            attributes.rename.is_none(),
+            "unexpected #[sqlx(rename = ..)] on a #[sqlx(flatten)] field",
+            field
+        );
+
+        assert_attribute!(
+            !attributes.default,
+            "unexpected #[sqlx(default)] on a #[sqlx(flatten)] field",
+            field
+        );
+    } else {
+        assert_attribute!(
+            attributes.prefix.is_none(),
+            "#[sqlx(prefix = ..)] requires #[sqlx(flatten)]",
+            field
+        );
+    }
+
+    Ok(attributes)
+}
+
 pub fn check_enum_attributes<'a>(input: &'a DeriveInput) -> syn::Result<SqlxContainerAttributes> {
     let attributes = parse_container_attributes(&input.attrs)?;
 
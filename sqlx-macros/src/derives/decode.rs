@@ -122,27 +122,39 @@ fn expand_derive_decode_strong_enum(
     let ident = &input.ident;
     let ident_s = ident.to_string();
 
-    let value_arms = variants.iter().map(|v| -> Arm {
-        let id = &v.ident;
-        let attributes = parse_child_attributes(&v.attrs).unwrap();
+    let names: Vec<String> = variants
+        .iter()
+        .map(|v| {
+            let id = &v.ident;
+            let attributes = parse_child_attributes(&v.attrs).unwrap();
+
+            if let Some(rename) = attributes.rename {
+                rename
+            } else if let Some(pattern) = cattr.rename_all {
+                rename_all(&*id.to_string(), pattern)
+            } else {
+                id.to_string()
+            }
+        })
+        .collect();
 
-        if let Some(rename) = attributes.rename {
-            parse_quote!(#rename => Ok(#ident :: #id),)
-        } else if let Some(pattern) = cattr.rename_all {
-            let name = rename_all(&*id.to_string(), pattern);
+    let value_arms = variants.iter().zip(&names).map(|(v, name)| -> Arm {
+        let id = &v.ident;
 
-            parse_quote!(#name => Ok(#ident :: #id),)
-        } else {
-            let name = id.to_string();
-            parse_quote!(#name => Ok(#ident :: #id),)
-        }
+        parse_quote!(#name => Ok(#ident :: #id),)
     });
 
+    let expected = names
+        .iter()
+        .map(|name| format!("{:?}", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
     let values = quote! {
         match value {
             #(#value_arms)*
 
-            _ => Err(format!("invalid value {:?} for enum {}", value, #ident_s).into())
+            _ => Err(format!("invalid value {:?} for enum {}; expected one of: {}", value, #ident_s, #expected).into())
         }
     };
 
@@ -211,7 +223,9 @@ fn expand_derive_decode_struct(
         for field in fields {
             let ty = &field.ty;
 
-            predicates.push(parse_quote!(#ty: sqlx::decode::Decode<'r, sqlx::Postgres>));
+            // fields are decoded through `PgRecordDecoder::try_decode`, which needs to be able
+            // to decode from a buffer of any lifetime
+            predicates.push(parse_quote!(#ty: for<'a> sqlx::decode::Decode<'a, sqlx::Postgres>));
             predicates.push(parse_quote!(#ty: sqlx::types::Type<sqlx::Postgres>));
         }
 
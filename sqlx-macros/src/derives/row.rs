@@ -1,11 +1,11 @@
-use proc_macro2::Span;
+use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
     Fields, FieldsNamed, Lifetime, Stmt,
 };
 
-use super::attributes::parse_child_attributes;
+use super::attributes::{check_row_field_attributes, SqlxChildAttributes};
 
 pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     match &input.data {
@@ -59,45 +59,107 @@ fn expand_derive_from_row_struct(
         generics.params.insert(0, parse_quote!(#lifetime));
     }
 
+    let fields = fields
+        .iter()
+        .map(|field| Ok((field, check_row_field_attributes(field)?)))
+        .collect::<syn::Result<Vec<_>>>()?;
+
     let predicates = &mut generics.make_where_clause().predicates;
 
     predicates.push(parse_quote!(&#lifetime str: sqlx::ColumnIndex<R>));
 
-    for field in fields {
+    for (field, attributes) in &fields {
         let ty = &field.ty;
 
-        predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
-        predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
+        if attributes.flatten {
+            predicates.push(parse_quote!(#ty: sqlx::FromRow<#lifetime, R>));
+        } else {
+            predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
+            predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
+        }
     }
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = match attributes.rename {
-            Some(rename) => rename,
-            None => id.to_string().trim_start_matches("r#").to_owned(),
-        };
-        let ty = &field.ty;
+    let reads = fields
+        .iter()
+        .filter_map(|(field, attributes)| -> Option<Stmt> {
+            let id = &field.ident.as_ref()?;
+            let ty = &field.ty;
+
+            if attributes.flatten {
+                return Some(match &attributes.prefix {
+                    Some(prefix) => parse_quote!(
+                        let #id: #ty = sqlx::FromRow::from_row_with_prefix(row, #prefix)?;
+                    ),
+                    None => parse_quote!(
+                        let #id: #ty = sqlx::FromRow::from_row(row)?;
+                    ),
+                });
+            }
+
+            let id_s = column_name(id, attributes);
 
-        if attributes.default {
-            Some(
-                parse_quote!(let #id: #ty = row.try_get(#id_s).or_else(|e| match e {
+            if attributes.default {
+                Some(
+                    parse_quote!(let #id: #ty = row.try_get(#id_s).or_else(|e| match e {
                 sqlx::Error::ColumnNotFound(_) => {
                     Ok(Default::default())
                 },
                 e => Err(e)
             })?;),
-            )
-        } else {
+                )
+            } else {
+                Some(parse_quote!(
+                    let #id: #ty = row.try_get(#id_s)?;
+                ))
+            }
+        });
+
+    // the same reads, with the column names looked up behind a prefix chosen at runtime
+    let prefixed_reads = fields
+        .iter()
+        .filter_map(|(field, attributes)| -> Option<Stmt> {
+            let id = &field.ident.as_ref()?;
+            let ty = &field.ty;
+
+            if attributes.flatten {
+                let prefix = attributes.prefix.as_deref().unwrap_or("");
+
+                return Some(parse_quote!(
+                    let #id: #ty = sqlx::FromRow::from_row_with_prefix(
+                        row,
+                        &format!("{}{}", prefix, #prefix),
+                    )?;
+                ));
+            }
+
+            let id_s = column_name(id, attributes);
+
+            let missing: syn::Expr = if attributes.default {
+                parse_quote!(Default::default())
+            } else {
+                parse_quote!(return Err(sqlx::Error::ColumnNotFound(name)))
+            };
+
             Some(parse_quote!(
-                let #id: #ty = row.try_get(#id_s)?;
+                let #id: #ty = {
+                    let name = format!("{}{}", prefix, #id_s);
+
+                    match row
+                        .columns()
+                        .iter()
+                        .position(|column| sqlx::Column::name(column) == name)
+                    {
+                        Some(index) => row.try_get(index)?,
+                        None => #missing,
+                    }
+                };
             ))
-        }
-    });
+        });
 
-    let names = fields.iter().map(|field| &field.ident);
+    let names = fields.iter().map(|(field, _)| &field.ident);
+    let prefixed_names = names.clone();
 
     Ok(quote!(
         impl #impl_generics sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
@@ -108,6 +170,21 @@ fn expand_derive_from_row_struct(
                     #(#names),*
                 })
             }
+
+            fn from_row_with_prefix(row: &#lifetime R, prefix: &str) -> sqlx::Result<Self> {
+                #(#prefixed_reads)*
+
+                Ok(#ident {
+                    #(#prefixed_names),*
+                })
+            }
         }
     ))
 }
+
+fn column_name(id: &Ident, attributes: &SqlxChildAttributes) -> String {
+    match &attributes.rename {
+        Some(rename) => rename.clone(),
+        None => id.to_string().trim_start_matches("r#").to_owned(),
+    }
+}
@@ -149,6 +149,12 @@ fn expand_derive_has_sql_type_strong_enum(
                 fn type_info() -> sqlx::postgres::PgTypeInfo {
                     sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
+
+                // the labels may also be stored in a TEXT or VARCHAR column
+                fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                    *ty == <Self as sqlx::Type<sqlx::Postgres>>::type_info()
+                        || <&str as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+                }
             }
         ));
     }
@@ -182,12 +188,21 @@ fn expand_derive_has_sql_type_struct(
     if cfg!(feature = "postgres") {
         let ty_name = attributes.rename.unwrap_or_else(|| ident.to_string());
 
+        // the array type of a composite is named after it, with a leading underscore
+        let array_ty_name = format!("_{}", ty_name);
+
         tts.extend(quote!(
             impl sqlx::Type< sqlx::Postgres > for #ident {
                 fn type_info() -> sqlx::postgres::PgTypeInfo {
                     sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
             }
+
+            impl sqlx::postgres::types::PgHasArrayType for #ident {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
         ));
     }
 
@@ -66,13 +66,22 @@ pub fn quote_args<DB: DatabaseExt>(
                         }
                     };
 
+                    let is_spread = input.spread.as_ref().map_or(false, |spread| spread.is_spread(i));
+
+                    // for a spread the elements of the list are checked instead
+                    let dupe_expr = if is_spread {
+                        quote!(sqlx::ty_match::dupe_value($#name.iter().next().unwrap()))
+                    } else {
+                        quote!(sqlx::ty_match::dupe_value(&$#name))
+                    };
+
                     Ok(quote_spanned!(expr.span() =>
                         // this shouldn't actually run
                         if false {
                             use sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
 
                             // evaluate the expression only once in case it contains moves
-                            let _expr = sqlx::ty_match::dupe_value(&$#name);
+                            let _expr = #dupe_expr;
 
                             // if `_expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
                             let ty_check = sqlx::ty_match::WrapSame::<#param_ty, _>::new(&_expr).wrap_same();
@@ -92,17 +101,63 @@ pub fn quote_args<DB: DatabaseExt>(
 
     let args_count = input.arg_names.len();
 
+    let spread = match &input.spread {
+        Some(spread) => spread,
+        None => {
+            return Ok(quote! {
+                #args_check
+
+                // bind as a local expression, by-ref
+                #(let #arg_name = &$#arg_name;)*
+                let mut query_args = <#db_path as sqlx::database::HasArguments>::Arguments::default();
+                query_args.reserve(
+                    #args_count,
+                    0 #(+ sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
+                );
+                #(query_args.add(#arg_name);)*
+            });
+        }
+    };
+
+    // each element of a spread list is bound on its own, and counted to expand the SQL
+    let (size_hints, binds): (Vec<_>, Vec<_>) = arg_name
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if spread.is_spread(i) {
+                (
+                    quote!(0),
+                    quote! {
+                        let mut len = 0_usize;
+
+                        for value in #name.iter() {
+                            query_args.add(value);
+                            len += 1;
+                        }
+
+                        len
+                    },
+                )
+            } else {
+                (
+                    quote!(sqlx::encode::Encode::<#db_path>::size_hint(#name)),
+                    quote! {
+                        query_args.add(#name);
+                        1_usize
+                    },
+                )
+            }
+        })
+        .unzip();
+
     Ok(quote! {
         #args_check
 
         // bind as a local expression, by-ref
         #(let #arg_name = &$#arg_name;)*
         let mut query_args = <#db_path as sqlx::database::HasArguments>::Arguments::default();
-        query_args.reserve(
-            #args_count,
-            0 #(+ sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
-        );
-        #(query_args.add(#arg_name);)*
+        query_args.reserve(#args_count, 0 #(+ #size_hints)*);
+        let query_lens = [#({ #binds }),*];
     })
 }
 
@@ -71,6 +71,37 @@ pub mod offline {
             })
             .map_err(Into::into)
         }
+
+        /// Deserialize the data for this query from its own file, e.g. `.sqlx/query-<hash>.json`.
+        /// The expected structure is a JSON map with a `"db"` key next to the query data.
+        pub fn from_query_file(path: impl AsRef<Path>, query: &str) -> crate::Result<Self> {
+            #[derive(serde::Deserialize)]
+            struct QueryFile {
+                db: String,
+                #[serde(flatten)]
+                data: DynQueryData,
+            }
+
+            let file: QueryFile =
+                serde_json::from_reader(File::open(path.as_ref()).map_err(|e| {
+                    format!("failed to open path {}: {}", path.as_ref().display(), e)
+                })?)?;
+
+            let mut query_data = file.data;
+
+            if query_data.query != query {
+                return Err(format!(
+                    "hash collision for stored queries:\n{:?}\n{:?}",
+                    query, query_data.query
+                )
+                .into());
+            }
+
+            query_data.db_name = file.db;
+            query_data.hash = hash_string(query);
+
+            Ok(query_data)
+        }
     }
 
     impl<DB: DatabaseExt> QueryData<DB>
@@ -185,3 +216,48 @@ pub mod offline {
         }
     }
 }
+
+#[cfg(all(test, feature = "offline"))]
+mod tests {
+    use super::offline::{hash_string, DynQueryData};
+    use std::fs;
+
+    // the layout of the files written by `cargo sqlx prepare --sharded`
+    #[test]
+    fn it_reads_query_files() -> crate::Result<()> {
+        let query = "SELECT 1";
+        let hash = hash_string(query);
+
+        let path = std::env::temp_dir().join(format!(
+            "sqlx-macros-query-{}-{}.json",
+            hash,
+            std::process::id()
+        ));
+
+        fs::write(
+            &path,
+            serde_json::to_vec(&serde_json::json!({
+                "db": "SQLite",
+                "query": query,
+                "describe": { "columns": [], "parameters": null, "nullable": [] },
+                "hash": hash,
+            }))?,
+        )?;
+
+        let data = DynQueryData::from_query_file(&path, query);
+        let collision = DynQueryData::from_query_file(&path, "SELECT 2");
+
+        fs::remove_file(&path)?;
+
+        let data = data?;
+
+        assert_eq!(data.db_name, "SQLite");
+        assert_eq!(data.query, query);
+        assert_eq!(data.hash, hash);
+
+        // the file of another query with the same hash is rejected
+        assert!(collision.is_err());
+
+        Ok(())
+    }
+}
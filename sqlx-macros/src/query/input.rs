@@ -1,20 +1,29 @@
 use std::env;
+use std::ffi::OsStr;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use proc_macro2::{Ident, Span};
-use quote::format_ident;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{Expr, LitBool, LitStr, Token};
 use syn::{ExprArray, Type};
 
+use super::spread::SpreadSql;
+
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
     pub(super) src: String,
 
-    #[cfg_attr(not(feature = "offline"), allow(dead_code))]
+    // set if the query has spread placeholders (`?..` or `$N..`)
+    pub(super) spread: Option<SpreadSql>,
+
     pub(super) src_span: Span,
 
+    // the file the query was read from, for `query_file!()`
+    pub(super) src_file: Option<PathBuf>,
+
     pub(super) record_type: RecordType,
 
     // `arg0 .. argN` for N arguments
@@ -31,6 +40,7 @@ enum QuerySrc {
 
 pub enum RecordType {
     Given(Type),
+    Scalar,
     Generated,
 }
 
@@ -67,6 +77,10 @@ impl Parse for QueryMacroInput {
                 args = Some(exprs.elems.into_iter().collect())
             } else if key == "record" {
                 record_type = RecordType::Given(input.parse()?);
+            } else if key == "scalar" {
+                // we currently expect only `scalar = _`
+                input.parse::<Token![_]>()?;
+                record_type = RecordType::Scalar;
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
@@ -86,9 +100,14 @@ impl Parse for QueryMacroInput {
             .map(|i| format_ident!("arg{}", i))
             .collect();
 
+        let (src, src_file) = src.resolve(src_span)?;
+
         Ok(QueryMacroInput {
-            src: src.resolve(src_span)?,
+            src,
+            // parsed in `expand_input` so that errors are reported like the others
+            spread: None,
             src_span,
+            src_file,
             record_type,
             arg_names,
             arg_exprs,
@@ -97,60 +116,158 @@ impl Parse for QueryMacroInput {
     }
 }
 
+impl QueryMacroInput {
+    /// The query to describe, with a single placeholder for each spread one.
+    pub(super) fn describe_src(&self) -> &str {
+        self.spread
+            .as_ref()
+            .map_or(&*self.src, |spread| &*spread.describe_src)
+    }
+
+    /// Items that make Cargo rebuild the crate when the query file or `SQLX_QUERY_DIR` change.
+    pub(super) fn quote_rebuild_tracking(&self) -> TokenStream {
+        let path = match self.src_file.as_ref().and_then(|path| path.to_str()) {
+            Some(path) => path,
+            None => return TokenStream::new(),
+        };
+
+        quote! {
+            const _: &str = include_str!(#path);
+            const _: Option<&str> = option_env!("SQLX_QUERY_DIR");
+        }
+    }
+
+    /// An expression of the SQL to execute, which expands the spread placeholders at runtime.
+    pub(super) fn quote_sql(&self) -> TokenStream {
+        match &self.spread {
+            Some(spread) => spread.quote_sql(),
+            None => {
+                let sql = &self.src;
+                quote!(#sql)
+            }
+        }
+    }
+}
+
 impl QuerySrc {
-    /// If the query source is a file, read it to a string. Otherwise return the query string.
-    fn resolve(self, source_span: Span) -> syn::Result<String> {
+    /// If the query source is a file, read it to a string and return its path as well.
+    /// Otherwise return the query string.
+    fn resolve(self, source_span: Span) -> syn::Result<(String, Option<PathBuf>)> {
         match self {
-            QuerySrc::String(string) => Ok(string),
-            QuerySrc::File(file) => read_file_src(&file, source_span),
+            QuerySrc::String(string) => Ok((string, None)),
+            QuerySrc::File(file) => {
+                let (src, file_path) = read_file_src(&file, source_span)?;
+                Ok((src, Some(file_path)))
+            }
         }
     }
 }
 
-fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
-    use std::path::Path;
+fn read_file_src(source: &str, source_span: Span) -> syn::Result<(String, PathBuf)> {
+    let file_path = resolve_file_path(
+        Path::new(source),
+        env::var_os("CARGO_MANIFEST_DIR").as_deref(),
+        env::var_os("SQLX_QUERY_DIR").as_deref(),
+    )
+    .map_err(|message| syn::Error::new(source_span, message))?;
+
+    let src = fs::read_to_string(&file_path).map_err(|e| {
+        syn::Error::new(
+            source_span,
+            format!(
+                "failed to read query file at {}: {}",
+                file_path.display(),
+                e
+            ),
+        )
+    })?;
 
-    let path = Path::new(source);
+    Ok((src, file_path))
+}
 
+// an absolute path is used as it is; a relative one is resolved against `query_dir` (itself
+// relative to the project root, `manifest_dir`) or else the project root
+fn resolve_file_path(
+    path: &Path,
+    manifest_dir: Option<&OsStr>,
+    query_dir: Option<&OsStr>,
+) -> Result<PathBuf, &'static str> {
     if path.is_absolute() {
-        return Err(syn::Error::new(
-            source_span,
-            "absolute paths will only work on the current machine",
-        ));
+        return Ok(path.to_path_buf());
     }
 
     // requires `proc_macro::SourceFile::path()` to be stable
     // https://github.com/rust-lang/rust/issues/54725
-    if path.is_relative()
+    if query_dir.is_none()
         && !path
             .parent()
             .map_or(false, |parent| !parent.as_os_str().is_empty())
     {
-        return Err(syn::Error::new(
-            source_span,
-            "paths relative to the current file's directory are not currently supported",
-        ));
+        return Err("paths relative to the current file's directory are not currently supported");
     }
 
-    let base_dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| {
-        syn::Error::new(
-            source_span,
-            "CARGO_MANIFEST_DIR is not set; please use Cargo to build",
-        )
-    })?;
+    let base_dir =
+        Path::new(manifest_dir.ok_or("CARGO_MANIFEST_DIR is not set; please use Cargo to build")?);
 
-    let base_dir_path = Path::new(&base_dir);
+    Ok(match query_dir {
+        Some(query_dir) => base_dir.join(query_dir).join(path),
+        None => base_dir.join(path),
+    })
+}
 
-    let file_path = base_dir_path.join(path);
+#[cfg(test)]
+mod tests {
+    use super::resolve_file_path;
+    use std::ffi::OsStr;
+    use std::path::{Path, PathBuf};
 
-    fs::read_to_string(&file_path).map_err(|e| {
-        syn::Error::new(
-            source_span,
-            format!(
-                "failed to read query file at {}: {}",
-                file_path.display(),
-                e
-            ),
+    fn resolve(path: &str, query_dir: Option<&str>) -> Result<PathBuf, &'static str> {
+        resolve_file_path(
+            Path::new(path),
+            Some(OsStr::new("/project")),
+            query_dir.map(OsStr::new),
         )
-    })
+    }
+
+    #[test]
+    fn it_resolves_paths_against_the_project_root() {
+        assert_eq!(
+            resolve("queries/select.sql", None),
+            Ok(PathBuf::from("/project/queries/select.sql"))
+        );
+
+        // file names alone would be relative to the current file, which can't be known
+        assert!(resolve("select.sql", None).is_err());
+    }
+
+    #[test]
+    fn it_resolves_paths_against_the_query_dir() {
+        assert_eq!(
+            resolve("select.sql", Some("queries")),
+            Ok(PathBuf::from("/project/queries/select.sql"))
+        );
+
+        assert_eq!(
+            resolve("users/select.sql", Some("/queries")),
+            Ok(PathBuf::from("/queries/users/select.sql"))
+        );
+    }
+
+    #[test]
+    fn it_uses_absolute_paths_as_they_are() {
+        assert_eq!(
+            resolve("/queries/select.sql", Some("queries")),
+            Ok(PathBuf::from("/queries/select.sql"))
+        );
+
+        assert_eq!(
+            resolve_file_path(Path::new("/queries/select.sql"), None, None),
+            Ok(PathBuf::from("/queries/select.sql"))
+        );
+    }
+
+    #[test]
+    fn it_requires_the_manifest_dir_for_relative_paths() {
+        assert!(resolve_file_path(Path::new("queries/select.sql"), None, None).is_err());
+    }
 }
@@ -15,14 +15,19 @@ use sqlx_rt::block_on;
 use crate::database::DatabaseExt;
 use crate::query::data::QueryData;
 use crate::query::input::RecordType;
+use crate::query::spread::SpreadSql;
 use either::Either;
 
 mod args;
 mod data;
 mod input;
 mod output;
+mod spread;
+
+pub fn expand_input(mut input: QueryMacroInput) -> crate::Result<TokenStream> {
+    input.spread =
+        SpreadSql::parse(&input.src).map_err(|e| syn::Error::new(input.src_span, e.to_string()))?;
 
-pub fn expand_input(input: QueryMacroInput) -> crate::Result<TokenStream> {
     let manifest_dir =
         env::var("CARGO_MANIFEST_DIR").map_err(|_| "`CARGO_MANIFEST_DIR` must be set")?;
 
@@ -34,25 +39,57 @@ pub fn expand_input(input: QueryMacroInput) -> crate::Result<TokenStream> {
             .map_err(|e| format!("failed to load environment from {:?}, {}", env_path, e))?
     }
 
+    // `SQLX_OFFLINE` forces the use of the saved query data, even if `DATABASE_URL` is set
+    let offline = dotenv::var("SQLX_OFFLINE").map_or(false, |s| s != "false" && s != "0");
+
     // if `dotenv` wasn't initialized by the above we make sure to do it here
     match dotenv::var("DATABASE_URL").ok() {
-        Some(db_url) => expand_from_db(input, &db_url),
+        Some(db_url) if !offline => expand_from_db(input, &db_url),
 
         #[cfg(feature = "offline")]
-        None => {
-            let data_file_path = std::path::Path::new(&manifest_dir).join("sqlx-data.json");
-
-            if data_file_path.exists() {
-                expand_from_file(input, data_file_path)
-            } else {
-                Err(
+        _ => {
+            use data::offline::{hash_string, DynQueryData};
+
+            let manifest_dir = std::path::Path::new(&manifest_dir);
+
+            // saved by `cargo sqlx prepare --sharded`
+            let query_file_path = manifest_dir
+                .join(".sqlx")
+                .join(format!("query-{}.json", hash_string(input.describe_src())));
+
+            let data_file_path = manifest_dir.join("sqlx-data.json");
+
+            // neither location is preferred, as that would silently pick data that has been
+            // left behind by the other
+            match (query_file_path.exists(), data_file_path.exists()) {
+                (true, true) => Err(format!(
+                    "found data for this query in `{}` but `{}` exists as well; delete the \
+                     one that is no longer kept up to date by `cargo sqlx prepare`",
+                    query_file_path.display(),
+                    data_file_path.display()
+                )
+                .into()),
+                (true, false) => {
+                    let query_data =
+                        DynQueryData::from_query_file(query_file_path, input.describe_src())?;
+                    expand_from_file(input, query_data)
+                }
+                (false, true) => {
+                    let query_data =
+                        DynQueryData::from_data_file(data_file_path, input.describe_src())?;
+                    expand_from_file(input, query_data)
+                }
+                (false, false) => Err(
                     "`DATABASE_URL` must be set, or `cargo sqlx prepare` must have been run \
-                     and sqlx-data.json must exist, to use query macros"
+                     and sqlx-data.json (or .sqlx/) must exist, to use query macros"
                         .into(),
-                )
+                ),
             }
         }
 
+        #[cfg(not(feature = "offline"))]
+        Some(_) => Err("`SQLX_OFFLINE` is set but the `offline` feature is not enabled".into()),
+
         #[cfg(not(feature = "offline"))]
         None => Err("`DATABASE_URL` must be set to use query macros".into()),
     }
@@ -69,7 +106,7 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
         "postgres" | "postgresql" => {
             let data = block_on(async {
                 let mut conn = sqlx_core::postgres::PgConnection::connect(db_url.as_str()).await?;
-                QueryData::from_db(&mut conn, &input.src).await
+                QueryData::from_db(&mut conn, input.describe_src()).await
             })?;
 
             expand_with_data(input, data)
@@ -82,7 +119,7 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
         "mssql" | "sqlserver" => {
             let data = block_on(async {
                 let mut conn = sqlx_core::mssql::MssqlConnection::connect(db_url.as_str()).await?;
-                QueryData::from_db(&mut conn, &input.src).await
+                QueryData::from_db(&mut conn, input.describe_src()).await
             })?;
 
             expand_with_data(input, data)
@@ -95,7 +132,7 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
         "mysql" | "mariadb" => {
             let data = block_on(async {
                 let mut conn = sqlx_core::mysql::MySqlConnection::connect(db_url.as_str()).await?;
-                QueryData::from_db(&mut conn, &input.src).await
+                QueryData::from_db(&mut conn, input.describe_src()).await
             })?;
 
             expand_with_data(input, data)
@@ -108,7 +145,7 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
         "sqlite" => {
             let data = block_on(async {
                 let mut conn = sqlx_core::sqlite::SqliteConnection::connect(db_url.as_str()).await?;
-                QueryData::from_db(&mut conn, &input.src).await
+                QueryData::from_db(&mut conn, input.describe_src()).await
             })?;
 
             expand_with_data(input, data)
@@ -124,11 +161,8 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
 #[cfg(feature = "offline")]
 pub fn expand_from_file(
     input: QueryMacroInput,
-    file: std::path::PathBuf,
+    query_data: data::offline::DynQueryData,
 ) -> crate::Result<TokenStream> {
-    use data::offline::DynQueryData;
-
-    let query_data = DynQueryData::from_data_file(file, &input.src)?;
     assert!(!query_data.db_name.is_empty());
 
     match &*query_data.db_name {
@@ -200,9 +234,24 @@ where
 
     let query_args = format_ident!("query_args");
 
-    let output = if data.describe.columns().is_empty() {
+    let output = if let RecordType::Scalar = input.record_type {
+        let columns = output::columns_to_rust::<DB>(&data.describe)?;
+
+        if columns.len() != 1 {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "expected exactly 1 column for `query_scalar!()`, got {}",
+                    columns.len()
+                ),
+            )
+            .into());
+        }
+
+        output::quote_query_scalar::<DB>(&input, &query_args, &columns[0])
+    } else if data.describe.columns().is_empty() {
         let db_path = DB::db_path();
-        let sql = &input.src;
+        let sql = input.quote_sql();
 
         quote! {
             sqlx::query_with::<#db_path, _>(#sql, #query_args)
@@ -240,6 +289,7 @@ where
                 (Cow::Owned(record_name), record_tokens)
             }
             RecordType::Given(ref out_ty) => (Cow::Borrowed(out_ty), quote!()),
+            RecordType::Scalar => unreachable!("(bug) scalar output is expanded above"),
         };
 
         record_tokens.extend(output::quote_query_as::<DB>(
@@ -253,12 +303,15 @@ where
     };
 
     let arg_names = &input.arg_names;
+    let rebuild_tracking = input.quote_rebuild_tracking();
 
     let ret_tokens = quote! {
         macro_rules! macro_result {
             (#($#arg_names:expr),*) => {{
                 use sqlx::Arguments as _;
 
+                #rebuild_tracking
+
                 #args_tokens
 
                 #output
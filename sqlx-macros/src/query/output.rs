@@ -112,7 +112,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
 
     let db_path = DB::db_path();
     let row_path = DB::row_path();
-    let sql = &input.src;
+    let sql = input.quote_sql();
 
     quote! {
         sqlx::query_with::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
@@ -124,6 +124,25 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+pub fn quote_query_scalar<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    bind_args: &Ident,
+    column: &RustColumn,
+) -> TokenStream {
+    // a wildcard override (or the `_unchecked!()` variant) leaves the type to be inferred
+    let ty = match (input.checked, &column.type_) {
+        (true, Some(type_)) => quote!(#type_),
+        _ => quote!(_),
+    };
+
+    let db_path = DB::db_path();
+    let sql = input.quote_sql();
+
+    quote! {
+        sqlx::query_scalar_with::<#db_path, #ty, _>(#sql, #bind_args)
+    }
+}
+
 fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
     let type_info = &*column.type_info();
 
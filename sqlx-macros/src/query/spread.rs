@@ -0,0 +1,283 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// A query with spread placeholders (`?..`, or `$N..` for Postgres), each of which expands to
+/// one placeholder per element of the list bound to it.
+pub struct SpreadSql {
+    /// The query with a single placeholder in place of each spread, to be described.
+    pub(super) describe_src: String,
+
+    pieces: Vec<Piece>,
+
+    // `$N` placeholders (Postgres) instead of positional `?` ones
+    numbered: bool,
+}
+
+enum Piece {
+    Sql(String),
+    Arg(usize),
+    Spread(usize),
+}
+
+enum Token {
+    Sql(String),
+    Placeholder {
+        // the number of a `$N` placeholder or `None` for `?`
+        number: Option<usize>,
+        src: String,
+        spread: bool,
+    },
+}
+
+impl SpreadSql {
+    /// Returns `None` if the query has no spread placeholders.
+    pub fn parse(src: &str) -> crate::Result<Option<Self>> {
+        let tokens = tokenize(src);
+
+        if !tokens
+            .iter()
+            .any(|token| matches!(token, Token::Placeholder { spread: true, .. }))
+        {
+            return Ok(None);
+        }
+
+        // in Postgres `?` is an operator
+        let numbered = tokens.iter().any(|token| {
+            matches!(
+                token,
+                Token::Placeholder {
+                    number: Some(_),
+                    ..
+                }
+            )
+        });
+
+        let mut describe_src = String::with_capacity(src.len());
+        let mut pieces = Vec::new();
+        let mut single = Vec::new();
+        let mut spread = Vec::new();
+        let mut next_arg = 0;
+
+        for token in tokens {
+            let (arg, src, is_spread) = match token {
+                Token::Placeholder {
+                    number: Some(number),
+                    src,
+                    spread,
+                } if numbered => (number - 1, src, spread),
+
+                Token::Placeholder {
+                    number: None,
+                    src,
+                    spread,
+                } if !numbered => {
+                    next_arg += 1;
+                    (next_arg - 1, src, spread)
+                }
+
+                Token::Placeholder { src: sql, .. } | Token::Sql(sql) => {
+                    describe_src.push_str(&sql);
+
+                    match pieces.last_mut() {
+                        Some(Piece::Sql(last)) => last.push_str(&sql),
+                        _ => pieces.push(Piece::Sql(sql)),
+                    }
+
+                    continue;
+                }
+            };
+
+            describe_src.push_str(&src);
+
+            if is_spread {
+                spread.push(arg);
+                pieces.push(Piece::Spread(arg));
+            } else {
+                single.push(arg);
+                pieces.push(Piece::Arg(arg));
+            }
+        }
+
+        if let Some(arg) = spread.iter().find(|arg| single.contains(arg)) {
+            return Err(format!(
+                "parameter ${} is used both as a list (`${}..`) and as a single value",
+                arg + 1,
+                arg + 1
+            )
+            .into());
+        }
+
+        Ok(Some(SpreadSql {
+            describe_src,
+            pieces,
+            numbered,
+        }))
+    }
+
+    pub(super) fn is_spread(&self, arg: usize) -> bool {
+        self.pieces
+            .iter()
+            .any(|piece| matches!(piece, Piece::Spread(spread) if *spread == arg))
+    }
+
+    /// An expression building the SQL from the number of values bound for each argument,
+    /// in `query_lens`.
+    pub(super) fn quote_sql(&self) -> TokenStream {
+        let pieces = self.pieces.iter().map(|piece| match piece {
+            Piece::Sql(sql) => quote!(sqlx::spread::Piece::Sql(#sql)),
+            Piece::Arg(arg) => quote!(sqlx::spread::Piece::Arg(#arg)),
+            Piece::Spread(arg) => quote!(sqlx::spread::Piece::Spread(#arg)),
+        });
+
+        let numbered = self.numbered;
+
+        // the query only borrows its SQL and may outlive the expression the macro is used in
+        quote! {
+            sqlx::spread::expand_sql_static(&[#(#pieces),*], &query_lens, #numbered)
+        }
+    }
+}
+
+// split the query at its placeholders, skipping over quoted strings, identifiers and comments
+fn tokenize(src: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut sql = String::new();
+    let mut rest = src;
+
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '\'' | '"' | '`' => rest[1..].find(c).map_or(rest.len(), |end| end + 2),
+            '-' if rest.starts_with("--") => rest.find('\n').map_or(rest.len(), |end| end + 1),
+            '/' if rest.starts_with("/*") => rest.find("*/").map_or(rest.len(), |end| end + 2),
+
+            '?' | '$' => {
+                let digits = rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len() - 1);
+
+                if c == '$' && digits == 0 {
+                    dollar_quoted_len(rest).unwrap_or(1)
+                } else {
+                    let number = if c == '$' {
+                        rest[1..=digits].parse().ok()
+                    } else if digits == 0 {
+                        None
+                    } else {
+                        // `?NNN` (SQLite) is left as it is
+                        sql.push_str(&rest[..=digits]);
+                        rest = &rest[digits + 1..];
+                        continue;
+                    };
+
+                    let end = digits + 1;
+                    let spread = rest[end..].starts_with("..");
+
+                    if !sql.is_empty() {
+                        tokens.push(Token::Sql(std::mem::take(&mut sql)));
+                    }
+
+                    tokens.push(Token::Placeholder {
+                        number,
+                        src: rest[..end].to_owned(),
+                        spread,
+                    });
+
+                    rest = &rest[end + if spread { 2 } else { 0 }..];
+                    continue;
+                }
+            }
+
+            c => c.len_utf8(),
+        };
+
+        sql.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    if !sql.is_empty() {
+        tokens.push(Token::Sql(sql));
+    }
+
+    tokens
+}
+
+// the length of a Postgres dollar-quoted string (`$$...$$` or `$tag$...$tag$`) at the start
+fn dollar_quoted_len(src: &str) -> Option<usize> {
+    let tag_len = src[1..].find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+
+    if !src[1 + tag_len..].starts_with('$') {
+        return None;
+    }
+
+    let tag = &src[..tag_len + 2];
+    let body = &src[tag.len()..];
+
+    Some(
+        body.find(tag)
+            .map_or(src.len(), |end| tag.len() + end + tag.len()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpreadSql;
+
+    fn describe_src(src: &str) -> String {
+        SpreadSql::parse(src).unwrap().unwrap().describe_src
+    }
+
+    #[test]
+    fn it_ignores_queries_without_spreads() {
+        assert!(SpreadSql::parse("SELECT * FROM t WHERE a = ? AND b IN (?)")
+            .unwrap()
+            .is_none());
+
+        assert!(SpreadSql::parse("SELECT '?..', \"$1..\" /* ?.. */ -- $1..")
+            .unwrap()
+            .is_none());
+
+        assert!(SpreadSql::parse("SELECT $body$ $1.. $body$")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn it_parses_positional_spreads() {
+        let sql = SpreadSql::parse("SELECT * FROM t WHERE a = ? AND b IN (?..) AND c = '?..'")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            sql.describe_src,
+            "SELECT * FROM t WHERE a = ? AND b IN (?) AND c = '?..'"
+        );
+        assert!(!sql.numbered);
+        assert!(!sql.is_spread(0));
+        assert!(sql.is_spread(1));
+    }
+
+    #[test]
+    fn it_parses_numbered_spreads() {
+        let sql = SpreadSql::parse("SELECT * FROM t WHERE b IN ($2..) AND a = $1 AND j ? 'k'")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            sql.describe_src,
+            "SELECT * FROM t WHERE b IN ($2) AND a = $1 AND j ? 'k'"
+        );
+        assert!(sql.numbered);
+        assert!(!sql.is_spread(0));
+        assert!(sql.is_spread(1));
+
+        assert_eq!(
+            describe_src("SELECT $1..::int4[], $$ $2.. $$"),
+            "SELECT $1::int4[], $$ $2.. $$"
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_parameter_used_as_a_list_and_a_value() {
+        assert!(SpreadSql::parse("SELECT * FROM t WHERE a IN ($1..) OR b = $1").is_err());
+    }
+}
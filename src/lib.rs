@@ -11,10 +11,13 @@ pub use sqlx_core::from_row::FromRow;
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{query, query_with};
 pub use sqlx_core::query_as::{query_as, query_as_with};
+pub use sqlx_core::query_builder::QueryBuilder;
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
 pub use sqlx_core::row::{ColumnIndex, Row};
-pub use sqlx_core::statement::StatementInfo;
-pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::statement::{Statement, StatementInfo};
+pub use sqlx_core::transaction::{
+    IsolationLevel, Transaction, TransactionManager, TransactionOptions,
+};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
@@ -73,6 +76,10 @@ pub mod ty_match;
 #[doc(hidden)]
 pub mod result_ext;
 
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod spread;
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
@@ -109,7 +116,7 @@ pub use self::encode::Encode;
 
 /// Provides [`Decode`](decode/trait.Decode.html) for decoding values from the database.
 pub mod decode {
-    pub use sqlx_core::decode::Decode;
+    pub use sqlx_core::decode::{Decode, DecodeLossy};
 
     #[cfg(feature = "macros")]
     #[doc(hidden)]
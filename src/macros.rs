@@ -83,6 +83,29 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL: `?` which matches arguments in order that it appears in the query
 ///
+/// ## Binding Lists (`IN (...)`)
+/// A placeholder followed by `..` (`?..`, or `$N..` in Postgres) is a spread: it is bound to a
+/// list, such as a `Vec<T>` or `&[T]`, and expands to one placeholder for each element when the
+/// query is executed. The elements are checked against the type of the placeholder as if it
+/// was not spread.
+///
+/// ```rust,ignore
+/// let ids: Vec<i64> = vec![1, 2, 3];
+///
+/// // executed as `select * from accounts where is_active = $1 and id in ($2, $3, $4)`
+/// sqlx::query!("select * from accounts where is_active = $1 and id in ($2..)", true, ids)
+/// ```
+///
+/// In Postgres the placeholders after a spread are renumbered to follow its elements; a `$N`
+/// that is spread may not also be used on its own. An empty list is expanded to `NULL`, so
+/// `IN (?..)` matches no rows and, as `NULL` is not comparable, neither does `NOT IN (?..)`.
+///
+/// As the SQL is built when the query is created, a query with a spread must be executed in
+/// the same statement (e.g. `sqlx::query!(...).fetch_all(&mut conn).await?`).
+///
+/// In Postgres a list can also be bound as an array and compared against with `= ANY($1)`,
+/// which doesn't change the SQL for each length of the list.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -305,6 +328,12 @@ macro_rules! query_unchecked (
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
+/// To keep queries elsewhere, e.g. in a directory shared by the crates of a workspace, set the
+/// `SQLX_QUERY_DIR` environment variable to the directory that relative paths should be resolved
+/// against instead. `SQLX_QUERY_DIR` may itself be absolute or relative to the project root.
+/// Absolute paths to a query file are used as they are, but will only work on machines with
+/// the same layout.
+///
 /// -----
 ///
 /// `examples/queries/account-by-id.sql`:
@@ -390,6 +419,11 @@ macro_rules! query_file_unchecked (
 /// * The column names of the query must match the field names of the struct.
 /// * Neither the query nor the struct may have unused fields.
 ///
+/// Because columns are matched to fields by name, reordering the columns of the query does not
+/// change which field receives which column. A field without a column of the same name (or a
+/// column without a field) is an error at compile time, so renaming a column in the query must
+/// be matched by renaming the field (or aliasing the column with `AS`).
+///
 /// The only modification to the syntax is that the struct name is given before the SQL string:
 /// ```rust,ignore
 /// # use sqlx::Connect;
@@ -573,3 +607,107 @@ macro_rules! query_file_as_unchecked (
         macro_result!($($args),*)
     })
 );
+
+/// A variant of [query!] which expects a single column from the query and evaluates to an
+/// instance of [QueryScalar][crate::query::QueryScalar].
+///
+/// Each row is decoded directly into the type of the column, so `fetch_all`, `fetch_one` and
+/// `fetch_optional` return `Vec<T>`, `T` and `Option<T>`. It is an error at compile time if the
+/// query does not produce exactly one column.
+///
+/// The column type overrides of [query!] are supported. A wildcard override like in [query_as!]
+/// (`"id: _"`) leaves the output type up to inference.
+///
+/// ```rust,ignore
+/// let ids: Vec<i64> = sqlx::query_scalar!("SELECT id FROM accounts ORDER BY id")
+///     .fetch_all(&mut conn)
+///     .await?;
+///
+/// let count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM accounts"#)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// See [query!] for more information.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_scalar (
+    ($query:expr) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source = $query);
+        }
+        macro_result!()
+    });
+    ($query:expr, $($args:expr),*$(,)?) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args),*]);
+        }
+        macro_result!($($args),*)
+    })
+);
+
+/// A variant of [query_scalar!] which takes a file path like [query_file!].
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_scalar (
+    ($path:literal) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path);
+        }
+        macro_result!()
+    });
+    ($path:literal, $($args:tt),*$(,)?) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, args = [$($args),*]);
+        }
+        macro_result!($($args),*)
+    })
+);
+
+/// A variant of [query_scalar!] which does not typecheck bind parameters and leaves the output type
+/// to inference. The query must still be valid for the database server and must still produce
+/// exactly one column.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_scalar_unchecked (
+    ($query:expr) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source = $query, checked = false);
+        }
+        macro_result!()
+    });
+    ($query:expr, $($args:expr),*$(,)?) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source = $query, args = [$($args),*], checked = false);
+        }
+        macro_result!($($args),*)
+    })
+);
+
+/// A variant of [query_file_scalar!] which does not typecheck bind parameters and leaves the output
+/// type to inference. The query must still be valid for the database server and must still produce
+/// exactly one column.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_scalar_unchecked (
+    ($path:literal) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, checked = false);
+        }
+        macro_result!()
+    });
+    ($path:literal, $($args:tt),*$(,)?) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, args = [$($args),*], checked = false);
+        }
+        macro_result!($($args),*)
+    })
+);
@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+// Support for spread placeholders (`?..` or `$N..`) in `query!()` and friends, which expand
+// to one placeholder for each element of the list bound to them.
+
+// The macros split the query at its placeholders; the SQL sent to the database is only known
+// once the lengths of the bound lists are.
+pub enum Piece {
+    Sql(&'static str),
+
+    // a placeholder for the argument at this index
+    Arg(usize),
+
+    // a placeholder for each element of the list at this argument index
+    Spread(usize),
+}
+
+// Write the pieces of the query into SQL, given the number of values bound for each
+// argument (`1` for arguments that are not spread).
+//
+// If `numbered`, the placeholders are written as `$N` and renumbered so that those of the
+// arguments after a list follow the ones of its elements. An empty list is written as `NULL`.
+pub fn expand_sql(pieces: &[Piece], lens: &[usize], numbered: bool) -> String {
+    let mut starts = Vec::with_capacity(lens.len());
+    let mut next = 1;
+
+    for len in lens {
+        starts.push(next);
+        next += len;
+    }
+
+    let mut sql = String::new();
+
+    for piece in pieces {
+        let (arg, len) = match *piece {
+            Piece::Sql(s) => {
+                sql.push_str(s);
+                continue;
+            }
+
+            Piece::Arg(arg) => (arg, 1),
+            Piece::Spread(arg) => (arg, lens[arg]),
+        };
+
+        if len == 0 {
+            sql.push_str("NULL");
+        }
+
+        for i in 0..len {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+
+            if numbered {
+                let _ = write!(sql, "${}", starts[arg] + i);
+            } else {
+                sql.push('?');
+            }
+        }
+    }
+
+    sql
+}
+
+thread_local! {
+    static EXPANDED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+// Expand the query like `expand_sql()`, into a string that lives as long as the program.
+//
+// The query borrows its SQL, and the macros have nowhere to keep an expansion for as long as
+// the query they return is kept. Each distinct expansion is leaked once, so executing the
+// same query with lists of the same lengths does not leak again.
+pub fn expand_sql_static(pieces: &[Piece], lens: &[usize], numbered: bool) -> &'static str {
+    let sql = expand_sql(pieces, lens, numbered);
+
+    EXPANDED.with(|expanded| {
+        let mut expanded = expanded.borrow_mut();
+
+        if let Some(sql) = expanded.get(&*sql) {
+            return *sql;
+        }
+
+        let sql: &'static str = Box::leak(sql.into_boxed_str());
+        expanded.insert(sql);
+
+        sql
+    })
+}
+
+#[test]
+fn test_expand_sql() {
+    use Piece::*;
+
+    let pieces = [
+        Sql("SELECT * FROM t WHERE a = "),
+        Arg(0),
+        Sql(" AND b IN ("),
+        Spread(1),
+        Sql(") AND c = "),
+        Arg(2),
+    ];
+
+    assert_eq!(
+        expand_sql(&pieces, &[1, 3, 1], true),
+        "SELECT * FROM t WHERE a = $1 AND b IN ($2, $3, $4) AND c = $5"
+    );
+
+    assert_eq!(
+        expand_sql(&pieces, &[1, 3, 1], false),
+        "SELECT * FROM t WHERE a = ? AND b IN (?, ?, ?) AND c = ?"
+    );
+
+    assert_eq!(
+        expand_sql(&pieces, &[1, 0, 1], true),
+        "SELECT * FROM t WHERE a = $1 AND b IN (NULL) AND c = $2"
+    );
+}
+
+#[test]
+fn test_expand_sql_static() {
+    use Piece::*;
+
+    let pieces = [Sql("SELECT * FROM t WHERE a IN ("), Spread(0), Sql(")")];
+
+    let sql = expand_sql_static(&pieces, &[2], false);
+
+    assert_eq!(sql, "SELECT * FROM t WHERE a IN (?, ?)");
+    assert!(std::ptr::eq(sql, expand_sql_static(&pieces, &[2], false)));
+}
@@ -1,4 +1,4 @@
-use sqlx::any::AnyRow;
+use sqlx::any::{AnyKind, AnyRow};
 use sqlx::{Any, Connection, Executor, Row};
 use sqlx_test::new;
 
@@ -18,6 +18,20 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_the_database_kind() -> anyhow::Result<()> {
+    let url = dotenv::var("DATABASE_URL")?;
+    let kind: AnyKind = url.parse()?;
+
+    let conn = new::<Any>().await?;
+    assert_eq!(conn.kind(), kind);
+
+    let pool = sqlx_test::pool::<Any>().await?;
+    assert_eq!(pool.any_kind(), kind);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_pings() -> anyhow::Result<()> {
     let mut conn = new::<Any>().await?;
@@ -4,8 +4,9 @@ use sqlx::prelude::*;
 use sqlx_test::new;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
-    Arc,
+    Arc, Mutex,
 };
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -35,3 +36,737 @@ async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn pool_should_propagate_after_connect_error() -> anyhow::Result<()> {
+    let res = AnyPoolOptions::new()
+        .after_connect(|_conn| {
+            Box::pin(async move { Err(sqlx::Error::Protocol("after_connect failed".into())) })
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Protocol(_))));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_replace_connections_rejected_by_before_acquire() -> anyhow::Result<()> {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .after_connect({
+            let counter = counter.clone();
+            move |_conn| {
+                let counter = counter.clone();
+                Box::pin(async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(())
+                })
+            }
+        })
+        .before_acquire(|_conn, _meta| Box::pin(async move { Ok(false) }))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // every idle connection is rejected so each acquire must open a new connection
+    let _ = pool.acquire().await?;
+    let _ = pool.acquire().await?;
+
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_discard_connections_failing_health_check_query() -> anyhow::Result<()> {
+    for (query, expected) in &[
+        ("SELECT 1", 1),
+        ("SELECT * FROM no_such_table", 3),
+        ("SELECT 1 WHERE 1 = 0", 3),
+    ] {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let pool = AnyPoolOptions::new()
+            .max_connections(1)
+            .after_connect({
+                let counter = counter.clone();
+                move |_conn| {
+                    let counter = counter.clone();
+                    Box::pin(async move {
+                        counter.fetch_add(1, Ordering::SeqCst);
+
+                        Ok(())
+                    })
+                }
+            })
+            .health_check_query(query)
+            .connect(&dotenv::var("DATABASE_URL")?)
+            .await?;
+
+        // a healthy idle connection is reused; one that fails the query is replaced
+        let _ = pool.acquire().await?;
+        let _ = pool.acquire().await?;
+
+        assert_eq!(counter.load(Ordering::SeqCst), *expected, "{}", query);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_skip_health_check_of_recently_used_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .health_check_query("SELECT * FROM no_such_table")
+        .max_idle_before_test(Duration::from_millis(200))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // the idle connection was just opened so it is not tested (and not replaced)
+    let _ = pool.acquire().await?;
+    let _ = pool.acquire().await?;
+
+    assert_eq!(pool.connect_count(), 1);
+
+    sqlx_rt::sleep(Duration::from_millis(300)).await;
+
+    // once idle for long enough, the connection fails the check and is replaced
+    let _ = pool.acquire().await?;
+
+    assert_eq!(pool.connect_count(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_report_fresh_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // the connection opened by `connect` is idle until acquired
+    let first = pool.acquire().await?;
+    assert!(!first.is_fresh());
+
+    let second = pool.acquire().await?;
+    assert!(second.is_fresh());
+
+    drop(second);
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let third = pool.acquire().await?;
+    assert!(!third.is_fresh());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_respect_max_connections_when_acquiring_unfairly() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire_unfair().await?;
+
+    // the pool is full, so even an unfair acquire has to wait
+    let res = pool.acquire_unfair().await;
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+
+    drop(conn);
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    // takes the idle connection
+    let conn = pool.acquire_unfair().await?;
+    assert!(!conn.is_fresh());
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_wake_unfair_acquires_before_waiting_tasks() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let acquire = |unfair: bool, id: usize| {
+        let pool = pool.clone();
+        let order = order.clone();
+
+        sqlx_rt::spawn(async move {
+            let _conn = if unfair {
+                pool.acquire_unfair().await
+            } else {
+                pool.acquire().await
+            }
+            .expect("failed to acquire a connection");
+
+            order.lock().unwrap().push(id);
+        });
+    };
+
+    for id in 0..3 {
+        acquire(false, id);
+
+        while pool.num_waiting() <= id {
+            sqlx_rt::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    acquire(true, 3);
+
+    while pool.num_waiting() < 4 {
+        sqlx_rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    drop(conn);
+
+    while order.lock().unwrap().len() < 4 {
+        sqlx_rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    // the unfair acquire is served first, then the others in order
+    assert_eq!(*order.lock().unwrap(), [3, 0, 1, 2]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_pass_connection_meta_to_before_acquire() -> anyhow::Result<()> {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .before_acquire({
+            let seen = seen.clone();
+            move |_conn, meta| {
+                seen.lock().unwrap().push(meta);
+
+                // recycle the connection once it has executed two queries
+                Box::pin(async move { Ok(meta.num_queries < 2) })
+            }
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute("SELECT 1").await?;
+    drop(conn);
+
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute("SELECT 1").await?;
+    drop(conn);
+
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let _ = pool.acquire().await?;
+
+    let seen = seen.lock().unwrap();
+    let num_queries: Vec<u64> = seen.iter().map(|meta| meta.num_queries).collect();
+
+    // the connection is rejected on the third acquire and replaced without calling the hook
+    assert_eq!(num_queries, vec![0, 1, 2]);
+    assert_eq!(pool.connect_count(), 2);
+
+    assert!(seen[1].created_at == seen[0].created_at);
+    assert!(seen[1].last_used_at > seen[0].last_used_at);
+    assert!(seen[1].age() >= seen[1].idle_for());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_count_queries_through_the_pool_and_transactions() -> anyhow::Result<()> {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .before_acquire({
+            let seen = seen.clone();
+            move |_conn, meta| {
+                seen.lock().unwrap().push(meta.num_queries);
+
+                Box::pin(async move { Ok(true) })
+            }
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    pool.execute("SELECT 1").await?;
+
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let mut tx = pool.begin().await?;
+    tx.execute("SELECT 1").await?;
+    tx.execute("SELECT 1").await?;
+    tx.commit().await?;
+
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let _ = pool.acquire().await?;
+
+    assert_eq!(*seen.lock().unwrap(), vec![0, 1, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_propagate_before_acquire_error() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .before_acquire(|_conn, _meta| {
+            Box::pin(async move { Err(sqlx::Error::Protocol("before_acquire failed".into())) })
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let res = pool.acquire().await;
+
+    assert!(matches!(res, Err(sqlx::Error::Protocol(_))));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_close_connections_rejected_by_after_release() -> anyhow::Result<()> {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let pool = AnyPoolOptions::new()
+        .test_before_release(true)
+        .after_release({
+            let counter = counter.clone();
+            move |_conn| {
+                let counter = counter.clone();
+                Box::pin(async move {
+                    // keep the first connection and reject the rest
+                    Ok(counter.fetch_add(1, Ordering::SeqCst) == 0)
+                })
+            }
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // connections are checked in the background once they are dropped
+    let _ = pool.acquire().await?;
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(pool.size(), 1);
+
+    let _ = pool.acquire().await?;
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_time_out_waiting_after_acquire_timeout() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_timeout(Duration::from_secs(10))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let _held = pool.acquire().await?;
+
+    let start = Instant::now();
+    let res = pool.acquire().await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_time_out_waiting_after_deadline() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let held = pool.acquire().await?;
+
+    // the deadline takes the place of `acquire_timeout`
+    let start = Instant::now();
+    let res = pool
+        .acquire_with_deadline(start + Duration::from_millis(100))
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    drop(held);
+
+    let _ = pool
+        .acquire_with_deadline(Instant::now() + Duration::from_millis(100))
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_acquire_with_deadline_should_bound_checking_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .before_acquire(|_conn, _meta| {
+            Box::pin(async move {
+                sqlx_rt::sleep(Duration::from_secs(10)).await;
+                Ok(true)
+            })
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // the idle connection opened by `connect` is still being checked when the deadline passes
+    let start = Instant::now();
+    let res = pool
+        .acquire_with_deadline(start + Duration::from_millis(100))
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_report_acquire_wait_time() -> anyhow::Result<()> {
+    let timings = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .on_acquire_timing({
+            let timings = timings.clone();
+            move |wait_time| timings.lock().unwrap().push(wait_time)
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let held = pool.acquire().await?;
+
+    let (second, _) = futures::join!(pool.acquire(), async move {
+        sqlx_rt::sleep(Duration::from_millis(200)).await;
+        drop(held);
+    });
+
+    drop(second?);
+
+    let timings = timings.lock().unwrap();
+
+    assert_eq!(timings.len(), 2);
+    assert_eq!(timings[0], Duration::from_secs(0));
+    assert!(timings[1] >= Duration::from_millis(100));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_allow_growing_max_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let _first = pool.acquire().await?;
+
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    pool.set_max_connections(2);
+    assert_eq!(pool.max_connections(), 2);
+
+    let _second = pool.acquire().await?;
+
+    // the maximum is never allowed to drop to zero
+    pool.set_max_connections(0);
+    assert_eq!(pool.max_connections(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_reject_acquire_after_close() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    // returning the checked-out connection lets `close()` finish
+    futures::join!(pool.close(), async move { drop(conn) });
+
+    assert!(pool.is_closed());
+    assert!(matches!(pool.acquire().await, Err(sqlx::Error::PoolClosed)));
+
+    // closing twice is a no-op
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_report_status() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // one connection is opened eagerly by `connect`
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.num_idle(), 1);
+
+    let first = pool.acquire().await?;
+    let second = pool.acquire().await?;
+
+    let status = pool.status();
+
+    assert_eq!(status.size, 2);
+    assert_eq!(status.num_idle, 0);
+    assert_eq!(status.num_waiting, 0);
+
+    drop(first);
+    drop(second);
+
+    assert_eq!(pool.idle(), 2);
+    assert_eq!(pool.num_idle(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_count_closed_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .health_check_query("SELECT * FROM no_such_table")
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // the idle connection fails the health check and is replaced
+    let _ = pool.acquire().await?;
+
+    let status = pool.status();
+
+    assert_eq!(status.closed_broken, 1);
+    assert_eq!(status.closed_max_lifetime, 0);
+    assert_eq!(status.closed_idle_timeout, 0);
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .max_lifetime(Duration::from_millis(100))
+        .idle_timeout(None)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+
+    // the expired connection is closed, either by the reaper or when acquired
+    let _ = pool.acquire().await?;
+
+    let status = pool.status();
+
+    assert!(status.closed_max_lifetime >= 1);
+    assert_eq!(status.closed_broken, 0);
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .idle_timeout(Duration::from_millis(100))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // give the reaper a few periods to close the idle connection
+    sqlx_rt::sleep(Duration::from_millis(500)).await;
+
+    assert_eq!(pool.status().closed_idle_timeout, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_try_acquire_should_not_wait() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.try_acquire();
+    assert!(conn.is_some());
+
+    // the only connection is checked out
+    assert!(pool.try_acquire().is_none());
+    assert!(pool.try_acquire_checked().await?.is_none());
+
+    drop(conn);
+
+    assert!(pool.try_acquire_checked().await?.is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_try_acquire_checked_should_skip_rejected_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .before_acquire(|_conn, _meta| Box::pin(async move { Ok(false) }))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    drop(pool.acquire().await?);
+
+    // the idle connection is rejected and no connection is opened in its place
+    assert!(pool.try_acquire_checked().await?.is_none());
+    assert!(pool.try_acquire().is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_try_acquire_should_skip_expired_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .max_lifetime(Duration::from_millis(100))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    drop(pool.acquire().await?);
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+
+    // the expired connection is closed rather than handed out
+    assert!(pool.try_acquire().is_none());
+    assert!(pool.try_acquire_checked().await?.is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_replenish_min_connections_after_reaping() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .min_connections(3)
+        .max_lifetime(Duration::from_millis(100))
+        .idle_timeout(None)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let initial = pool.connect_count();
+    assert!(initial >= 3);
+
+    // give the reaper a few periods to retire and replace the connections
+    sqlx_rt::sleep(Duration::from_millis(500)).await;
+
+    assert!(pool.connect_count() > initial);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_keep_min_idle_connections_ready() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .min_connections(1)
+        .min_idle(2)
+        .max_connections(5)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // the idle buffer is filled in the background
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+    assert_eq!(pool.num_idle(), 2);
+
+    let _a = pool.acquire().await?;
+    let _b = pool.acquire().await?;
+
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+    assert_eq!(pool.num_idle(), 2);
+    assert_eq!(pool.size(), 4);
+
+    // never beyond `max_connections`
+    let _c = pool.acquire().await?;
+    let _d = pool.acquire().await?;
+
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+    assert_eq!(pool.size(), 5);
+    assert_eq!(pool.num_idle(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_connect_lazy_should_not_connect_until_used() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .min_connections(2)
+        .connect_lazy(&dotenv::var("DATABASE_URL")?)?;
+
+    assert_eq!(pool.size(), 0);
+    assert_eq!(pool.connect_count(), 0);
+
+    let _conn = pool.acquire().await?;
+
+    assert!(pool.connect_count() >= 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "postgres")]
+#[sqlx_macros::test]
+async fn pool_should_retry_refused_connections_until_connect_timeout() -> anyhow::Result<()> {
+    // nothing should be listening on port 1
+    let pool = AnyPoolOptions::new()
+        .connect_timeout(Duration::from_millis(500))
+        .connect_retry_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .connect_lazy("postgres://127.0.0.1:1/postgres")?;
+
+    let start = Instant::now();
+    let res = pool.acquire().await;
+
+    assert!(matches!(res, Err(sqlx::Error::Io(_))), "{:?}", res.err());
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    assert_eq!(pool.size(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_open_a_replacement_for_a_detached_connection() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut detached = pool.acquire().await?.detach();
+    assert_eq!(pool.size(), 0);
+
+    // the pool is not waiting for the detached connection to come back
+    let mut conn = pool.acquire().await?;
+    assert!(conn.is_fresh());
+    assert_eq!(pool.size(), 1);
+
+    conn.ping().await?;
+    detached.ping().await?;
+
+    drop(conn);
+    assert_eq!(pool.size(), 1);
+
+    detached.close().await?;
+
+    Ok(())
+}
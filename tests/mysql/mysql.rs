@@ -1,7 +1,8 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Connection, Done, Executor, Row};
+use sqlx::mysql::{MySql, MySqlCapabilities, MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::{Column, Connection, Done, Executor, Row};
 use sqlx_test::new;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -200,3 +201,186 @@ async fn it_caches_statements() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_call_a_stored_procedure() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP PROCEDURE IF EXISTS it_can_call_a_stored_procedure")
+        .await?;
+
+    conn.execute(
+        "
+CREATE PROCEDURE it_can_call_a_stored_procedure()
+BEGIN
+    SELECT 42 AS a;
+    SELECT 'x' AS b UNION ALL SELECT 'y';
+END
+        ",
+    )
+    .await?;
+
+    for prepared in &[false, true] {
+        let query = "CALL it_can_call_a_stored_procedure()";
+
+        let results: Vec<_> = if *prepared {
+            sqlx::query(query)
+                .fetch_many(&mut conn)
+                .try_collect()
+                .await?
+        } else {
+            conn.fetch_many(query).try_collect().await?
+        };
+
+        // each result set is followed by its own done, and the CALL itself ends with another
+        assert_eq!(results.len(), 6);
+
+        let rows: Vec<String> = results
+            .iter()
+            .filter_map(|v| v.as_ref().right())
+            .map(|row| match row.try_get::<i64, _>(0) {
+                Ok(v) => Ok(v.to_string()),
+                Err(_) => row.try_get::<String, _>(0),
+            })
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(rows, ["42", "x", "y"]);
+        assert!(results[1].is_left());
+        assert!(results[4].is_left());
+        assert!(results[5].is_left());
+    }
+
+    conn.execute("DROP PROCEDURE it_can_call_a_stored_procedure")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_server_version() -> anyhow::Result<()> {
+    let conn = new::<MySql>().await?;
+
+    let (major, _, _) = conn
+        .server_version()
+        .expect("server version not understood");
+
+    assert!(major >= 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_capabilities() -> anyhow::Result<()> {
+    let conn = new::<MySql>().await?;
+
+    let capabilities = conn.capabilities();
+
+    assert!(capabilities.contains(MySqlCapabilities::PROTOCOL_41));
+    assert!(capabilities.contains(MySqlCapabilities::TRANSACTIONS));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_a_timed_out_query() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let start = Instant::now();
+
+    let res = sqlx::query("SELECT SLEEP(10)")
+        .timeout(Duration::from_millis(100))
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)));
+
+    // the query was killed on the server rather than left to finish
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_a_prepared_statement() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let statement = conn
+        .prepare("SELECT CAST(? AS SIGNED) + 1 AS value")
+        .await?;
+
+    assert_eq!(statement.columns()[0].name(), "value");
+
+    let cached = conn.cached_statements_size();
+
+    for i in 0..5_i64 {
+        let value: i64 = statement
+            .query_scalar()
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    // executed as the statement prepared above rather than through the cache
+    assert_eq!(conn.cached_statements_size(), cached);
+
+    drop(statement);
+
+    // the statement is closed before this query
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_session_parameters_after_reset() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("SET time_zone = '+08:00', sql_mode = ''")
+        .await?;
+    conn.reset().await?;
+
+    let (time_zone, sql_mode): (String, String) = sqlx::query_as("SELECT @@time_zone, @@sql_mode")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(time_zone, "+00:00");
+    assert!(sql_mode.contains("PIPES_AS_CONCAT"));
+    assert!(sql_mode.contains("NO_ENGINE_SUBSTITUTION"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_too_many_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut builder = sqlx::QueryBuilder::<MySql>::new("SELECT 1 WHERE 1 IN (");
+    let mut separated = builder.separated(", ");
+
+    for i in 0..=i32::from(u16::MAX) {
+        separated.push_bind(i);
+    }
+
+    builder.push(")");
+
+    let res = builder.build().execute(&mut conn).await;
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::TooManyArguments {
+            count: 65536,
+            max: 65535
+        })
+    ));
+
+    // the connection is still usable
+    conn.ping().await?;
+
+    Ok(())
+}
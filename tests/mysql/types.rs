@@ -42,6 +42,12 @@ test_type!(bytes<Vec<u8>>(MySql,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(std_duration<std::time::Duration>(MySql,
+    "TIME '00:00:00'" == std::time::Duration::from_secs(0),
+    "TIME '05:10:20.115100'" == std::time::Duration::from_micros(18_620_115_100),
+    "TIME '838:59:59'" == std::time::Duration::from_secs(3_020_399)
+));
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
@@ -1,7 +1,7 @@
 use futures::TryStreamExt;
-use sqlx::{Connection, Executor, FromRow, Postgres};
+use sqlx::{Connection, Executor, FromRow, Postgres, Row};
 use sqlx_core::postgres::types::PgRange;
-use sqlx_test::{new, test_type};
+use sqlx_test::{new, test_decode_type, test_type};
 use std::fmt::Debug;
 use std::ops::Bound;
 
@@ -78,6 +78,21 @@ struct InventoryItem {
     price: Option<i64>,
 }
 
+// A composite type may contain NULL fields and other composite types
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "address")]
+struct Address {
+    street: Option<String>,
+    city: String,
+}
+
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "customer")]
+struct Customer {
+    name: String,
+    address: Address,
+}
+
 // Custom range type
 #[derive(sqlx::Type, Debug, PartialEq)]
 #[sqlx(rename = "float_range")]
@@ -105,6 +120,12 @@ test_type!(strong_enum<Strong>(Postgres,
     "'four'::text" == Strong::Three
 ));
 
+// a "string" enum can be decoded from any text-like column
+test_decode_type!(strong_enum_varchar<Strong>(Postgres,
+    "'one'::varchar" == Strong::One,
+    "'four'::varchar" == Strong::Three
+));
+
 test_type!(floatrange<FloatRange>(Postgres,
     "'[1.234, 5.678]'::float_range" == FloatRange(PgRange::from((Bound::Included(1.234), Bound::Included(5.678)))),
 ));
@@ -245,6 +266,22 @@ SELECT id, mood FROM people WHERE id = $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_enum_with_unknown_label() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res: Result<(Strong,), _> = sqlx::query_as("SELECT 'five'::text")
+        .fetch_one(&mut conn)
+        .await;
+
+    let err = res.unwrap_err().to_string();
+
+    assert!(err.contains("invalid value \"five\" for enum Strong"));
+    assert!(err.contains(r#"expected one of: "one", "two", "four""#));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_record_type() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -270,6 +307,75 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_nested_record_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let value = Customer {
+        name: "alice".to_owned(),
+        address: Address {
+            street: None,
+            city: "Springfield".to_owned(),
+        },
+    };
+
+    let rec: (bool, Customer) = sqlx::query_as(
+        "
+SELECT $1 = ROW('alice', ROW(NULL, 'Springfield')::address)::customer, $1
+        ",
+    )
+    .bind(&value)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_record_array_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let value = vec![
+        Some(Address {
+            street: Some("Evergreen Terrace".to_owned()),
+            city: "Springfield".to_owned(),
+        }),
+        None,
+        Some(Address {
+            street: None,
+            city: "Shelbyville".to_owned(),
+        }),
+    ];
+
+    let sql = "
+SELECT $1 = ARRAY[
+    ROW('Evergreen Terrace', 'Springfield')::address,
+    NULL,
+    ROW(NULL, 'Shelbyville')::address
+], $1
+        ";
+
+    let rec: (bool, Vec<Option<Address>>) = sqlx::query_as(sql)
+        .bind(&value)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, value);
+
+    // the text format is decoded the same way
+    let row = conn
+        .fetch_one("SELECT ARRAY[ROW('Evergreen Terrace', 'Springfield')::address, NULL, ROW(NULL, 'Shelbyville')::address]")
+        .await?;
+
+    assert_eq!(row.try_get::<Vec<Option<Address>>, _>(0)?, value);
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_from_row() -> anyhow::Result<()> {
@@ -402,5 +508,65 @@ async fn test_default() -> anyhow::Result<()> {
     assert_eq!(has_default.not_default, 1);
     assert_eq!(has_default.default, None);
 
+    // only a missing column uses the default; a NULL is decoded as usual
+    #[derive(Debug, sqlx::FromRow)]
+    struct HasRequiredDefault {
+        #[sqlx(default)]
+        default: i32,
+    }
+
+    let has_default: HasRequiredDefault = sqlx::query_as(r#"SELECT 1 AS not_default"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(has_default.default, 0);
+
+    let res: sqlx::Result<HasRequiredDefault> = sqlx::query_as(r#"SELECT NULL::int4 AS "default""#)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_flatten() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct Customer {
+        id: i32,
+        name: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Order {
+        id: i32,
+        #[sqlx(default)]
+        note: Option<String>,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct OrderWithCustomer {
+        #[sqlx(flatten)]
+        order: Order,
+
+        #[sqlx(flatten, prefix = "customer_")]
+        customer: Customer,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row: OrderWithCustomer =
+        sqlx::query_as(r#"SELECT 1 AS id, 2 AS customer_id, 'Herp Derpinson' AS customer_name"#)
+            .fetch_one(&mut conn)
+            .await?;
+    println!("{:?}", row);
+
+    assert_eq!(row.order.id, 1);
+    assert_eq!(row.order.note, None);
+    assert_eq!(row.customer.id, 2);
+    assert_eq!(row.customer.name, "Herp Derpinson");
+
     Ok(())
 }
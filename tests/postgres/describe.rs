@@ -1,5 +1,7 @@
-use sqlx::{postgres::Postgres, Column, Executor, TypeInfo};
+use sqlx::postgres::{PgConnectOptions, PgConnection, Postgres};
+use sqlx::{Column, Connection, Executor, TypeInfo};
 use sqlx_test::new;
+use std::env;
 
 #[sqlx_macros::test]
 async fn it_describes_simple() -> anyhow::Result<()> {
@@ -93,3 +95,51 @@ async fn it_describes_composite() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_caches_describe_until_the_statement_cache_is_cleared() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.describe_cache(true)).await?;
+
+    conn.execute("CREATE TEMPORARY TABLE describe_cache (id INT4)")
+        .await?;
+
+    let d = conn.describe("SELECT * FROM describe_cache").await?;
+    assert_eq!(d.columns().len(), 1);
+
+    conn.execute("ALTER TABLE describe_cache ADD COLUMN name TEXT")
+        .await?;
+
+    // served from the cache
+    let d = conn.describe("SELECT * FROM describe_cache").await?;
+    assert_eq!(d.columns().len(), 1);
+
+    conn.clear_cached_statements().await?;
+
+    let d = conn.describe("SELECT * FROM describe_cache").await?;
+    assert_eq!(d.columns().len(), 2);
+    assert_eq!(d.column(1).name(), "name");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_does_not_cache_describe_by_default() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE describe_no_cache (id INT4)")
+        .await?;
+
+    let d = conn.describe("SELECT * FROM describe_no_cache").await?;
+    assert_eq!(d.columns().len(), 1);
+
+    conn.execute("ALTER TABLE describe_no_cache ADD COLUMN name TEXT")
+        .await?;
+
+    let d = conn.describe("SELECT * FROM describe_no_cache").await?;
+    assert_eq!(d.columns().len(), 2);
+
+    Ok(())
+}
@@ -261,6 +261,37 @@ async fn test_array_from_slice() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_spread_list() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids: &[i32] = &[3, 2, 4];
+
+    // `$2..` expands to `$2, $3, $4` and `$3` is renumbered to `$5`
+    let rows = sqlx::query!(
+        "SELECT id FROM unnest(array[1, 2, 3, 4]) ids(id) WHERE id <> $1 AND id IN ($2..) AND id <> $3 ORDER BY id",
+        0i32, ids, 4i32
+    )
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.iter().map(|row| row.id).collect::<Vec<_>>(), vec![Some(2), Some(3)]);
+
+    // an empty list is expanded to `IN (NULL)`, which matches no rows
+    let ids: Vec<i32> = Vec::new();
+
+    let rows = sqlx::query!(
+        "SELECT id FROM unnest(array[1, 2, 3, 4]) ids(id) WHERE id IN ($1..) AND id <> $2",
+        ids, 0i32
+    )
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert!(rows.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn fetch_is_usable_issue_224() -> anyhow::Result<()> {
     // ensures that the stream returned by `query::Map::fetch()` is usable with `TryStreamExt`
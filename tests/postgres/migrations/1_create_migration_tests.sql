@@ -0,0 +1,3 @@
+CREATE TABLE migration_tests (
+    id BIGSERIAL PRIMARY KEY
+);
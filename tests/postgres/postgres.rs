@@ -1,9 +1,10 @@
 use futures::TryStreamExt;
+use sqlx::pool::RecyclingMethod;
 use sqlx::postgres::{
     PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgSeverity,
 };
-use sqlx::postgres::{PgPoolOptions, PgRow};
-use sqlx::{postgres::Postgres, Connection, Done, Executor, Row};
+use sqlx::postgres::{PgListener, PgPipeline, PgPoolOptions, PgRow};
+use sqlx::{postgres::Postgres, Column, Connection, Done, Executor, Row};
 use sqlx_test::new;
 use std::env;
 use std::thread;
@@ -84,6 +85,33 @@ async fn it_can_inspect_errors() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_inspect_constraint_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE accounts (email TEXT CONSTRAINT accounts_email_key UNIQUE)",
+    )
+    .await?;
+
+    conn.execute("INSERT INTO accounts (email) VALUES ('a@example.com')")
+        .await?;
+
+    let err = conn
+        .execute("INSERT INTO accounts (email) VALUES ('a@example.com')")
+        .await
+        .unwrap_err();
+
+    let err = err.as_database_error().unwrap();
+
+    assert_eq!(err.code().as_deref(), Some("23505"));
+    assert_eq!(err.constraint(), Some("accounts_email_key"));
+    assert_eq!(err.table(), Some("accounts"));
+    assert!(err.schema().is_some());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -559,8 +587,636 @@ async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_disable_statement_cache() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    // a capacity of 0 means that each statement is closed right after it is executed
+    options = options.statement_cache_capacity(0);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..5 {
+        let row = sqlx::query("SELECT $1::int4 AS val")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: i32 = row.get("val");
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    let open: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pg_prepared_statements")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // only the statement counting the open statements is still open
+    assert_eq!(1, open);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_the_search_path_on_connect() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(options.search_path("pg_catalog, public"))
+        .await?;
+
+    // every connection of the pool starts with the configured search path
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    for conn in vec![&mut a, &mut b] {
+        let search_path: String = sqlx::query_scalar("SHOW search_path")
+            .fetch_one(&mut *conn)
+            .await?;
+
+        assert_eq!("pg_catalog, public", search_path);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_too_many_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut builder = sqlx::QueryBuilder::<Postgres>::new("SELECT 1 WHERE 1 IN (");
+    let mut separated = builder.separated(", ");
+
+    for i in 0..=i32::from(u16::MAX) {
+        separated.push_bind(i);
+    }
+
+    builder.push(")");
+
+    let res = builder.build().execute(&mut conn).await;
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::TooManyArguments {
+            count: 65536,
+            max: 65535
+        })
+    ));
+
+    // the connection is still usable
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_a_pipeline() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE pipeline_test (id INT4 PRIMARY KEY)")
+        .await?;
+
+    let mut pipeline = PgPipeline::new();
+
+    pipeline
+        .push(
+            sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1), ($2)")
+                .bind(1_i32)
+                .bind(2_i32),
+        )
+        .push(sqlx::query("UPDATE pipeline_test SET id = id + 10 WHERE id = $1").bind(2_i32))
+        .push(sqlx::query("SELECT id FROM pipeline_test ORDER BY id"));
+
+    let results = conn.execute_pipeline(pipeline).await?;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].rows_affected(), 2);
+    assert_eq!(results[1].rows_affected(), 1);
+
+    let ids: Vec<i32> = results[2].rows().iter().map(|row| row.get(0)).collect();
+    assert_eq!(ids, vec![1, 12]);
+
+    // a failing query rolls back the queries before it and skips the ones after it
+    let mut pipeline = PgPipeline::new();
+
+    pipeline
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(3_i32))
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(1_i32))
+        .push(sqlx::query("INSERT INTO pipeline_test (id) VALUES ($1)").bind(4_i32));
+
+    let err = match conn.execute_pipeline(pipeline).await {
+        Err(err) => err,
+        Ok(_) => panic!("expected the pipeline to fail"),
+    };
+    assert_eq!(err.index(), 1);
+    assert!(err.error().is_constraint_violation());
+
+    // the connection is still usable
+    conn.ping().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipeline_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    // a statement cached by an earlier pipeline is not described again
+    let mut pipeline = PgPipeline::new();
+
+    pipeline.push(sqlx::query("SELECT id FROM pipeline_test ORDER BY id"));
+
+    let results = conn.execute_pipeline(pipeline).await?;
+
+    let ids: Vec<i32> = results[0].rows().iter().map(|row| row.get("id")).collect();
+    assert_eq!(ids, vec![1, 12]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_with_the_simple_query_protocol() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let results: Vec<_> = conn
+        .execute_simple(
+            "CREATE TEMPORARY TABLE simple_test (id INT4); \
+             INSERT INTO simple_test (id) VALUES (1), (2); \
+             SELECT id FROM simple_test ORDER BY id",
+        )
+        .try_collect()
+        .await?;
+
+    let rows_affected: Vec<u64> = results
+        .iter()
+        .filter_map(|v| v.as_ref().left().map(|done| done.rows_affected()))
+        .collect();
+
+    assert_eq!(rows_affected, vec![0, 2, 2]);
+
+    let ids: Vec<i32> = results
+        .iter()
+        .filter_map(|v| v.as_ref().right().map(|row| row.get(0)))
+        .collect();
+
+    assert_eq!(ids, vec![1, 2]);
+
+    // nothing was prepared on the connection
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
     new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_copy_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copy_in_test (id INT4 NOT NULL, name TEXT NOT NULL)")
+        .await?;
+
+    let mut copy = conn
+        .copy_in_raw("COPY copy_in_test (id, name) FROM STDIN WITH (FORMAT CSV)")
+        .await?;
+
+    assert!(copy.is_textual());
+    assert_eq!(copy.num_columns(), 2);
+
+    copy.send("1,alice\n2,b").await?;
+    copy.send("ob\n").await?;
+
+    assert_eq!(copy.finish().await?, 2);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM copy_in_test WHERE id = 2")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "bob");
+
+    // an aborted copy leaves the connection usable
+    let mut copy = conn
+        .copy_in_raw("COPY copy_in_test (id, name) FROM STDIN WITH (FORMAT CSV)")
+        .await?;
+
+    copy.send("3,carol\n").await?;
+    copy.abort("changed my mind").await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM copy_in_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copy_rows_test (id INT4 NOT NULL, name TEXT)")
+        .await?;
+
+    let names = vec![
+        Some("tab\there"),
+        Some("new\nline"),
+        Some("back\\slash \\N"),
+        Some("\\."),
+        Some(""),
+        None,
+    ];
+
+    let rows: Vec<(i32, Option<&str>)> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (i as i32, *name))
+        .collect();
+
+    let copied = conn
+        .copy_in_rows("COPY copy_rows_test (id, name) FROM STDIN", &rows)
+        .await?;
+
+    assert_eq!(copied, rows.len() as u64);
+
+    let copied: Vec<Option<String>> =
+        sqlx::query_scalar("SELECT name FROM copy_rows_test ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    let expected: Vec<Option<String>> = names.iter().map(|name| name.map(String::from)).collect();
+
+    assert_eq!(copied, expected);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_copy_in_rows_in_the_csv_format() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE copy_rows_csv_test (id INT4 NOT NULL, name TEXT)")
+        .await?;
+
+    let res = conn
+        .copy_in_rows(
+            "COPY copy_rows_csv_test (id, name) FROM STDIN WITH (FORMAT csv)",
+            vec![(1_i32, "a,b")],
+        )
+        .await;
+
+    assert!(res.is_err());
+
+    // the statement is never issued, so the connection is still usable
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM copy_rows_csv_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut stream = conn
+        .copy_out_raw("COPY (SELECT generate_series(1, 3) AS id) TO STDOUT")
+        .await?;
+
+    let mut data = Vec::new();
+    while let Some(chunk) = stream.try_next().await? {
+        data.extend_from_slice(&chunk);
+    }
+
+    drop(stream);
+
+    assert_eq!(data, b"1\n2\n3\n");
+
+    // the connection is usable again after the stream has been read to completion
+    let val: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(val, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_listen_and_notify() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener
+        .listen_all(vec!["sqlx_test_a", "sqlx_test_b"])
+        .await?;
+
+    pool.execute("NOTIFY sqlx_test_b, 'hello'").await?;
+
+    let notification = listener.try_recv().await?.expect("connection was lost");
+
+    assert_eq!(notification.channel(), "sqlx_test_b");
+    assert_eq!(notification.payload(), "hello");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_released_connections() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .recycling_method(RecyclingMethod::Clean)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE recycled (id INT4)")
+        .await?;
+
+    // prepare and cache a statement, which the reset deallocates on the server
+    sqlx::query("SELECT $1::int4")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+
+    drop(conn);
+
+    // the same (and only) connection, with a clean session
+    let mut conn = pool.acquire().await?;
+
+    // checked before running any query, as the one below caches its own statement
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_tables WHERE tablename = 'recycled')")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert!(!exists);
+
+    sqlx::query("SELECT $1::int4")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_shrink_buffers() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let large: String = sqlx::query_scalar("SELECT repeat('a', 1024 * 1024)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(large.len(), 1024 * 1024);
+
+    conn.shrink_buffers();
+
+    // the buffers grow again as needed
+    let large: String = sqlx::query_scalar("SELECT repeat('b', 1024 * 1024)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(large.len(), 1024 * 1024);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_server_version() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let expected: String = sqlx::query_scalar("SHOW server_version_num")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(conn.server_version_num(), Some(expected.parse()?));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_capabilities() -> anyhow::Result<()> {
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    let conn = PgConnection::connect_with(&options).await?;
+    let capabilities = conn.capabilities();
+
+    assert_eq!(capabilities.protocol_version(), (3, 0));
+    assert!(capabilities.is_persistent());
+
+    let conn = PgConnection::connect_with(&options.persistent(false)).await?;
+    assert!(!conn.capabilities().is_persistent());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_time_out_a_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let before: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let res = sqlx::query("SELECT pg_sleep(5)")
+        .timeout(Duration::from_millis(100))
+        .execute(&mut conn)
+        .await;
+
+    // 57014 = query_canceled
+    let err = res.unwrap_err();
+    let err = err.into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("57014"));
+
+    // the session setting is untouched, including within a transaction
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("SET LOCAL statement_timeout = '30s'")
+        .execute(&mut tx)
+        .await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT $1")
+        .bind(5_i32)
+        .timeout(Duration::from_secs(10))
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(value, 5);
+
+    let during: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(during, "30s");
+
+    tx.rollback().await?;
+
+    let after: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(before, after);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_cancel_a_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let handle = conn.cancel_handle();
+
+    let (res, cancelled) = futures::join!(
+        sqlx::query("SELECT pg_sleep(10)").execute(&mut conn),
+        async {
+            sqlx_rt::sleep(Duration::from_millis(500)).await;
+            handle.cancel().await
+        }
+    );
+
+    cancelled?;
+
+    // 57014 = query_canceled
+    let err = res.unwrap_err().into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("57014"));
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_widen_numeric_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn
+        .fetch_one("SELECT 1::int2, 2::int4, 3.5::float4, 4::int8, 'text'")
+        .await?;
+
+    assert!(row.try_get::<i64, _>(1).is_err());
+    assert_eq!(row.try_get_lossy::<i32, _>(0)?, 1);
+    assert_eq!(row.try_get_lossy::<i64, _>(1)?, 2);
+    assert_eq!(row.try_get_lossy::<f64, _>(2)?, 3.5);
+    assert_eq!(row.try_get_lossy::<f64, _>(3)?, 4.0);
+
+    // narrowing is not performed
+    assert!(row.try_get_lossy::<i32, _>(3).is_err());
+    assert!(row.try_get_lossy::<f64, _>(4).is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "migrate")]
+#[sqlx_macros::test]
+async fn it_runs_migrations_concurrently() -> anyhow::Result<()> {
+    use sqlx::migrate::Migrator;
+    use std::path::Path;
+
+    let pool = sqlx_test::pool::<Postgres>().await?;
+
+    let migrator = Migrator::new(Path::new("tests/postgres/migrations")).await?;
+
+    // without the advisory lock, both runs would try to apply the migration
+    futures::try_join!(migrator.run(&pool), migrator.run(&pool))?;
+
+    let applied: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE version = 1")
+            .fetch_one(&pool)
+            .await?;
+
+    assert_eq!(applied, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_a_prepared_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let statement = conn.prepare("SELECT $1::int4 + 1 AS value").await?;
+
+    assert_eq!(statement.columns()[0].name(), "value");
+    assert_eq!(
+        statement
+            .info()
+            .parameters()
+            .map(|p| p.either(|p| p.len(), |n| n)),
+        Some(1)
+    );
+
+    let cached = conn.cached_statements_size();
+
+    for i in 0..5_i32 {
+        let value: i32 = statement
+            .query_scalar()
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    // executed as the statement prepared above rather than through the cache
+    assert_eq!(conn.cached_statements_size(), cached);
+
+    let count_prepared = "SELECT COUNT(*) FROM pg_prepared_statements \
+        WHERE statement = 'SELECT $1::int4 + 1 AS value'";
+
+    let prepared: i64 = sqlx::query_scalar(count_prepared)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(prepared, 1);
+
+    // closed before the next query once dropped
+    drop(statement);
+
+    let prepared: i64 = sqlx::query_scalar(count_prepared)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(prepared, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_without_persistent_statements() -> anyhow::Result<()> {
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = PgConnection::connect_with(&options.persistent(false)).await?;
+
+    // without arguments, the query is sent as a simple query
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // with arguments, the statement is closed after it was executed
+    for i in 0..3_i32 {
+        let value: i32 = sqlx::query_scalar("SELECT $1 + 1")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+-- https://www.postgresql.org/docs/current/hstore.html
+CREATE EXTENSION IF NOT EXISTS hstore;
+
 -- https://www.postgresql.org/docs/current/sql-createtype.html
 CREATE TYPE status AS ENUM ('new', 'open', 'closed');
 
@@ -9,6 +12,18 @@ CREATE TYPE inventory_item AS
     price       BIGINT
 );
 
+CREATE TYPE address AS
+(
+    street TEXT,
+    city   TEXT
+);
+
+CREATE TYPE customer AS
+(
+    name    TEXT,
+    address address
+);
+
 -- https://github.com/prisma/database-schema-examples/tree/master/postgres/basic-twitter#basic-twitter
 CREATE TABLE tweet
 (
@@ -14,6 +14,12 @@ test_type!(null<Option<i16>>(Postgres,
 
 test_type!(null_vec<Vec<Option<i16>>>(Postgres,
     "array[10,NULL,50]::int2[]" == vec![Some(10_i16), None, Some(50)],
+    "array[]::int2[]" == Vec::<Option<i16>>::new(),
+    "array[NULL]::int2[]" == vec![None::<i16>],
+));
+
+test_type!(null_string_vec<Vec<Option<String>>>(Postgres,
+    "array[NULL,'NULL','a,b']::text[]" == vec![None, Some("NULL".to_owned()), Some("a,b".to_owned())],
 ));
 
 test_type!(bool<bool>(Postgres,
@@ -167,6 +173,52 @@ test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(Postgres,
             .unwrap(),
 ));
 
+#[cfg(feature = "ipnetwork")]
+test_type!(ipaddr<std::net::IpAddr>(Postgres,
+    "'127.0.0.1'::inet" == "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+    "'::ffff:1.2.3.0'::inet" == "::ffff:1.2.3.0".parse::<std::net::IpAddr>().unwrap(),
+    "'10.1.2.3/32'::cidr" == "10.1.2.3".parse::<std::net::IpAddr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipv4addr<std::net::Ipv4Addr>(Postgres,
+    "'8.8.8.8'::inet" == std::net::Ipv4Addr::new(8, 8, 8, 8),
+));
+
+#[cfg(feature = "ipnetwork")]
+test_type!(ipv6addr<std::net::Ipv6Addr>(Postgres,
+    "'2001:4f8:3:ba::1'::inet" == "2001:4f8:3:ba::1".parse::<std::net::Ipv6Addr>().unwrap(),
+));
+
+#[cfg(feature = "ipnetwork")]
+#[sqlx_macros::test]
+async fn test_ipaddr_rejects_networks() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    for sql in &[
+        "SELECT '10.0.0.0/8'::cidr",
+        "SELECT '8.8.8.8/24'::inet",
+        "SELECT '2001:4f8:3:ba::/64'::cidr",
+    ] {
+        let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+
+        assert!(row.try_get::<std::net::IpAddr, _>(0).is_err());
+        assert!(row
+            .try_get::<sqlx::types::ipnetwork::IpNetwork, _>(0)
+            .is_ok());
+    }
+
+    let row = sqlx::query("SELECT '::1'::inet")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.try_get::<std::net::Ipv4Addr, _>(0).is_err());
+
+    Ok(())
+}
+
 #[cfg(feature = "ipnetwork")]
 test_type!(ipnetwork_vec<Vec<sqlx::types::ipnetwork::IpNetwork>>(Postgres,
     "'{127.0.0.1,8.8.8.8/24}'::inet[]"
@@ -176,6 +228,80 @@ test_type!(ipnetwork_vec<Vec<sqlx::types::ipnetwork::IpNetwork>>(Postgres,
         ]
 ));
 
+#[cfg(feature = "mac_address")]
+mod mac_address {
+    use super::*;
+    use sqlx::types::mac_address::MacAddress;
+
+    test_type!(mac_address<MacAddress>(Postgres,
+        "'00:01:02:03:04:05'::macaddr" == MacAddress::new([0, 1, 2, 3, 4, 5]),
+        "'08:00:2b:aa:bb:ff'::macaddr" == MacAddress::new([0x08, 0x00, 0x2b, 0xaa, 0xbb, 0xff]),
+    ));
+
+    test_type!(mac_address_vec<Vec<MacAddress>>(Postgres,
+        "'{00:01:02:03:04:05,ff:ff:ff:ff:ff:ff}'::macaddr[]"
+            == vec![MacAddress::new([0, 1, 2, 3, 4, 5]), MacAddress::new([0xff; 6])],
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_mac_address_rejects_macaddr8() -> anyhow::Result<()> {
+        use sqlx::Row;
+
+        let mut conn = sqlx_test::new::<Postgres>().await?;
+
+        let row = sqlx::query("SELECT '00:01:02:03:04:05:06:07'::macaddr8")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert!(row.try_get::<MacAddress, _>(0).is_err());
+        assert!(row.try_get_unchecked::<MacAddress, _>(0).is_err());
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+mod bit_vec {
+    use super::*;
+    use sqlx::types::BitVec;
+
+    fn bits(s: &str) -> BitVec {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    test_type!(bit_vec<BitVec>(Postgres,
+        "B''::varbit" == bits(""),
+        "B'101'::varbit" == bits("101"),
+        "B'10101010'::varbit" == bits("10101010"),
+        "B'110011001'::varbit" == bits("110011001"),
+        "B'0001'::bit(4)" == bits("0001"),
+    ));
+
+    test_type!(bit_vec_vec<Vec<BitVec>>(Postgres,
+        "array[B'1', B'0110']::varbit[]" == vec![bits("1"), bits("0110")],
+    ));
+}
+
+#[cfg(feature = "hstore")]
+mod hstore {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn hstore(pairs: &[(&str, Option<&str>)]) -> HashMap<String, Option<String>> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.map(str::to_owned)))
+            .collect()
+    }
+
+    test_type!(hstore<HashMap<String, Option<String>>>(Postgres,
+        "''::hstore" == hstore(&[]),
+        "'a=>1'::hstore" == hstore(&[("a", Some("1"))]),
+        "'a=>NULL, b=>\"\"'::hstore" == hstore(&[("a", None), ("b", Some(""))]),
+        "'\"quote\\\"d\"=>\"NULL\"'::hstore" == hstore(&[("quote\"d", Some("NULL"))]),
+    ));
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
@@ -216,6 +342,25 @@ mod chrono {
                 )
             ]
     ));
+
+    test_type!(chrono_tstzrange<PgRange<DateTime::<Utc>>>(Postgres,
+        "'[2019-01-02 05:00:00+00,2019-01-02 06:30:00+00)'::tstzrange"
+            == PgRange::from(
+                DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 0, 0), Utc)
+                    ..DateTime::<Utc>::from_utc(
+                        NaiveDate::from_ymd(2019, 1, 2).and_hms(6, 30, 0),
+                        Utc
+                    )
+            ),
+        "'(2019-01-02 05:00:00+00,)'::tstzrange"
+            == PgRange::new(
+                Bound::Excluded(DateTime::<Utc>::from_utc(
+                    NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 0, 0),
+                    Utc
+                )),
+                Bound::Unbounded
+            ),
+    ));
 }
 
 #[cfg(feature = "time")]
@@ -245,6 +390,10 @@ mod time_tests {
     test_type!(time_timestamp<OffsetDateTime>(
         Postgres,
         "TIMESTAMPTZ '2019-01-02 05:10:20.115100'"
+            == date!(2019 - 1 - 2)
+                .with_time(time!(5:10:20.115100))
+                .assume_utc(),
+        "TIMESTAMPTZ '2019-01-02 10:40:20.115100+05:30'"
             == date!(2019 - 1 - 2)
                 .with_time(time!(5:10:20.115100))
                 .assume_utc()
@@ -339,6 +488,22 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(Postgres,
     "12345.6789::numeric" == "12345.6789".parse::<sqlx::types::BigDecimal>().unwrap(),
 ));
 
+#[cfg(feature = "numeric-lossy")]
+test_decode_type!(numeric_f64<f64>(Postgres,
+    "0::numeric" == 0.0_f64,
+    "12345.6789::numeric" == 12345.6789_f64,
+    "-0.01234::numeric" == -0.01234_f64,
+    "SUM(x) FROM (VALUES (1.5::numeric), (2.25)) AS t (x)" == 3.75_f64,
+));
+
+#[cfg(feature = "numeric-lossy")]
+test_decode_type!(numeric_i64<i64>(Postgres,
+    "0::numeric" == 0_i64,
+    "-9223372036854775808::numeric" == i64::MIN,
+    "100000000.00::numeric" == 100_000_000_i64,
+    "SUM(x) FROM (VALUES (1::int8), (2)) AS t (x)" == 3_i64,
+));
+
 #[cfg(feature = "decimal")]
 test_type!(decimal<sqlx::types::Decimal>(Postgres,
     "0::numeric" == sqlx::types::Decimal::from_str("0").unwrap(),
@@ -348,6 +513,7 @@ test_type!(decimal<sqlx::types::Decimal>(Postgres,
     "0.01234::numeric" == sqlx::types::Decimal::from_str("0.01234").unwrap(),
     "12.34::numeric" == sqlx::types::Decimal::from_str("12.34").unwrap(),
     "12345.6789::numeric" == sqlx::types::Decimal::from_str("12345.6789").unwrap(),
+    "1.5::numeric(40, 30)" == sqlx::types::Decimal::from_str("1.5").unwrap(),
 ));
 
 const EXC2: Bound<i32> = Bound::Excluded(2);
@@ -374,6 +540,43 @@ test_type!(int4range<PgRange<i32>>(Postgres,
     "'[1,2]'::int4range" == PgRange::from((INC1, EXC3)),
 ));
 
+test_type!(int8range<PgRange<i64>>(Postgres,
+    "'[1,10)'::int8range" == PgRange::from(1_i64..10),
+    "'[-5,)'::int8range" == PgRange::new(Bound::Included(-5_i64), Bound::Unbounded),
+    "'(,)'::int8range" == PgRange::<i64>::unbounded(),
+));
+
+#[cfg(feature = "decimal")]
+test_type!(decimal_numrange<PgRange<sqlx::types::Decimal>>(Postgres,
+    "'[0.5,1.25]'::numrange"
+        == PgRange::inclusive(
+            sqlx::types::Decimal::from_str("0.5").unwrap(),
+            sqlx::types::Decimal::from_str("1.25").unwrap()
+        ),
+    "'(0.5,1.25)'::numrange"
+        == PgRange::exclusive(
+            sqlx::types::Decimal::from_str("0.5").unwrap(),
+            sqlx::types::Decimal::from_str("1.25").unwrap()
+        ),
+));
+
+#[test]
+fn test_range_bounds() {
+    let range = PgRange::inclusive(1, 5);
+
+    assert_eq!(range.start(), Bound::Included(&1));
+    assert_eq!(range.end(), Bound::Included(&5));
+    assert_eq!(
+        range.into_bounds(),
+        (Bound::Included(1), Bound::Included(5))
+    );
+
+    let range = PgRange::<i32>::unbounded();
+
+    assert_eq!(range.start(), Bound::Unbounded);
+    assert_eq!(range.end(), Bound::Unbounded);
+}
+
 test_prepared_type!(interval<PgInterval>(
     Postgres,
     "INTERVAL '1h'"
@@ -402,8 +605,20 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
-test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
+test_prepared_type!(std_duration<std::time::Duration>(
+    Postgres,
+    "INTERVAL '1h'" == std::time::Duration::from_secs(3_600),
+    "INTERVAL '2 days 03:10:20.116100'"
+        == std::time::Duration::from_micros(
+            (2 * 86_400 + 3 * 3_600 + 10 * 60 + 20) * 1_000_000 + 116100
+        ),
+));
+
+test_type!(money<PgMoney>(Postgres,
+    "123.45::money" == PgMoney(12345),
+    "(-1234567.89)::money" == PgMoney(-123456789),
+));
 
-test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
+test_type!(money_vec<Vec<PgMoney>>(Postgres,
     "array[123.45,420.00,666.66]::money[]" == vec![PgMoney(12345), PgMoney(42000), PgMoney(66666)],
 ));
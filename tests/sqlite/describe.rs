@@ -36,6 +36,40 @@ async fn it_describes_simple() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_column_names() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let info = conn.describe("SELECT * FROM tweet").await?;
+
+    assert_eq!(
+        info.column_names().collect::<Vec<_>>(),
+        ["id", "text", "is_sent", "owner_id"]
+    );
+
+    assert_eq!(info.index_of("id"), Some(0));
+    assert_eq!(info.index_of("owner_id"), Some(3));
+    assert_eq!(info.index_of("nope"), None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_overrides_nullable() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut info = conn.describe("SELECT * FROM tweet").await?;
+
+    info.set_nullable(0, Some(true));
+    info.set_nullable(3, None);
+
+    assert_eq!(info.nullable(0), Some(true));
+    assert_eq!(info.nullable(1), Some(false));
+    assert_eq!(info.nullable(3), None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_variables() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -55,6 +89,22 @@ async fn it_describes_variables() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_parameter_names() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let info = conn
+        .describe("SELECT * FROM tweet WHERE id = :id AND text = @text AND owner_id = ?")
+        .await?;
+
+    assert_eq!(info.parameter_name(0), Some(":id"));
+    assert_eq!(info.parameter_name(1), Some("@text"));
+    assert_eq!(info.parameter_name(2), None); // anonymous
+    assert_eq!(info.parameter_name(3), None); // out of bounds
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_expression() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
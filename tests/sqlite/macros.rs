@@ -110,6 +110,54 @@ async fn test_query_as_raw() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_as_maps_columns_by_name() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // the columns are in a different order than the fields of `RawAccount`
+    let account = sqlx::query_as!(RawAccount, "SELECT is_active, name, id from accounts")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+    assert_eq!(account.is_active, Some(true));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let id = sqlx::query_scalar!("SELECT id FROM accounts WHERE id = ?", 1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    let names: Vec<String> = sqlx::query_scalar!("SELECT name FROM accounts ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(names[0], "Herp Derpinson");
+
+    let missing = sqlx::query_scalar!("SELECT id FROM accounts WHERE id = ?", -1_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(missing, None);
+
+    // a wildcard override leaves the type to inference
+    let id: MyInt = sqlx::query_scalar!(r#"SELECT id as "id: _" FROM tweet"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, MyInt(1));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn macro_select_from_view() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -200,4 +248,57 @@ async fn test_column_override_exact() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_in_spread_list() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = vec![3_i64, 1, 2];
+
+    let accounts = sqlx::query!("select id, name from accounts where id in (?..)", ids)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, 1);
+
+    // binds after the list are still bound to their own placeholders
+    let count = sqlx::query_scalar!(
+        "select count(*) as count from accounts where name = ? and id in (?..) and id = ?",
+        "Herp Derpinson",
+        &[2_i64, 1][..],
+        1_i64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 1);
+
+    // an empty list is expanded to `IN (NULL)`, which matches no rows
+    let ids: Vec<i64> = Vec::new();
+
+    let accounts = sqlx::query!("select id from accounts where id in (?..)", ids)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert!(accounts.is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_in_spread_list_bound_to_variable() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = vec![1_i64, 2];
+
+    // the expanded SQL must outlive the statement the macro is used in
+    let query = sqlx::query!("select id, name from accounts where id in (?..)", ids);
+    let accounts = query.fetch_all(&mut conn).await?;
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, 1);
+
+    Ok(())
+}
+
 // we don't emit bind parameter typechecks for SQLite so testing the overrides is redundant
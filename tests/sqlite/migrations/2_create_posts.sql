@@ -0,0 +1,7 @@
+CREATE TABLE posts (
+    id INTEGER PRIMARY KEY NOT NULL,
+    account_id INTEGER NOT NULL REFERENCES accounts (id)
+);
+
+-- +migrate Down
+DROP TABLE posts;
@@ -0,0 +1,4 @@
+CREATE TABLE comments (
+    id INTEGER PRIMARY KEY NOT NULL,
+    post_id INTEGER NOT NULL REFERENCES posts (id)
+);
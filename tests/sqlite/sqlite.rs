@@ -1,10 +1,12 @@
 use futures::TryStreamExt;
-use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::pool::MaybePoolConnection;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use sqlx::{
-    query, sqlite::Sqlite, sqlite::SqliteRow, Connection, Done, Executor, Row, SqliteConnection,
-    SqlitePool,
+    query, sqlite::Sqlite, sqlite::SqliteRow, Connection, Done, Executor, IsolationLevel, Row,
+    SqliteConnection, SqlitePool, TransactionOptions,
 };
 use sqlx_test::new;
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -120,6 +122,87 @@ async fn it_can_describe_with_pragma() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_get_raw_values() -> anyhow::Result<()> {
+    use sqlx::{TypeInfo, ValueRef};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn
+        .fetch_one("SELECT 10 AS num, 'hello' AS str, NULL AS empty")
+        .await?;
+
+    let num = row.try_get_raw("num")?;
+    assert!(!num.is_null());
+    assert_eq!(num.type_info().name(), "INTEGER");
+
+    let str = row.try_get_raw(1)?;
+    assert!(!str.is_null());
+    assert_eq!(str.type_info().name(), "TEXT");
+
+    assert!(row.try_get_raw("empty")?.is_null());
+
+    assert!(matches!(
+        row.try_get_raw("missing"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_maps_rows_of_a_query_with_dynamic_arguments() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteArguments;
+    use sqlx::Arguments;
+
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Item {
+        id: i32,
+        name: String,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut args = SqliteArguments::default();
+    args.add(2_i32);
+    args.add("two");
+
+    let item: Item = sqlx::query_as_with("SELECT ? AS id, ? AS name", args)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        item,
+        Item {
+            id: 2,
+            name: "two".to_owned()
+        }
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_borrowed_slices() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let buffer = vec![0_u8, 1, 2, 3, 255];
+    let text = String::from("Hello, world!");
+
+    let (bytes, none, s): (Vec<u8>, Option<Vec<u8>>, String) = sqlx::query_as("SELECT ?, ?, ?")
+        .bind(&buffer[1..])
+        .bind(None::<&[u8]>)
+        .bind(&text[..5])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(bytes, &buffer[1..]);
+    assert_eq!(none, None);
+    assert_eq!(s, "Hello");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_binds_positional_parameters_issue_467() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -169,6 +252,381 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_keeps_the_pool_connection_after_commit() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut tx = pool.begin().await?;
+
+    tx.execute("CREATE TEMPORARY TABLE kept (id INTEGER)")
+        .await?;
+    tx.execute("INSERT INTO kept (id) VALUES (1)").await?;
+
+    let mut conn = match tx.commit_and_return().await? {
+        MaybePoolConnection::PoolConnection(conn) => conn,
+        MaybePoolConnection::Connection(_) => panic!("expected a pool connection"),
+    };
+
+    assert_eq!(pool.num_idle(), 0);
+
+    // a temporary table is only visible to the connection that created it
+    let (id,): (i32,) = sqlx::query_as("SELECT id FROM kept")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    Ok(())
+}
+
+async fn insert_acquired<'a, A>(conn: A, id: i32) -> anyhow::Result<()>
+where
+    A: sqlx::Acquire<'a, Database = Sqlite>,
+{
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO acquired (id) VALUES (?)")
+        .bind(id)
+        .execute(&mut tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+async fn count_acquired<'a, A>(conn: A) -> anyhow::Result<i64>
+where
+    A: sqlx::Acquire<'a, Database = Sqlite>,
+{
+    let mut conn = conn.acquire().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM acquired")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(count)
+}
+
+#[sqlx_macros::test]
+async fn it_accepts_anything_that_acquires_a_connection() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    pool.execute("CREATE TABLE acquired (id INTEGER)").await?;
+
+    insert_acquired(&pool, 1).await?;
+    assert_eq!(count_acquired(&pool).await?, 1);
+
+    let mut conn = pool.acquire().await?;
+
+    insert_acquired(&mut conn, 2).await?;
+    assert_eq!(count_acquired(&mut conn).await?, 2);
+
+    let mut tx = conn.begin().await?;
+
+    insert_acquired(&mut tx, 3).await?;
+    assert_eq!(count_acquired(&mut tx).await?, 3);
+
+    tx.rollback().await?;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE acquired (id INTEGER)").await?;
+
+    insert_acquired(&mut conn, 1).await?;
+    assert_eq!(count_acquired(&mut conn).await?, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_commits_or_rolls_back_a_transaction_closure() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE closure_tx (id INTEGER)")
+        .await?;
+
+    let id: i32 = conn
+        .transaction(|tx| {
+            Box::pin(async move {
+                tx.execute("INSERT INTO closure_tx (id) VALUES (1)").await?;
+
+                sqlx::query_scalar("SELECT id FROM closure_tx")
+                    .fetch_one(&mut *tx)
+                    .await
+            })
+        })
+        .await?;
+
+    assert_eq!(id, 1);
+
+    let res: Result<(), sqlx::Error> = conn
+        .transaction(|tx| {
+            Box::pin(async move {
+                tx.execute("INSERT INTO closure_tx (id) VALUES (2)").await?;
+
+                Err(sqlx::Error::RowNotFound)
+            })
+        })
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    // return the connection, with its temporary table, to the pool
+    drop(conn);
+
+    let count: i64 = pool
+        .transaction(|tx| {
+            Box::pin(async move {
+                sqlx::query_scalar("SELECT COUNT(*) FROM closure_tx")
+                    .fetch_one(&mut *tx)
+                    .await
+            })
+        })
+        .await?;
+
+    // the second insert was rolled back
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct SerializationFailure;
+
+impl std::fmt::Display for SerializationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("could not serialize access due to concurrent update")
+    }
+}
+
+impl std::error::Error for SerializationFailure {}
+
+impl sqlx::error::DatabaseError for SerializationFailure {
+    fn message(&self) -> &str {
+        "could not serialize access due to concurrent update"
+    }
+
+    fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+        Some("40001".into())
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+#[sqlx_macros::test]
+async fn it_retries_a_transaction_on_serialization_failure() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE retried_tx (id INTEGER)")
+        .await?;
+
+    let mut attempts = 0;
+
+    let count: i64 = conn
+        .transaction_retrying(3, Duration::from_millis(1), |tx| {
+            attempts += 1;
+            let fail = attempts < 3;
+
+            Box::pin(async move {
+                tx.execute("INSERT INTO retried_tx (id) VALUES (1)").await?;
+
+                if fail {
+                    return Err(SerializationFailure.into());
+                }
+
+                sqlx::query_scalar("SELECT COUNT(*) FROM retried_tx")
+                    .fetch_one(&mut *tx)
+                    .await
+            })
+        })
+        .await?;
+
+    // the inserts of the failed attempts were rolled back
+    assert_eq!(attempts, 3);
+    assert_eq!(count, 1);
+
+    // gives up after the last attempt
+    let mut attempts = 0;
+
+    let res: Result<(), _> = conn
+        .transaction_retrying(2, Duration::from_millis(1), |_| {
+            attempts += 1;
+
+            Box::pin(async move { Err(SerializationFailure.into()) })
+        })
+        .await;
+
+    assert!(res.unwrap_err().is_serialization_failure());
+    assert_eq!(attempts, 2);
+
+    // other errors are not retried
+    let mut attempts = 0;
+
+    let res: Result<(), _> = conn
+        .transaction_retrying(3, Duration::from_millis(1), |_| {
+            attempts += 1;
+
+            Box::pin(async move { Err(sqlx::Error::RowNotFound) })
+        })
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+    assert_eq!(attempts, 1);
+
+    // the function is never run without any attempts
+    let mut attempts = 0;
+
+    let res: Result<(), _> = conn
+        .transaction_retrying(0, Duration::from_millis(1), |_| {
+            attempts += 1;
+
+            Box::pin(async move { Ok(()) })
+        })
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+    assert_eq!(attempts, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_a_transaction_begun_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let serializable = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    let mut attempts = 0;
+
+    let value: i64 = conn
+        .transaction_retrying_with(serializable, 2, Duration::from_millis(1), |tx| {
+            attempts += 1;
+            let fail = attempts < 2;
+
+            Box::pin(async move {
+                if fail {
+                    return Err(SerializationFailure.into());
+                }
+
+                sqlx::query_scalar("SELECT 1").fetch_one(&mut *tx).await
+            })
+        })
+        .await?;
+
+    assert_eq!(attempts, 2);
+    assert_eq!(value, 1);
+
+    // the options are checked when beginning the transaction
+    let mut attempts = 0;
+
+    let res: Result<(), _> = conn
+        .transaction_retrying_with(
+            TransactionOptions::new().read_only(true),
+            2,
+            Duration::from_millis(1),
+            |_| {
+                attempts += 1;
+
+                Box::pin(async move { Ok(()) })
+            },
+        )
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+    assert_eq!(attempts, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_classifies_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE classified (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("INSERT INTO classified (id) VALUES (1)")
+        .await?;
+
+    let err = conn
+        .execute("INSERT INTO classified (id) VALUES (1)")
+        .await
+        .unwrap_err();
+
+    assert!(err.is_constraint_violation());
+    assert!(!err.is_retryable());
+    assert!(!err.is_connection_error());
+
+    let err = conn.execute("SELEC 1").await.unwrap_err();
+
+    assert!(!err.is_constraint_violation());
+    assert!(!err.is_retryable());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_the_last_insert_id() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE generated (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let done = sqlx::query("INSERT INTO generated (name) VALUES ('a'), ('b')")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 2);
+    assert_eq!(done.last_insert_id(), 2);
+    assert_eq!(done.last_insert_rowid(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_changes_on_the_connection() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE changed (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let total = conn.total_changes();
+
+    conn.execute("INSERT INTO changed (name) VALUES ('a'), ('b'), ('c')")
+        .await?;
+
+    assert_eq!(conn.last_insert_rowid(), 3);
+    assert_eq!(conn.changes(), 3);
+
+    conn.execute("UPDATE changed SET name = 'z' WHERE id < 3")
+        .await?;
+
+    assert_eq!(conn.changes(), 2);
+    assert_eq!(conn.total_changes(), total + 5);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_opens_in_memory() -> anyhow::Result<()> {
     // If the filename is ":memory:", then a private, temporary in-memory database
@@ -300,6 +758,55 @@ SELECT id, other FROM users WHERE id = last_insert_rowid();
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_executes_many_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let rows_affected: Vec<u64> = conn
+        .execute_many(
+            r#"
+INSERT INTO users (id) VALUES (1), (2), (3);
+UPDATE users SET id = id + 10 WHERE id > 1;
+DELETE FROM users;
+            "#,
+        )
+        .map_ok(|done| done.rows_affected())
+        .try_collect()
+        .await?;
+
+    assert_eq!(rows_affected, [3, 2, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_in_chunks() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let sizes: Vec<usize> = conn
+        .fetch_chunked(
+            "SELECT 1 UNION SELECT 2 UNION SELECT 3 UNION SELECT 4 UNION SELECT 5",
+            2,
+        )
+        .map_ok(|chunk| chunk.len())
+        .try_collect()
+        .await?;
+
+    assert_eq!(sizes, [2, 2, 1]);
+
+    let chunks: Vec<Vec<SqliteRow>> = sqlx::query("SELECT 1 WHERE 1 = 0")
+        .fetch_chunked(&mut conn, 2)
+        .try_collect()
+        .await?;
+
+    assert!(chunks.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_interleaves_reads_and_writes() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -388,3 +895,427 @@ async fn it_caches_statements() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_clear_a_disabled_statement_cache() -> anyhow::Result<()> {
+    let options: SqliteConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+    let mut conn = SqliteConnection::connect_with(&options.statement_cache_capacity(0)).await?;
+
+    let val: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(5, val);
+    assert_eq!(0, conn.cached_statements_size());
+
+    conn.clear_cached_statements().await?;
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_an_in_memory_database_within_a_pool() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .min_connections(2)
+        .max_connections(2)
+        .connect("sqlite::memory:")
+        .await?;
+
+    let mut first = pool.acquire().await?;
+    let mut second = pool.acquire().await?;
+
+    first
+        .execute("CREATE TABLE shared (id INTEGER PRIMARY KEY); INSERT INTO shared VALUES (1)")
+        .await?;
+
+    let id: i32 = sqlx::query_scalar("SELECT id FROM shared")
+        .fetch_one(&mut second)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    // another pool sees a different database
+    let mut other = SqliteConnection::connect("sqlite::memory:").await?;
+    let res = other.execute("SELECT id FROM shared").await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_pragmas_from_connect_options() -> anyhow::Result<()> {
+    let options: SqliteConnectOptions =
+        "sqlite::memory:?synchronous=normal&foreign_keys=off&busy_timeout=250".parse()?;
+
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    let synchronous: i32 = sqlx::query_scalar("PRAGMA synchronous")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let foreign_keys: i32 = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let busy_timeout: i32 = sqlx::query_scalar("PRAGMA busy_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // 1 = NORMAL
+    assert_eq!(synchronous, 1);
+    assert_eq!(foreign_keys, 0);
+    assert_eq!(busy_timeout, 250);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_widen_numeric_values() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn
+        .fetch_one("SELECT 10, 2.5, NULL, 'text', 9007199254740993")
+        .await?;
+
+    // an INTEGER is not compatible with `f64` unless it is widened
+    assert!(row.try_get::<f64, _>(0).is_err());
+    assert_eq!(row.try_get_lossy::<f64, _>(0)?, 10.0);
+    assert_eq!(row.try_get_lossy::<i64, _>(0)?, 10);
+    assert_eq!(row.try_get_lossy::<f64, _>(1)?, 2.5);
+    assert_eq!(row.try_get_lossy::<Option<f64>, _>(2)?, None);
+
+    // incompatible types and integers that would be rounded are still an error
+    assert!(row.try_get_lossy::<f64, _>(3).is_err());
+    assert!(row.try_get_lossy::<i64, _>(1).is_err());
+    assert!(row.try_get_lossy::<f64, _>(4).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_with_query_builder() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut builder = sqlx::QueryBuilder::<Sqlite>::new("SELECT id FROM tweet WHERE id IN (");
+
+    let mut separated = builder.separated(", ");
+    for id in &[2_i64, 1, 100] {
+        separated.push_bind(*id);
+    }
+
+    builder.push(") ORDER BY id");
+
+    let rows = builder.build().fetch_all(&mut conn).await?;
+    let ids: Vec<i64> = rows.iter().map(|row| row.get(0)).collect();
+
+    assert_eq!(ids, [1]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?; // transaction
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(1_i32)
+        .execute(&mut tx)
+        .await?;
+
+    let mut tx2 = tx.begin().await?; // savepoint
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(2_i32)
+        .execute(&mut tx2)
+        .await?;
+
+    let mut tx3 = tx2.begin().await?; // nested savepoint
+
+    sqlx::query("INSERT INTO users (id) VALUES (?)")
+        .bind(3_i32)
+        .execute(&mut tx3)
+        .await?;
+
+    tx3.rollback().await?; // undo only the innermost insert
+    tx2.commit().await?; // release the savepoint into the transaction
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    tx.rollback().await?;
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_options() -> anyhow::Result<()> {
+    use sqlx::{Error, IsolationLevel, TransactionOptions};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    // SQLite is always serializable
+    let mut tx = conn
+        .begin_with(TransactionOptions::new().isolation_level(IsolationLevel::Serializable))
+        .await?;
+
+    // options cannot be applied to a savepoint
+    let options = TransactionOptions::new().isolation_level(IsolationLevel::ReadCommitted);
+
+    assert!(matches!(
+        tx.begin_with(options).await,
+        Err(Error::Configuration(_))
+    ));
+
+    tx.rollback().await?;
+
+    let options = TransactionOptions::new().read_only(true);
+
+    assert!(matches!(
+        conn.begin_with(options).await,
+        Err(Error::Configuration(_))
+    ));
+
+    // the connection is not left in a transaction by a rejected `begin_with`
+    conn.begin().await?.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_waiting_for_a_query() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let res = sqlx::query(
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c LIMIT 1000000) \
+         SELECT count(*) FROM c",
+    )
+    .timeout(Duration::from_millis(1))
+    .fetch_one(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)));
+
+    // a query that completes in time is unaffected, and the connection can still be used
+    let val: i32 = sqlx::query_scalar("SELECT 1")
+        .timeout(Duration::from_secs(10))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(val, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "migrate")]
+#[sqlx_macros::test]
+async fn it_reverts_migrations() -> anyhow::Result<()> {
+    use sqlx::migrate::{MigrateError, Migrator};
+    use std::path::Path;
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations")).await?;
+
+    let reversible: Vec<_> = migrator.iter().map(|m| m.is_reversible()).collect();
+    assert_eq!(reversible, vec![false, true, true]);
+
+    migrator.run(&mut conn).await?;
+
+    const TABLES: &str = "SELECT name FROM sqlite_master \
+         WHERE type = 'table' AND name IN ('accounts', 'posts', 'comments') ORDER BY name";
+
+    assert_eq!(
+        sqlx::query_scalar::<_, String>(TABLES)
+            .fetch_all(&mut conn)
+            .await?,
+        vec!["accounts", "comments", "posts"]
+    );
+
+    // revert everything newer than the first migration
+    migrator.undo(&mut conn, 1).await?;
+
+    assert_eq!(
+        sqlx::query_scalar::<_, String>(TABLES)
+            .fetch_all(&mut conn)
+            .await?,
+        vec!["accounts"]
+    );
+
+    // the first migration has no down script
+    let res = migrator.undo(&mut conn, 0).await;
+    assert!(matches!(res, Err(MigrateError::Irreversible(1))));
+
+    assert_eq!(
+        sqlx::query_scalar::<_, String>(TABLES)
+            .fetch_all(&mut conn)
+            .await?,
+        vec!["accounts"]
+    );
+
+    // reverted migrations can be applied again
+    migrator.run(&mut conn).await?;
+
+    assert_eq!(
+        sqlx::query_scalar::<_, String>(TABLES)
+            .fetch_all(&mut conn)
+            .await?,
+        vec!["accounts", "comments", "posts"]
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "migrate")]
+#[sqlx_macros::test]
+async fn it_detects_modified_migrations() -> anyhow::Result<()> {
+    use sqlx::migrate::{MigrateError, Migrator};
+    use std::path::Path;
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations")).await?;
+
+    migrator.run(&mut conn).await?;
+
+    // pretend the first migration was modified after it was applied
+    sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 1")
+        .execute(&mut conn)
+        .await?;
+
+    let res = migrator.run(&mut conn).await;
+    assert!(matches!(res, Err(MigrateError::VersionMismatch(1))));
+
+    migrator.set_ignore_checksums(true).run(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_a_prepared_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let statement = conn.prepare("SELECT ?1 + 1 AS value").await?;
+
+    let cached = conn.cached_statements_size();
+
+    assert_eq!(statement.sql(), "SELECT ?1 + 1 AS value");
+    assert_eq!(
+        statement.info().column_names().collect::<Vec<_>>(),
+        ["value"]
+    );
+
+    for i in 0..5_i32 {
+        let value: i32 = statement
+            .query_scalar()
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+    }
+
+    // every execution used the same cached statement
+    assert_eq!(cached + 1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_counts_bound_arguments() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteArguments;
+    use sqlx::Arguments;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let statement = conn.prepare("SELECT ?1, ?2").await?;
+    let parameters = statement.parameters().map(|p| p.either(|p| p.len(), |n| n));
+
+    let mut arguments = SqliteArguments::default();
+    assert!(arguments.is_empty());
+
+    arguments.add(1_i32);
+    assert_eq!(arguments.len(), 1);
+    assert_ne!(parameters, Some(arguments.len()));
+
+    arguments.add("two");
+    assert_eq!(parameters, Some(arguments.len()));
+
+    let (a, b): (i32, String) = sqlx::query_as_with("SELECT ?1, ?2", arguments)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((a, &*b), (1, "two"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_number_of_columns_of_a_row() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn
+        .fetch_one("SELECT 1 AS a, 'two' AS b, NULL AS c")
+        .await?;
+
+    assert_eq!(row.len(), 3);
+    assert_eq!(row.len(), row.columns().len());
+    assert!(!row.is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "migrate")]
+#[sqlx_macros::test]
+async fn it_tracks_migrations_in_a_custom_table() -> anyhow::Result<()> {
+    use sqlx::migrate::Migrator;
+    use std::path::Path;
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.execute("ATTACH DATABASE ':memory:' AS migrations")
+        .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations")).await?;
+
+    migrator
+        .set_table_name("migrations.__applied")
+        .run(&mut conn)
+        .await?;
+
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM migrations.__applied ORDER BY version")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(applied, vec![1, 2, 3]);
+
+    let default_table: i32 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = '_sqlx_migrations'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(default_table, 0);
+
+    // running again only validates the applied migrations
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
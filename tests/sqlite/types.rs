@@ -11,6 +11,27 @@ test_type!(i32(Sqlite, "94101" == 94101_i32));
 
 test_type!(i64(Sqlite, "9358295312" == 9358295312_i64));
 
+#[cfg(feature = "macros")]
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct UserId(i64);
+
+#[cfg(feature = "macros")]
+test_type!(transparent<UserId>(Sqlite,
+    "0" == UserId(0),
+    "9358295312" == UserId(9358295312)
+));
+
+#[cfg(feature = "macros")]
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Wrapper<T>(T);
+
+#[cfg(feature = "macros")]
+test_type!(transparent_generic<Wrapper<String>>(Sqlite,
+    "'this is foo'" == Wrapper("this is foo".to_owned())
+));
+
 // NOTE: This behavior can be surprising. Floating-point parameters are widening to double which can
 //       result in strange rounding.
 test_type!(f32(Sqlite, "3.1410000324249268" == 3.141f32 as f64 as f32));
@@ -49,3 +70,39 @@ mod chrono {
         "datetime('2016-11-08T03:50:23-05:00')" == FixedOffset::west(5 * 3600).ymd(2016, 11, 08).and_hms(3, 50, 23)
     ));
 }
+
+#[cfg(feature = "json")]
+mod json_tests {
+    use super::*;
+    use serde_json::{json, Value as JsonValue};
+    use sqlx::types::Json;
+
+    test_type!(json<JsonValue>(Sqlite,
+        "'\"Hello, World\"'" == json!("Hello, World"),
+        "'[\"Hello\",\"World!\"]'" == json!(["Hello", "World!"])
+    ));
+
+    #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+    struct Friend {
+        name: String,
+        age: u32,
+    }
+
+    test_type!(json_struct<Json<Friend>>(Sqlite,
+        "'{\"name\":\"Joe\",\"age\":33}'" == Json(Friend { name: "Joe".to_string(), age: 33 })
+    ));
+
+    #[sqlx_macros::test]
+    async fn it_fails_to_decode_invalid_json() -> anyhow::Result<()> {
+        use sqlx::Row;
+
+        let mut conn = sqlx_test::new::<Sqlite>().await?;
+        let row = sqlx::query("SELECT 'not json'")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert!(row.try_get::<Json<Friend>, _>(0).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let ids = vec![1_i32];
+    let _query = sqlx::query!("select 1 where 1 in ($1..) or 1 = $1", ids);
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = sqlx::query_scalar!("select id, name from accounts");
+}
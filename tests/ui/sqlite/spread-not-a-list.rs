@@ -0,0 +1,4 @@
+fn main() {
+    let id = 1_i64;
+    let _query = sqlx::query!("select id from accounts where id in (?..)", id);
+}